@@ -0,0 +1,10 @@
+//! Tags describing which kind of build produced a startup record.
+
+/// Build environment of packaged release builds opened outside a development tool.
+pub const RELEASE_ENVIRONMENT: &str = "release";
+/// Build environment of debug builds opened outside a development tool.
+pub const DEBUG_ENVIRONMENT: &str = "debug";
+/// Build environment of runs started by a development tool such as `trunk` or `tauri dev`.
+pub const DEV_ENVIRONMENT: &str = "dev";
+/// Build environment of records stored before build environments were captured.
+pub const UNKNOWN_ENVIRONMENT: &str = "unknown";
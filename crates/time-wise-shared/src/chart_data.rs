@@ -0,0 +1,364 @@
+//! Startup chart aggregation, so the dashboard and the backend bucket and label runs alike.
+//!
+//! Time labels are injected by the caller, since only the frontend has a browser locale.
+
+use serde::Serialize;
+
+/// Slowest startup still counted as fast, in milliseconds.
+pub const FAST_START_MAX_MS: u64 = 500;
+/// Slowest startup still counted as steady, in milliseconds.
+pub const STEADY_START_MAX_MS: u64 = 1_500;
+
+const MS_PER_MINUTE: i64 = 60_000;
+const MS_PER_DAY: i64 = 24 * 60 * MS_PER_MINUTE;
+
+/// A startup run reduced to what the charts need.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ChartSample {
+    pub recorded_at_ms: u64,
+    pub duration_ms: u64,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+/// Data point backing the histogram chart.
+pub struct ChartPoint {
+    pub label: String,
+    /// Bar value: a duration in recent mode, a run count in distribution mode.
+    pub value: u64,
+}
+
+/// Total duration and number of runs in one speed bucket.
+#[derive(Clone, Copy, Debug, Default, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryBucket {
+    pub total_ms: u64,
+    pub runs: usize,
+}
+
+impl CategoryBucket {
+    fn add(&mut self, duration_ms: u64) {
+        self.total_ms += duration_ms;
+        self.runs += 1;
+    }
+
+    /// Mean duration of the bucket, or `None` without runs.
+    pub fn average_ms(&self) -> Option<u64> {
+        (self.runs > 0).then(|| self.total_ms / self.runs as u64)
+    }
+}
+
+/// Speed bucket a startup falls into.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpeedCategory {
+    Fast,
+    Steady,
+    Slow,
+}
+
+impl SpeedCategory {
+    /// Bucket for a run of `duration_ms`, or `None` for zero-duration placeholders.
+    pub fn of(duration_ms: u64) -> Option<Self> {
+        match duration_ms {
+            0 => None,
+            1..=FAST_START_MAX_MS => Some(Self::Fast),
+            ms if ms <= STEADY_START_MAX_MS => Some(Self::Steady),
+            _ => Some(Self::Slow),
+        }
+    }
+}
+
+/// Runs split into fast, steady, and slow buckets.
+#[derive(Clone, Copy, Debug, Default, Serialize, PartialEq, Eq)]
+pub struct CategoryTotals {
+    pub fast: CategoryBucket,
+    pub steady: CategoryBucket,
+    pub slow: CategoryBucket,
+}
+
+/// Builds points from the latest `count` samples (newest first), oldest on the left, padding with placeholders.
+///
+/// Placeholders (label "-", value 0) are presentation-only and never stand in for real runs.
+pub fn chart_points(
+    samples: &[ChartSample],
+    count: usize,
+    label: impl Fn(u64) -> String,
+) -> Vec<ChartPoint> {
+    let mut points: Vec<ChartPoint> = samples
+        .iter()
+        .take(count)
+        .map(|sample| ChartPoint {
+            label: label(sample.recorded_at_ms),
+            value: sample.duration_ms,
+        })
+        .collect();
+
+    points.reverse();
+
+    while points.len() < count {
+        points.insert(
+            0,
+            ChartPoint {
+                label: "-".to_string(),
+                value: 0,
+            },
+        );
+    }
+
+    points
+}
+
+/// Builds `bucket_count` equal-width duration buckets holding the number of runs in each.
+pub fn distribution_points(samples: &[ChartSample], bucket_count: usize) -> Vec<ChartPoint> {
+    let bucket_count = bucket_count.max(1);
+    let max_ms = samples
+        .iter()
+        .map(|sample| sample.duration_ms)
+        .max()
+        .unwrap_or(0);
+    let width = max_ms.div_ceil(bucket_count as u64).max(1);
+
+    let mut counts = vec![0u64; bucket_count];
+    for sample in samples {
+        let index = ((sample.duration_ms / width) as usize).min(bucket_count - 1);
+        counts[index] += 1;
+    }
+
+    counts
+        .into_iter()
+        .enumerate()
+        .map(|(index, count)| ChartPoint {
+            label: format_duration_compact(index as u64 * width),
+            value: count,
+        })
+        .collect()
+}
+
+/// Sorts runs into the fast, steady, and slow buckets.
+///
+/// Zero-duration entries are skipped: no real startup measures 0 ms, so they can only be padding.
+pub fn category_totals(samples: &[ChartSample]) -> CategoryTotals {
+    let mut totals = CategoryTotals::default();
+    for sample in samples {
+        let bucket = match SpeedCategory::of(sample.duration_ms) {
+            Some(SpeedCategory::Fast) => &mut totals.fast,
+            Some(SpeedCategory::Steady) => &mut totals.steady,
+            Some(SpeedCategory::Slow) => &mut totals.slow,
+            None => continue,
+        };
+        bucket.add(sample.duration_ms);
+    }
+    totals
+}
+
+/// Total milliseconds saved by every run compared with the slowest one.
+///
+/// The slowest run contributes nothing, so a single run saves 0; zero-duration placeholders are skipped.
+pub fn time_saved_vs_worst(samples: &[ChartSample]) -> u64 {
+    let durations = samples
+        .iter()
+        .map(|sample| sample.duration_ms)
+        .filter(|duration_ms| *duration_ms > 0);
+    let Some(worst_ms) = durations.clone().max() else {
+        return 0;
+    };
+    durations.map(|duration_ms| worst_ms - duration_ms).sum()
+}
+
+/// Least-squares slope of `values` against their index, or `None` with fewer than two values.
+pub fn linear_slope(values: &[f64]) -> Option<f64> {
+    if values.len() < 2 {
+        return None;
+    }
+    let n = values.len() as f64;
+    let mean_x = (n - 1.0) / 2.0;
+    let mean_y = values.iter().sum::<f64>() / n;
+    let (covariance, variance) =
+        values
+            .iter()
+            .enumerate()
+            .fold((0.0, 0.0), |(covariance, variance), (index, value)| {
+                let dx = index as f64 - mean_x;
+                (covariance + dx * (value - mean_y), variance + dx * dx)
+            });
+    Some(covariance / variance)
+}
+
+/// Latest runs the forecast fits its trend line to.
+pub const FORECAST_WINDOW: usize = 10;
+/// Fewest runs the forecast needs before it projects anything.
+pub const FORECAST_MIN_RUNS: usize = 4;
+/// Fastest startup a forecast may predict, so a steep improvement never projects to zero.
+pub const FORECAST_FLOOR_MS: u64 = 50;
+
+/// Projects the next run's duration one step past the trend line of the latest `n` runs.
+///
+/// `samples` are newest first; zero-duration placeholders are skipped, and fewer than [`FORECAST_MIN_RUNS`] runs yield `None`.
+pub fn forecast_next(samples: &[ChartSample], n: usize) -> Option<u64> {
+    let mut durations: Vec<f64> = samples
+        .iter()
+        .filter(|sample| sample.duration_ms > 0)
+        .take(n)
+        .map(|sample| sample.duration_ms as f64)
+        .collect();
+    durations.reverse();
+    if durations.len() < FORECAST_MIN_RUNS {
+        return None;
+    }
+    let slope = linear_slope(&durations)?;
+    let count = durations.len() as f64;
+    let mean = durations.iter().sum::<f64>() / count;
+    // The fitted line passes through the mean at the middle index; the next run sits at index `count`.
+    let projected = mean + slope * (count - (count - 1.0) / 2.0);
+    Some((projected.round().max(0.0) as u64).max(FORECAST_FLOOR_MS))
+}
+
+/// Decimal places shown for durations of a second or more unless configured otherwise.
+pub const DEFAULT_DURATION_PRECISION: u32 = 2;
+
+pub fn format_duration(ms: u64) -> String {
+    format_duration_with_precision(ms, DEFAULT_DURATION_PRECISION)
+}
+
+/// Formats whole milliseconds below a second, and seconds with `precision` decimals above.
+pub fn format_duration_with_precision(ms: u64, precision: u32) -> String {
+    if ms >= 1_000 {
+        format!("{:.*} s", precision as usize, ms as f64 / 1_000.0)
+    } else {
+        format!("{ms} ms")
+    }
+}
+
+/// Compact human-readable duration.
+pub fn format_duration_compact(ms: u64) -> String {
+    if ms == 0 {
+        "0".to_string()
+    } else if ms >= 60_000 {
+        format!("{:.1} m", ms as f64 / 60_000.0)
+    } else if ms >= 1_000 {
+        format!("{:.1} s", ms as f64 / 1_000.0)
+    } else {
+        format!("{ms} ms")
+    }
+}
+
+/// Formats the timestamp as a 24-hour "HH:MM:SS" time, `offset_minutes` east of UTC.
+pub fn format_clock_time(ms: u64, offset_minutes: i32) -> String {
+    let local_ms = ms.min(i64::MAX as u64) as i64 + i64::from(offset_minutes) * MS_PER_MINUTE;
+    let seconds = local_ms.rem_euclid(MS_PER_DAY) / 1_000;
+    format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3_600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR_MS: u64 = 60 * 60 * 1_000;
+
+    fn samples(durations: &[u64]) -> Vec<ChartSample> {
+        durations
+            .iter()
+            .enumerate()
+            .map(|(index, duration_ms)| ChartSample {
+                recorded_at_ms: (10 - index as u64) * HOUR_MS,
+                duration_ms: *duration_ms,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn chart_points_label_injected_timestamps_oldest_first() {
+        let points = chart_points(&samples(&[100, 200, 300]), 4, |ms| {
+            format_clock_time(ms, 90)
+        });
+        assert_eq!(
+            points,
+            vec![
+                ChartPoint {
+                    label: "-".to_string(),
+                    value: 0,
+                },
+                ChartPoint {
+                    label: "09:30:00".to_string(),
+                    value: 300,
+                },
+                ChartPoint {
+                    label: "10:30:00".to_string(),
+                    value: 200,
+                },
+                ChartPoint {
+                    label: "11:30:00".to_string(),
+                    value: 100,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn distribution_points_count_every_run() {
+        let counts: Vec<_> = distribution_points(&samples(&[100, 250, 500, 990, 1_000]), 4)
+            .into_iter()
+            .map(|point| point.value)
+            .collect();
+        assert_eq!(counts, vec![1, 1, 1, 2]);
+    }
+
+    #[test]
+    fn category_totals_bucket_runs_and_skip_placeholders() {
+        let totals = category_totals(&samples(&[0, 300, 500, 900, 2_000]));
+        assert_eq!(
+            totals.fast,
+            CategoryBucket {
+                total_ms: 800,
+                runs: 2
+            }
+        );
+        assert_eq!(totals.steady.average_ms(), Some(900));
+        assert_eq!(totals.slow.runs, 1);
+        assert_eq!(category_totals(&[]).fast.average_ms(), None);
+    }
+
+    #[test]
+    fn time_saved_sums_the_gap_to_the_slowest_run() {
+        assert_eq!(time_saved_vs_worst(&samples(&[400, 1_000, 0, 700])), 900);
+        assert_eq!(time_saved_vs_worst(&samples(&[1_000])), 0);
+        assert_eq!(time_saved_vs_worst(&[]), 0);
+    }
+
+    #[test]
+    fn linear_slope_fits_evenly_spaced_values() {
+        assert_eq!(linear_slope(&[1.0, 3.0, 5.0, 7.0]), Some(2.0));
+        assert_eq!(linear_slope(&[4.0, 4.0, 4.0]), Some(0.0));
+        assert_eq!(linear_slope(&[4.0]), None);
+    }
+
+    #[test]
+    fn forecast_follows_the_trend_direction() {
+        // Newest first: durations grew from 400 ms to 1 s, so the next run should be slower still.
+        let slowing = samples(&[1_000, 850, 700, 550, 400]);
+        assert_eq!(forecast_next(&slowing, 10), Some(1_150));
+
+        let improving = samples(&[400, 550, 700, 850, 1_000]);
+        assert!(forecast_next(&improving, 10).is_some_and(|ms| ms < 400));
+
+        let collapsing = samples(&[100, 2_000, 4_000, 6_000]);
+        assert_eq!(forecast_next(&collapsing, 10), Some(FORECAST_FLOOR_MS));
+    }
+
+    #[test]
+    fn forecast_needs_enough_runs() {
+        assert_eq!(forecast_next(&samples(&[500, 0, 600, 700]), 10), None);
+        assert_eq!(forecast_next(&samples(&[500, 600, 700, 800, 900]), 3), None);
+        assert_eq!(forecast_next(&[], 10), None);
+    }
+
+    #[test]
+    fn clock_time_wraps_around_midnight() {
+        assert_eq!(format_clock_time(0, -60), "23:00:00");
+        assert_eq!(format_clock_time(23 * HOUR_MS + 61_000, 60), "00:01:01");
+    }
+}
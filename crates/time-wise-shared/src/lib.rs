@@ -0,0 +1,9 @@
+//! Domain types and helpers used by both the Leptos frontend and the Tauri backend.
+//!
+//! Everything here is plain std and serde, so it builds for both the wasm and native targets.
+
+pub mod build_environment;
+pub mod chart_data;
+pub mod preferences;
+pub mod stats;
+pub mod usage_rounding;
@@ -0,0 +1,442 @@
+//! User preferences as persisted by the backend and read by the dashboard, with their defaults.
+//!
+//! Both sides deserialize with `#[serde(default)]`, so documents written by older builds keep working.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::chart_data::DEFAULT_DURATION_PRECISION;
+use crate::usage_rounding::UsageRounding;
+
+/// Which field names an app in the dashboard usage list.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TileLabelSource {
+    /// The process name.
+    #[default]
+    Name,
+    /// The window title when one was captured, falling back to the process name.
+    WindowTitle,
+}
+
+/// Order of the apps in the dashboard usage list.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum UsageSort {
+    /// Running apps first, then by total time.
+    #[default]
+    ActiveFirst,
+    /// Most total time first.
+    TotalTime,
+    /// Most recently seen first.
+    Recent,
+    /// Alphabetically by name.
+    Name,
+}
+
+impl UsageSort {
+    pub const ALL: [UsageSort; 4] = [
+        UsageSort::ActiveFirst,
+        UsageSort::TotalTime,
+        UsageSort::Recent,
+        UsageSort::Name,
+    ];
+
+    /// Stored preference value, matching the serde representation.
+    pub fn key(self) -> &'static str {
+        match self {
+            UsageSort::ActiveFirst => "active_first",
+            UsageSort::TotalTime => "total_time",
+            UsageSort::Recent => "recent",
+            UsageSort::Name => "name",
+        }
+    }
+
+    /// Parses a stored key, falling back to the default order.
+    pub fn from_key(key: &str) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|sort| sort.key() == key)
+            .unwrap_or_default()
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            UsageSort::ActiveFirst => "Active first",
+            UsageSort::TotalTime => "Total time",
+            UsageSort::Recent => "Recently used",
+            UsageSort::Name => "Name",
+        }
+    }
+}
+
+/// Which moment ends a recorded startup measurement.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StartupMilestone {
+    /// The backend finished initializing.
+    #[default]
+    ProcessReady,
+    /// The dashboard rendered for the first time.
+    FirstPaint,
+}
+
+/// Where a recorded startup measurement starts counting.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StartupMeasurement {
+    /// From the moment this process started.
+    #[default]
+    Process,
+    /// From system boot, for launches started by launch-on-boot.
+    SinceBoot,
+}
+
+/// How often data is backed up automatically.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BackupSchedule {
+    #[default]
+    Off,
+    Daily,
+    Weekly,
+}
+
+impl BackupSchedule {
+    pub const ALL: [BackupSchedule; 3] = [
+        BackupSchedule::Off,
+        BackupSchedule::Daily,
+        BackupSchedule::Weekly,
+    ];
+
+    /// Stored preference value, matching the serde representation.
+    pub fn key(self) -> &'static str {
+        match self {
+            BackupSchedule::Off => "off",
+            BackupSchedule::Daily => "daily",
+            BackupSchedule::Weekly => "weekly",
+        }
+    }
+
+    /// Parses a stored key, falling back to no backups.
+    pub fn from_key(key: &str) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|schedule| schedule.key() == key)
+            .unwrap_or_default()
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            BackupSchedule::Off => "Off",
+            BackupSchedule::Daily => "Daily",
+            BackupSchedule::Weekly => "Weekly",
+        }
+    }
+}
+
+/// First day of the week for weekly summaries.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WeekStart {
+    #[default]
+    Monday,
+    Sunday,
+}
+
+impl WeekStart {
+    pub const ALL: [WeekStart; 2] = [WeekStart::Monday, WeekStart::Sunday];
+
+    /// Stored preference value, matching the serde representation.
+    pub fn key(self) -> &'static str {
+        match self {
+            WeekStart::Monday => "monday",
+            WeekStart::Sunday => "sunday",
+        }
+    }
+
+    /// Parses a stored key, falling back to Monday.
+    pub fn from_key(key: &str) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|start| start.key() == key)
+            .unwrap_or_default()
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            WeekStart::Monday => "Monday",
+            WeekStart::Sunday => "Sunday",
+        }
+    }
+
+    /// Weekday index of the first day, counting Monday as 0.
+    pub fn weekday_index(self) -> usize {
+        match self {
+            WeekStart::Monday => 0,
+            WeekStart::Sunday => 6,
+        }
+    }
+}
+
+/// `avatar` value that shows the app icon instead of a letter.
+pub const AVATAR_ICON: &str = "icon";
+
+/// Category that apps without an entry in `app_categories` are grouped under.
+pub const UNCATEGORIZED: &str = "Other";
+
+/// Every persisted preference with its default value.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct Preferences {
+    /// Notify when a pinned app has not been opened by `habit_reminder_minutes`.
+    pub habit_reminders: bool,
+    /// Minutes after local midnight at which habit reminders fire.
+    pub habit_reminder_minutes: u32,
+    /// Apps the user wants to keep an eye on.
+    pub pinned_apps: Vec<String>,
+    /// Use native vibrancy (macOS) or acrylic (Windows) behind the dashboard.
+    pub window_effects: bool,
+    /// User-assigned category per app name.
+    pub app_categories: BTreeMap<String, String>,
+    /// Bars shown by the startup chart in recent-runs mode.
+    pub recent_chart_bars: u32,
+    /// Buckets shown by the startup chart in distribution mode.
+    pub distribution_chart_bars: u32,
+    /// Decimal places shown for startup times of a second or more.
+    pub duration_precision: u32,
+    /// Hide well-known system processes from usage tracking.
+    pub builtin_blocklist: bool,
+    /// Additional process names the user never wants tracked.
+    pub blocked_apps: Vec<String>,
+    /// Track only the apps in `allowed_apps`.
+    pub allowlist_mode: bool,
+    /// Apps tracked while `allowlist_mode` is on.
+    pub allowed_apps: Vec<String>,
+    /// Play a short sound when tracking is paused or resumed.
+    pub sound_feedback: bool,
+    /// Store battery level and thermal state alongside each startup measurement.
+    pub capture_environment: bool,
+    /// Store the CPU time used before ready with each startup, to spot contention.
+    pub capture_cpu_time: bool,
+    /// Count runs that ended without a graceful exit, shown under Diagnostics.
+    pub track_unclean_exits: bool,
+    /// Startups faster than this many milliseconds are not recorded; zero records every run.
+    pub min_recordable_ms: u64,
+    /// Record startup times and show the startup card; off turns the app into a usage-only tracker.
+    pub show_startup_metrics: bool,
+    /// Moment at which a startup measurement stops.
+    pub startup_milestone: StartupMilestone,
+    /// Whether a launch-on-boot startup counts from system boot instead of process start.
+    pub startup_measurement: StartupMeasurement,
+    /// Consecutive polls a process must survive before it shows up in usage.
+    pub min_observed_polls: u32,
+    /// Record apps under one name across platforms, e.g. "Code" for "Code.exe".
+    pub canonical_app_names: bool,
+    /// App sessions shorter than this many seconds are dropped from usage; zero counts every session.
+    pub min_session_seconds: u32,
+    /// Minutes an inactive app stays listed in usage before it is hidden.
+    pub inactive_display_minutes: u32,
+    /// Poll on wall-clock multiples of the poll interval instead of drifting with each sleep.
+    pub align_polls: bool,
+    /// Notify when an opted-in app is reopened after a long break.
+    pub welcome_back: bool,
+    /// Minutes an app must have been gone before reopening it counts as a long break.
+    pub welcome_back_minutes: u32,
+    /// Apps that get welcome back notifications.
+    pub welcome_back_apps: Vec<String>,
+    /// Local hour (0–23) at which a new day starts for every per-day computation.
+    pub day_rollover_hour: u32,
+    /// Skip every network request, even for features that are opted in.
+    pub offline_mode: bool,
+    /// Opt in to comparing startup times with an anonymized community baseline.
+    pub community_comparison: bool,
+    /// HTTP endpoint serving the community baseline.
+    pub community_endpoint: String,
+    /// Check `update_endpoint` for a newer version when the settings window opens.
+    pub update_check: bool,
+    /// HTTP endpoint serving the latest version as plain text.
+    pub update_endpoint: String,
+    /// Daily usage budget in minutes per app name.
+    pub app_budgets: BTreeMap<String, u32>,
+    /// Label usage tiles with the process name or the window title.
+    pub tile_label_source: TileLabelSource,
+    /// Order of the apps in the dashboard usage list.
+    pub usage_sort: UsageSort,
+    /// Step usage totals are rounded to when displayed; stored totals stay exact.
+    pub usage_rounding: UsageRounding,
+    /// List apps that are no longer running alongside the active ones.
+    pub show_inactive_apps: bool,
+    /// Most apps shown as tiles in the dashboard usage list.
+    pub usage_tile_limit: u32,
+    /// Dashboard chart view selected when the dashboard was last used.
+    pub last_view: String,
+    /// Show exact milliseconds when hovering a formatted duration.
+    pub show_raw_durations: bool,
+    /// Dashboard avatar: a single letter, or `icon` for the app icon.
+    pub avatar: String,
+    /// Hide the dashboard when it loses focus instead of waiting for an explicit toggle.
+    pub auto_hide_on_blur: bool,
+    /// Hide a dashboard shown from the tray after this many idle seconds; zero turns it off.
+    pub auto_hide_seconds: u32,
+    /// Keep the dashboard open until toggled; overrides `auto_hide_on_blur`.
+    pub dashboard_pinned: bool,
+    /// Monitor label the dashboard opens on; empty follows the tray icon.
+    pub preferred_monitor: String,
+    /// Launchers whose runs are kept but left out of per-launcher stats.
+    pub ignored_launchers: Vec<String>,
+    /// Runs a launcher needs before it is ranked by slowdown.
+    pub launcher_ranking_min_runs: u32,
+    /// Launch-on-boot as the user last chose it; `None` until they first change it.
+    pub autostart_intended: Option<bool>,
+    /// Launch-on-boot is suspended until this time, without changing `autostart_intended`.
+    pub autostart_paused_until_ms: Option<u64>,
+    /// How often a backup bundle is written to `backup_dir`.
+    pub backup_schedule: BackupSchedule,
+    /// Folder scheduled backups are written to; empty until one is chosen.
+    pub backup_dir: String,
+    /// When the latest backup was written.
+    pub last_backup_ms: Option<u64>,
+    /// Send a digest of the previous week once a new week begins.
+    pub weekly_summary: bool,
+    /// Day weekly summaries consider the start of a week.
+    pub week_start: WeekStart,
+    /// When the latest weekly summary was sent, marking the week it covered.
+    pub last_weekly_summary_ms: Option<u64>,
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        Self {
+            habit_reminders: false,
+            habit_reminder_minutes: 18 * 60,
+            pinned_apps: Vec::new(),
+            window_effects: false,
+            app_categories: BTreeMap::new(),
+            recent_chart_bars: 5,
+            distribution_chart_bars: 6,
+            duration_precision: DEFAULT_DURATION_PRECISION,
+            builtin_blocklist: true,
+            blocked_apps: Vec::new(),
+            allowlist_mode: false,
+            allowed_apps: Vec::new(),
+            sound_feedback: false,
+            capture_environment: true,
+            capture_cpu_time: true,
+            track_unclean_exits: false,
+            min_recordable_ms: 0,
+            show_startup_metrics: true,
+            startup_milestone: StartupMilestone::ProcessReady,
+            startup_measurement: StartupMeasurement::Process,
+            min_observed_polls: 2,
+            canonical_app_names: false,
+            min_session_seconds: 0,
+            inactive_display_minutes: 5,
+            align_polls: false,
+            welcome_back: false,
+            welcome_back_minutes: 24 * 60,
+            welcome_back_apps: Vec::new(),
+            day_rollover_hour: 0,
+            offline_mode: false,
+            community_comparison: false,
+            community_endpoint: String::new(),
+            update_check: false,
+            update_endpoint: String::new(),
+            app_budgets: BTreeMap::new(),
+            tile_label_source: TileLabelSource::Name,
+            usage_sort: UsageSort::ActiveFirst,
+            usage_rounding: UsageRounding::None,
+            show_inactive_apps: true,
+            usage_tile_limit: 6,
+            last_view: "recent".to_string(),
+            show_raw_durations: false,
+            avatar: "A".to_string(),
+            auto_hide_on_blur: false,
+            auto_hide_seconds: 0,
+            dashboard_pinned: false,
+            preferred_monitor: String::new(),
+            ignored_launchers: Vec::new(),
+            launcher_ranking_min_runs: 3,
+            autostart_intended: None,
+            autostart_paused_until_ms: None,
+            backup_schedule: BackupSchedule::Off,
+            backup_dir: String::new(),
+            last_backup_ms: None,
+            weekly_summary: false,
+            week_start: WeekStart::Monday,
+            last_weekly_summary_ms: None,
+        }
+    }
+}
+
+impl Preferences {
+    /// Trimmed endpoint for an opt-in network feature, or `None` while offline, opted out, or unset.
+    pub fn network_endpoint(&self, enabled: bool, endpoint: &str) -> Option<String> {
+        let endpoint = endpoint.trim();
+        (enabled && !self.offline_mode && !endpoint.is_empty()).then(|| endpoint.to_string())
+    }
+}
+
+/// Most decimal places `duration_precision` may ask for.
+pub const MAX_DURATION_PRECISION: u32 = 3;
+
+/// Rejects values the deserializer accepts but the app cannot use.
+pub fn validate(preferences: &Preferences) -> Result<(), String> {
+    if preferences.duration_precision > MAX_DURATION_PRECISION {
+        return Err(format!(
+            "duration_precision must be at most {MAX_DURATION_PRECISION}"
+        ));
+    }
+    Ok(())
+}
+
+/// Pulls values `validate` would reject back into range, for files written by hand or by older builds.
+pub fn clamp(preferences: &mut Preferences) {
+    preferences.duration_precision = preferences.duration_precision.min(MAX_DURATION_PRECISION);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_document_deserializes_to_defaults() {
+        let preferences: Preferences = serde_json::from_str("{}").unwrap();
+        assert_eq!(preferences, Preferences::default());
+    }
+
+    #[test]
+    fn partial_document_keeps_defaults_for_missing_keys() {
+        let preferences: Preferences = serde_json::from_str(
+            r#"{"habit_reminders": true, "pinned_apps": ["Journal"], "app_categories": {}, "tile_label_source": "window_title"}"#,
+        )
+        .unwrap();
+
+        assert!(preferences.habit_reminders);
+        assert_eq!(preferences.pinned_apps, vec!["Journal".to_string()]);
+        assert_eq!(preferences.recent_chart_bars, 5);
+        assert!(preferences.builtin_blocklist);
+        assert_eq!(preferences.tile_label_source, TileLabelSource::WindowTitle);
+    }
+
+    #[test]
+    fn offline_mode_withholds_every_endpoint() {
+        let mut preferences = Preferences::default();
+        assert_eq!(
+            preferences.network_endpoint(true, " https://example.com "),
+            Some("https://example.com".to_string())
+        );
+        assert_eq!(
+            preferences.network_endpoint(false, "https://example.com"),
+            None
+        );
+        assert_eq!(preferences.network_endpoint(true, "  "), None);
+
+        preferences.offline_mode = true;
+        assert_eq!(
+            preferences.network_endpoint(true, "https://example.com"),
+            None
+        );
+    }
+}
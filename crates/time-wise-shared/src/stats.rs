@@ -0,0 +1,25 @@
+//! Summary statistics over durations, shared so the dashboard and the benchmark agree.
+
+/// Median of `values_ms`, averaging the two middle values for an even count.
+pub fn median_ms(values_ms: &[u64]) -> Option<u64> {
+    let mut sorted = values_ms.to_vec();
+    sorted.sort_unstable();
+    let middle = sorted.len() / 2;
+    match sorted.len() {
+        0 => None,
+        len if len.is_multiple_of(2) => Some((sorted[middle - 1] + sorted[middle]) / 2),
+        _ => Some(sorted[middle]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn median_handles_odd_even_and_empty() {
+        assert_eq!(median_ms(&[300, 100, 200]), Some(200));
+        assert_eq!(median_ms(&[100, 400]), Some(250));
+        assert_eq!(median_ms(&[]), None);
+    }
+}
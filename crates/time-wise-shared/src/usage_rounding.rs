@@ -0,0 +1,83 @@
+//! Display rounding for accumulated usage, shared with the backend so digests round like the dashboard.
+//!
+//! Rounding only ever applies to formatted output; stored totals keep their exact milliseconds.
+
+use serde::{Deserialize, Serialize};
+
+/// Step displayed usage totals are rounded to.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum UsageRounding {
+    #[default]
+    None,
+    TenSeconds,
+    Minute,
+}
+
+impl UsageRounding {
+    pub const ALL: [UsageRounding; 3] = [
+        UsageRounding::None,
+        UsageRounding::TenSeconds,
+        UsageRounding::Minute,
+    ];
+
+    /// Stored preference value, matching the serde representation.
+    pub fn key(self) -> &'static str {
+        match self {
+            UsageRounding::None => "none",
+            UsageRounding::TenSeconds => "ten_seconds",
+            UsageRounding::Minute => "minute",
+        }
+    }
+
+    /// Parses a stored key, falling back to exact totals.
+    pub fn from_key(key: &str) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|rounding| rounding.key() == key)
+            .unwrap_or_default()
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            UsageRounding::None => "Exact",
+            UsageRounding::TenSeconds => "Nearest 10 seconds",
+            UsageRounding::Minute => "Nearest minute",
+        }
+    }
+
+    /// Rounding step in milliseconds, or `None` when totals are shown as recorded.
+    pub fn step_ms(self) -> Option<u64> {
+        match self {
+            UsageRounding::None => None,
+            UsageRounding::TenSeconds => Some(10_000),
+            UsageRounding::Minute => Some(60_000),
+        }
+    }
+}
+
+/// Rounds `ms` to the nearest step of `policy`, halves rounding up.
+pub fn round_ms(ms: u64, policy: UsageRounding) -> u64 {
+    match policy.step_ms() {
+        Some(step) => ms.saturating_add(step / 2) / step * step,
+        None => ms,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_ms_follows_each_policy() {
+        assert_eq!(round_ms(12_345, UsageRounding::None), 12_345);
+
+        assert_eq!(round_ms(12_345, UsageRounding::TenSeconds), 10_000);
+        assert_eq!(round_ms(15_000, UsageRounding::TenSeconds), 20_000);
+        assert_eq!(round_ms(4_999, UsageRounding::TenSeconds), 0);
+
+        assert_eq!(round_ms(89_999, UsageRounding::Minute), 60_000);
+        assert_eq!(round_ms(90_000, UsageRounding::Minute), 120_000);
+        assert_eq!(round_ms(u64::MAX, UsageRounding::Minute) % 60_000, 0);
+    }
+}
@@ -1,15 +1,202 @@
 use serde::Serialize;
 use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
-use std::sync::{Arc, Mutex};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, RefreshKind, System};
 
-const STALE_ENTRY_GRACE: Duration = Duration::from_secs(5 * 60);
+use crate::calendar::{day_start_ms, local_day_offset_minutes};
+use crate::usage_history::{sessions_including_open, top_apps, UsageHistory, UsageSession};
+
+/// Default time an inactive app stays listed before it is hidden from usage records.
+const DEFAULT_INACTIVE_DISPLAY_CUTOFF: Duration = Duration::from_secs(5 * 60);
+
+/// Inactive apps are evicted from memory, losing their in-memory total, after this long.
+const ENTRY_EVICTION_AFTER: Duration = Duration::from_secs(24 * 60 * 60);
 
 /// Interval used for polling running applications.
 pub const APP_USAGE_POLL_INTERVAL: Duration = Duration::from_secs(15);
 
+/// Time from `now_ms` to the next multiple of `interval_ms`, a full interval when already on one.
+pub fn next_aligned_sleep(now_ms: u64, interval_ms: u64) -> Duration {
+    if interval_ms == 0 {
+        return Duration::ZERO;
+    }
+    Duration::from_millis(interval_ms - now_ms % interval_ms)
+}
+
+/// Well-known system processes hidden from usage unless the user disables the built-in list.
+#[cfg(target_os = "macos")]
+const BUILT_IN_BLOCKLIST: &[&str] = &[
+    "Dock",
+    "Finder",
+    "Spotlight",
+    "SystemUIServer",
+    "ControlCenter",
+    "NotificationCenter",
+    "loginwindow",
+    "WindowServer",
+];
+
+#[cfg(target_os = "windows")]
+const BUILT_IN_BLOCKLIST: &[&str] = &[
+    "explorer.exe",
+    "dwm.exe",
+    "SearchHost.exe",
+    "ShellExperienceHost.exe",
+    "StartMenuExperienceHost.exe",
+    "RuntimeBroker.exe",
+    "TextInputHost.exe",
+    "ctfmon.exe",
+];
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+const BUILT_IN_BLOCKLIST: &[&str] = &[
+    "systemd",
+    "dbus-daemon",
+    "Xorg",
+    "Xwayland",
+    "gnome-shell",
+    "pipewire",
+    "kworker",
+];
+
+/// Lowercase names, without `.exe`, that the same app reports on different platforms, with the name kept for all of them.
+const CANONICAL_APP_NAMES: &[(&str, &str)] = &[
+    ("code", "Code"),
+    ("visual studio code", "Code"),
+    ("chrome", "Google Chrome"),
+    ("google chrome", "Google Chrome"),
+    ("msedge", "Microsoft Edge"),
+    ("microsoft edge", "Microsoft Edge"),
+    ("firefox", "Firefox"),
+    ("ms-teams", "Microsoft Teams"),
+    ("teams", "Microsoft Teams"),
+    ("microsoft teams", "Microsoft Teams"),
+    ("winword", "Microsoft Word"),
+    ("microsoft word", "Microsoft Word"),
+    ("excel", "Microsoft Excel"),
+    ("microsoft excel", "Microsoft Excel"),
+    ("outlook", "Microsoft Outlook"),
+    ("microsoft outlook", "Microsoft Outlook"),
+    ("slack", "Slack"),
+    ("discord", "Discord"),
+    ("spotify", "Spotify"),
+    ("zoom", "Zoom"),
+    ("zoom.us", "Zoom"),
+];
+
+/// Names an app the same way on every platform: the `.app` bundle name when the executable
+/// sits in one, without a `.exe` suffix, mapped through the known cross-platform aliases.
+pub fn canonical_app_name(name: &str, executable: Option<&Path>) -> String {
+    let bundle = executable
+        .and_then(|path| path.to_str())
+        .and_then(|path| path.split_once(".app/"))
+        .and_then(|(prefix, _)| prefix.rsplit('/').next())
+        .filter(|bundle| !bundle.is_empty());
+    let base = bundle.unwrap_or(name).trim();
+    let base = base
+        .len()
+        .checked_sub(".exe".len())
+        .filter(|&stem_len| {
+            base.is_char_boundary(stem_len) && base[stem_len..].eq_ignore_ascii_case(".exe")
+        })
+        .map_or(base, |stem_len| &base[..stem_len]);
+    let lowercase = base.to_lowercase();
+    CANONICAL_APP_NAMES
+        .iter()
+        .find(|(alias, _)| *alias == lowercase)
+        .map_or_else(|| base.to_string(), |(_, canonical)| canonical.to_string())
+}
+
+/// Process names excluded from tracking: the built-in list plus the user's own entries.
+#[derive(Debug, Clone)]
+pub struct ProcessBlocklist {
+    use_built_in: bool,
+    custom: Vec<String>,
+    /// When set, only these names are tracked.
+    allowed: Option<Vec<String>>,
+}
+
+impl ProcessBlocklist {
+    pub fn new(use_built_in: bool, custom: Vec<String>) -> Self {
+        Self {
+            use_built_in,
+            custom,
+            allowed: None,
+        }
+    }
+
+    /// Blocks every name that is not in `allowed`, on top of the blocklist.
+    pub fn with_allowlist(mut self, allowed: Vec<String>) -> Self {
+        self.allowed = Some(allowed);
+        self
+    }
+
+    /// Returns true when `name` matches a blocked entry or misses the allowlist, ignoring ASCII case.
+    pub fn blocks(&self, name: &str) -> bool {
+        self.blocks_any(&[name])
+    }
+
+    /// Like [`Self::blocks`] for an app known by several names: any blocked name blocks it,
+    /// and any allowed name lets it through the allowlist.
+    pub fn blocks_any(&self, names: &[&str]) -> bool {
+        if let Some(allowed) = &self.allowed {
+            if !names.iter().any(|name| contains_name(allowed, name)) {
+                return true;
+            }
+        }
+        let built_in: &[&str] = if self.use_built_in {
+            BUILT_IN_BLOCKLIST
+        } else {
+            &[]
+        };
+        built_in
+            .iter()
+            .copied()
+            .chain(self.custom.iter().map(String::as_str))
+            .any(|blocked| {
+                names
+                    .iter()
+                    .any(|name| blocked.trim().eq_ignore_ascii_case(name))
+            })
+    }
+}
+
+/// Names a process the way the tracker records it, or returns `None` when `blocklist` hides it.
+///
+/// With canonical names on, list entries may be written in either form (the seeded allowlist
+/// stores canonical names), so both the raw and the canonical name are checked.
+fn tracked_name(
+    name: String,
+    executable: Option<&Path>,
+    canonical_names: bool,
+    blocklist: &ProcessBlocklist,
+) -> Option<String> {
+    if !canonical_names {
+        return (!blocklist.blocks(&name)).then_some(name);
+    }
+    let canonical = canonical_app_name(&name, executable);
+    (!blocklist.blocks_any(&[&name, &canonical])).then_some(canonical)
+}
+
+fn contains_name(names: &[String], name: &str) -> bool {
+    names
+        .iter()
+        .any(|entry| entry.trim().eq_ignore_ascii_case(name))
+}
+
+/// Appends each of `names` missing from `allowlist`, ignoring ASCII case; returns how many were added.
+pub fn extend_allowlist(allowlist: &mut Vec<String>, names: Vec<String>) -> usize {
+    let before = allowlist.len();
+    for name in names {
+        if !contains_name(allowlist, &name) {
+            allowlist.push(name);
+        }
+    }
+    allowlist.len() - before
+}
+
 #[derive(Clone)]
 pub struct AppUsageRecorder {
     inner: Arc<Mutex<AppUsageInner>>,
@@ -31,19 +218,80 @@ impl AppUsageRecorder {
         }
     }
 
-    pub fn record_current_processes(&self) -> Result<(), String> {
-        let mut guard = self
-            .inner
-            .lock()
-            .map_err(|_| "app usage recorder mutex poisoned".to_string())?;
+    /// Locks the recorder state, recovering it when an earlier holder panicked.
+    fn lock(&self) -> MutexGuard<'_, AppUsageInner> {
+        self.inner.lock().unwrap_or_else(|poisoned| {
+            eprintln!("app usage recorder mutex was poisoned; recovering its state");
+            self.inner.clear_poison();
+            poisoned.into_inner()
+        })
+    }
+
+    /// Polls running processes; `window_titles` maps process ids to a window title to show for their app.
+    pub fn record_current_processes(
+        &self,
+        blocklist: &ProcessBlocklist,
+        window_titles: &HashMap<u32, String>,
+    ) -> Result<(), String> {
+        let mut guard = self.lock();
         guard.refresh_system();
-        let snapshot = guard.collect_snapshot();
+        let snapshot = guard.collect_snapshot(blocklist, window_titles);
         let instant_now = Instant::now();
         let system_now = SystemTime::now();
         guard.apply_snapshot(&snapshot, instant_now, system_now);
         Ok(())
     }
 
+    /// Requires a process to appear in this many consecutive polls before it is tracked.
+    pub fn set_min_observed_polls(&self, polls: u32) {
+        self.lock().min_observed_polls = polls.max(1);
+    }
+
+    /// Drops sessions shorter than `duration` when they end instead of counting them; zero keeps every session.
+    pub fn set_min_session_duration(&self, duration: Duration) {
+        self.lock().min_session_duration = duration;
+    }
+
+    /// Records apps under [`canonical_app_name`] instead of the raw process name.
+    pub fn set_canonical_names(&self, enabled: bool) {
+        self.lock().canonical_names = enabled;
+    }
+
+    /// Remembers when each app was last seen in a previous run, so the first sighting this run reports its gap.
+    pub fn seed_last_seen(&self, apps: &[(String, u64)]) {
+        let mut guard = self.lock();
+        for (name, last_seen_ms) in apps {
+            guard
+                .last_seen
+                .entry(name.clone())
+                .or_insert(UNIX_EPOCH + Duration::from_millis(*last_seen_ms));
+        }
+    }
+
+    /// Hides apps from usage records once they have been inactive this long; they stay in memory.
+    pub fn set_inactive_display_cutoff(&self, cutoff: Duration) {
+        self.lock().inactive_display_cutoff = cutoff;
+    }
+
+    /// Ends every open session without extending observed coverage, e.g. when tracking is paused.
+    pub fn close_open_sessions(&self) -> Result<(), String> {
+        let mut guard = self.lock();
+        guard.close_open_sessions(Instant::now(), SystemTime::now());
+        Ok(())
+    }
+
+    /// Counts the interval since the last poll by closing every open session now, then drains
+    /// all sessions awaiting persistence. Used right before the process exits.
+    pub fn flush(&self) -> Vec<UsageSession> {
+        self.flush_at(Instant::now(), SystemTime::now())
+    }
+
+    fn flush_at(&self, instant_now: Instant, system_now: SystemTime) -> Vec<UsageSession> {
+        let mut guard = self.lock();
+        guard.close_open_sessions(instant_now, system_now);
+        std::mem::take(&mut guard.completed_sessions)
+    }
+
     pub fn records(&self) -> Vec<AppUsageRecord> {
         self.records_internal(Instant::now(), SystemTime::now())
     }
@@ -53,14 +301,19 @@ impl AppUsageRecorder {
         instant_now: Instant,
         system_now: SystemTime,
     ) -> Vec<AppUsageRecord> {
-        let guard = match self.inner.lock() {
-            Ok(guard) => guard,
-            Err(_) => return Vec::new(),
-        };
+        let guard = self.lock();
 
+        let cutoff = guard.inactive_display_cutoff;
         let mut records: Vec<_> = guard
             .entries
             .values()
+            .filter(|entry| {
+                entry.active
+                    || system_now
+                        .duration_since(entry.last_seen)
+                        .map(|elapsed| elapsed <= cutoff)
+                        .unwrap_or(true)
+            })
             .map(|entry| entry.to_record(instant_now, system_now))
             .filter(|record| record.total_active_ms > 0 || record.active)
             .collect();
@@ -68,6 +321,116 @@ impl AppUsageRecorder {
         records
     }
 
+    /// Names of the apps running right now, filtered and named exactly as the poller would record them.
+    pub fn running_app_names(&self, blocklist: &ProcessBlocklist) -> Vec<String> {
+        let mut guard = self.lock();
+        guard.refresh_system();
+        let mut names: Vec<_> = guard
+            .collect_snapshot(blocklist, &HashMap::new())
+            .into_iter()
+            .map(|snapshot| snapshot.identity.name)
+            .collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    /// Takes the sessions closed since the previous call so they can be persisted.
+    pub fn drain_completed_sessions(&self) -> Vec<UsageSession> {
+        std::mem::take(&mut self.lock().completed_sessions)
+    }
+
+    /// Lists every running process with the filtering decisions the tracker applies to it.
+    #[cfg(debug_assertions)]
+    pub fn candidate_processes(
+        &self,
+        blocklist: &ProcessBlocklist,
+    ) -> Result<Vec<CandidateProcess>, String> {
+        let mut guard = self.lock();
+        guard.refresh_system();
+        let raw = guard.system.processes().values().map(|process| {
+            (
+                process_name(process),
+                executable_from_process(process),
+                should_track_process(process),
+            )
+        });
+        Ok(candidate_list(raw, blocklist, guard.canonical_names))
+    }
+
+    /// Takes the apps reopened since the previous call.
+    pub fn drain_reopened(&self) -> Vec<ReopenedApp> {
+        std::mem::take(&mut self.lock().reopened)
+    }
+
+    /// Returns the sessions still running, ending now.
+    pub fn open_sessions(&self) -> Vec<UsageSession> {
+        let guard = self.lock();
+        let now_ms = system_time_to_ms(SystemTime::now());
+        guard
+            .entries
+            .values()
+            .filter_map(|entry| {
+                let start = entry.session_start.filter(|_| entry.active)?;
+                Some(entry.session(start, now_ms))
+            })
+            .collect()
+    }
+
+    /// Sessions not yet in usage history: the open ones, ending at `now_ms`, and the closed ones
+    /// waiting to be drained.
+    pub fn unsaved_sessions(&self, now_ms: u64) -> Vec<UsageSession> {
+        let guard = self.lock();
+        let open_sessions = guard.entries.values().filter_map(|entry| {
+            let start = entry.session_start.filter(|_| entry.active)?;
+            Some(entry.session(start, now_ms))
+        });
+        guard
+            .completed_sessions
+            .iter()
+            .cloned()
+            .chain(open_sessions)
+            .collect()
+    }
+
+    /// Returns the `n` apps used longest since the local day started, longest first.
+    ///
+    /// Sums the sessions stored in `history` with the ones this recorder still holds.
+    pub fn top_apps_today(
+        &self,
+        history: &UsageHistory,
+        n: usize,
+        now_ms: u64,
+        rollover_hour: u32,
+    ) -> Vec<(String, u64)> {
+        let today_ms = day_start_ms(now_ms, local_day_offset_minutes(rollover_hour));
+        self.top_apps_since(history, n, today_ms, now_ms)
+    }
+
+    /// Returns the `n` apps with the most usage within `[since_ms, now_ms)`, longest first.
+    fn top_apps_since(
+        &self,
+        history: &UsageHistory,
+        n: usize,
+        since_ms: u64,
+        now_ms: u64,
+    ) -> Vec<(String, u64)> {
+        let sessions = sessions_including_open(history, self, since_ms, now_ms);
+        top_apps(&sessions, n, since_ms, now_ms)
+    }
+
+    /// Name of the running app whose current session started last, i.e. the one opened most recently.
+    pub fn latest_active_app(&self) -> Option<String> {
+        let guard = self.lock();
+        guard
+            .entries
+            .values()
+            .filter(|entry| entry.active)
+            .filter_map(|entry| Some((entry.session_start?, &entry.identity.name)))
+            .max_by(|a, b| a.0.cmp(&b.0).then_with(|| b.1.cmp(a.1)))
+            .map(|(_, name)| name.clone())
+    }
+
     #[cfg(test)]
     fn record_mock_snapshot(
         &self,
@@ -75,9 +438,23 @@ impl AppUsageRecorder {
         instant_now: Instant,
         system_now: SystemTime,
     ) {
-        if let Ok(mut guard) = self.inner.lock() {
-            guard.apply_snapshot(&snapshot, instant_now, system_now);
-        }
+        self.lock()
+            .apply_snapshot(&snapshot, instant_now, system_now);
+    }
+
+    /// Applies a poll that saw exactly the apps named in `names`, for tests outside this module.
+    #[cfg(test)]
+    pub(crate) fn record_mock_apps(
+        &self,
+        names: &[&str],
+        instant_now: Instant,
+        system_now: SystemTime,
+    ) {
+        let snapshot = names
+            .iter()
+            .map(|name| ProcessSnapshot::for_tests(name, None))
+            .collect();
+        self.record_mock_snapshot(snapshot, instant_now, system_now);
     }
 
     #[cfg(test)]
@@ -89,6 +466,30 @@ impl AppUsageRecorder {
 struct AppUsageInner {
     system: System,
     entries: HashMap<AppIdentity, AppUsageEntry>,
+    pending: HashMap<AppIdentity, PendingApp>,
+    min_observed_polls: u32,
+    min_session_duration: Duration,
+    inactive_display_cutoff: Duration,
+    canonical_names: bool,
+    completed_sessions: Vec<UsageSession>,
+    reopened: Vec<ReopenedApp>,
+    /// When apps without an entry were last seen, so a reopen after eviction or a restart still reports its gap.
+    last_seen: HashMap<String, SystemTime>,
+}
+
+/// An app seen again after being inactive, with how long it had been gone.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReopenedApp {
+    pub name: String,
+    pub gap: Duration,
+}
+
+/// A process seen in consecutive polls that has not yet reached `min_observed_polls`.
+#[derive(Debug, Clone, Copy)]
+struct PendingApp {
+    polls: u32,
+    first_instant: Instant,
+    first_system: SystemTime,
 }
 
 impl AppUsageInner {
@@ -96,6 +497,14 @@ impl AppUsageInner {
         Self {
             system,
             entries: HashMap::new(),
+            pending: HashMap::new(),
+            min_observed_polls: 1,
+            min_session_duration: Duration::ZERO,
+            inactive_display_cutoff: DEFAULT_INACTIVE_DISPLAY_CUTOFF,
+            canonical_names: false,
+            completed_sessions: Vec::new(),
+            reopened: Vec::new(),
+            last_seen: HashMap::new(),
         }
     }
 
@@ -103,14 +512,43 @@ impl AppUsageInner {
         self.system.refresh_processes(ProcessesToUpdate::All, true);
     }
 
-    fn collect_snapshot(&self) -> Vec<ProcessSnapshot> {
+    fn collect_snapshot(
+        &self,
+        blocklist: &ProcessBlocklist,
+        window_titles: &HashMap<u32, String>,
+    ) -> Vec<ProcessSnapshot> {
         self.system
             .processes()
             .values()
-            .filter_map(ProcessSnapshot::from_process)
+            .filter_map(|process| {
+                let mut snapshot =
+                    ProcessSnapshot::from_process(process, blocklist, self.canonical_names)?;
+                snapshot.window_title = window_titles.get(&process.pid().as_u32()).cloned();
+                Some(snapshot)
+            })
             .collect()
     }
 
+    fn close_open_sessions(&mut self, instant_now: Instant, system_now: SystemTime) {
+        let min_session = self.min_session_duration;
+        let ended: Vec<_> = self
+            .entries
+            .values_mut()
+            .filter_map(|entry| entry.mark_inactive(instant_now, system_now, min_session))
+            .collect();
+        self.completed_sessions.extend(ended);
+    }
+
+    /// Removes the inactive entry an update moved to `identity`'s executable path, so its time carries over.
+    fn take_renamed_entry(&mut self, identity: &AppIdentity) -> Option<AppUsageEntry> {
+        let key = self
+            .entries
+            .iter()
+            .find(|(existing, entry)| !entry.active && existing.is_renamed_as(identity))
+            .map(|(existing, _)| existing.clone())?;
+        self.entries.remove(&key)
+    }
+
     fn apply_snapshot(
         &mut self,
         snapshot: &[ProcessSnapshot],
@@ -121,27 +559,90 @@ impl AppUsageInner {
 
         for process in snapshot {
             observed.insert(process.identity.clone());
-            let entry = self
-                .entries
+            if let Some(entry) = self.entries.get_mut(&process.identity) {
+                if let Some(gap) = entry.record_presence(instant_now, system_now) {
+                    self.reopened.push(ReopenedApp {
+                        name: process.identity.name.clone(),
+                        gap,
+                    });
+                }
+                continue;
+            }
+
+            let pending = self
+                .pending
                 .entry(process.identity.clone())
-                .or_insert_with(|| AppUsageEntry::new(process.identity.clone(), system_now));
+                .or_insert(PendingApp {
+                    polls: 0,
+                    first_instant: instant_now,
+                    first_system: system_now,
+                });
+            pending.polls += 1;
+            if pending.polls < self.min_observed_polls {
+                continue;
+            }
+
+            // Confirmed: backdate the entry to the first consecutive sighting.
+            let pending = *pending;
+            self.pending.remove(&process.identity);
+            let mut entry = match self.take_renamed_entry(&process.identity) {
+                Some(mut previous) => {
+                    previous.identity = process.identity.clone();
+                    previous
+                }
+                None => {
+                    if let Some(last_seen) = self.last_seen.remove(&process.identity.name) {
+                        self.reopened.push(ReopenedApp {
+                            name: process.identity.name.clone(),
+                            gap: pending
+                                .first_system
+                                .duration_since(last_seen)
+                                .unwrap_or_default(),
+                        });
+                    }
+                    AppUsageEntry::new(process.identity.clone(), pending.first_system)
+                }
+            };
+            entry.record_presence(pending.first_instant, pending.first_system);
             entry.record_presence(instant_now, system_now);
+            self.entries.insert(process.identity.clone(), entry);
         }
+        self.pending
+            .retain(|identity, _| observed.contains(identity));
 
-        for (identity, entry) in &mut self.entries {
-            if !observed.contains(identity) {
-                entry.mark_inactive(instant_now);
+        // An app's helper processes usually have no window, so keep the first title any of them reported.
+        let mut window_titles: HashMap<&AppIdentity, &str> = HashMap::new();
+        for process in snapshot {
+            if let Some(title) = &process.window_title {
+                window_titles.entry(&process.identity).or_insert(title);
             }
         }
+        for (identity, entry) in &mut self.entries {
+            entry.window_title = window_titles.get(identity).map(|title| title.to_string());
+        }
 
-        self.entries.retain(|_, entry| {
+        let min_session = self.min_session_duration;
+        let ended: Vec<_> = self
+            .entries
+            .iter_mut()
+            .filter(|(identity, _)| !observed.contains(*identity))
+            .filter_map(|(_, entry)| entry.mark_inactive(instant_now, system_now, min_session))
+            .collect();
+        self.completed_sessions.extend(ended);
+
+        let last_seen = &mut self.last_seen;
+        self.entries.retain(|identity, entry| {
             if entry.active {
                 return true;
             }
-            match system_now.duration_since(entry.last_seen) {
-                Ok(elapsed) => elapsed <= STALE_ENTRY_GRACE,
+            let keep = match system_now.duration_since(entry.last_seen) {
+                Ok(elapsed) => elapsed <= ENTRY_EVICTION_AFTER,
                 Err(_) => false,
+            };
+            if !keep {
+                last_seen.insert(identity.name.clone(), entry.last_seen);
             }
+            keep
         });
     }
 }
@@ -152,14 +653,55 @@ struct AppIdentity {
     executable: Option<PathBuf>,
 }
 
+impl AppIdentity {
+    /// Returns true when `other` is this app at an executable path that differs only in version numbers.
+    fn is_renamed_as(&self, other: &AppIdentity) -> bool {
+        match (&self.executable, &other.executable) {
+            (Some(path), Some(other_path)) => {
+                self.name == other.name
+                    && path != other_path
+                    && versionless_path(path) == versionless_path(other_path)
+            }
+            _ => false,
+        }
+    }
+}
+
+/// The path with every version number, such as `1.2.3` in `app-1.2.3`, replaced by `#`.
+fn versionless_path(path: &Path) -> String {
+    let text = path.to_string_lossy();
+    let mut stable = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(current) = chars.next() {
+        if !current.is_ascii_digit() {
+            stable.push(current);
+            continue;
+        }
+        stable.push('#');
+        while let Some(next) = chars.peek() {
+            if next.is_ascii_digit() || *next == '.' {
+                chars.next();
+            } else {
+                break;
+            }
+        }
+    }
+    stable
+}
+
 #[derive(Debug)]
 struct AppUsageEntry {
     identity: AppIdentity,
     accumulated: Duration,
+    /// `accumulated` when the open session started, so a too-short session can be rolled back.
+    session_base: Duration,
     last_tick: Option<Instant>,
     first_seen: SystemTime,
     last_seen: SystemTime,
+    session_start: Option<SystemTime>,
     active: bool,
+    /// Window title from the latest poll, when title capture is on and the app has a titled window.
+    window_title: Option<String>,
 }
 
 impl AppUsageEntry {
@@ -167,34 +709,86 @@ impl AppUsageEntry {
         Self {
             identity,
             accumulated: Duration::default(),
+            session_base: Duration::default(),
             last_tick: None,
             first_seen: seen_at,
             last_seen: seen_at,
+            session_start: None,
             active: false,
+            window_title: None,
         }
     }
 
-    fn record_presence(&mut self, instant_now: Instant, system_now: SystemTime) {
+    /// Marks the app as seen now; returns how long it had been gone when this reopens it.
+    fn record_presence(
+        &mut self,
+        instant_now: Instant,
+        system_now: SystemTime,
+    ) -> Option<Duration> {
         let was_active = self.active;
+        // A missing tick means this is the first observation, which has no prior gap.
+        let reopened_after = match self.last_tick {
+            Some(_) if !was_active => Some(
+                system_now
+                    .duration_since(self.last_seen)
+                    .unwrap_or_default(),
+            ),
+            _ => None,
+        };
         if let Some(last_tick) = self.last_tick {
             if was_active {
                 let delta = instant_now.saturating_duration_since(last_tick);
                 self.accumulated += delta;
             }
         }
+        if !was_active {
+            self.session_start = Some(system_now);
+            self.session_base = self.accumulated;
+        }
         self.last_tick = Some(instant_now);
         self.last_seen = system_now;
         self.active = true;
+        reopened_after
     }
 
-    fn mark_inactive(&mut self, instant_now: Instant) {
+    /// Stops accumulating and returns the session that just ended, if any.
+    ///
+    /// A session shorter than `min_session` is discarded and its time taken back out of the total.
+    fn mark_inactive(
+        &mut self,
+        instant_now: Instant,
+        system_now: SystemTime,
+        min_session: Duration,
+    ) -> Option<UsageSession> {
+        let mut ended = None;
         if self.active {
             if let Some(last_tick) = self.last_tick {
                 self.accumulated += instant_now.saturating_duration_since(last_tick);
             }
+            if let Some(start) = self.session_start.take() {
+                if self.accumulated.saturating_sub(self.session_base) < min_session {
+                    self.accumulated = self.session_base;
+                } else {
+                    ended = Some(self.session(start, system_time_to_ms(system_now)));
+                }
+            }
         }
         self.active = false;
         self.last_tick = Some(instant_now);
+        ended
+    }
+
+    fn session(&self, start: SystemTime, end_ms: u64) -> UsageSession {
+        UsageSession {
+            name: self.identity.name.clone(),
+            executable: self
+                .identity
+                .executable
+                .as_ref()
+                .map(|path| path.display().to_string()),
+            start_ms: system_time_to_ms(start),
+            end_ms,
+        }
     }
 
     fn to_record(&self, instant_now: Instant, _system_now: SystemTime) -> AppUsageRecord {
@@ -216,11 +810,12 @@ impl AppUsageEntry {
             last_seen_at_ms: system_time_to_ms(self.last_seen),
             active: self.active,
             first_seen_at_ms: system_time_to_ms(self.first_seen),
+            window_title: self.window_title.clone(),
         }
     }
 }
 
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct AppUsageRecord {
     pub name: String,
@@ -229,25 +824,77 @@ pub struct AppUsageRecord {
     pub last_seen_at_ms: u64,
     pub first_seen_at_ms: u64,
     pub active: bool,
+    pub window_title: Option<String>,
+}
+
+/// A running process as the tracker sees it, for diagnosing why an app is not tracked.
+#[cfg(debug_assertions)]
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CandidateProcess {
+    pub name: String,
+    pub executable: Option<String>,
+    /// Whether the platform filter (`should_track_process`) accepted it.
+    pub passes_filter: bool,
+    /// Whether the built-in or custom blocklist hides it.
+    pub blocked: bool,
+    /// Whether it ends up in usage.
+    pub tracked: bool,
+}
+
+/// Applies the tracker's decisions to raw `(name, executable, passes_filter)` triples, tracked first.
+#[cfg(debug_assertions)]
+fn candidate_list(
+    raw: impl IntoIterator<Item = (Option<String>, Option<PathBuf>, bool)>,
+    blocklist: &ProcessBlocklist,
+    canonical_names: bool,
+) -> Vec<CandidateProcess> {
+    let mut candidates: Vec<_> = raw
+        .into_iter()
+        .map(|(name, executable, passes_filter)| {
+            let blocked = name.clone().is_some_and(|name| {
+                tracked_name(name, executable.as_deref(), canonical_names, blocklist).is_none()
+            });
+            CandidateProcess {
+                tracked: passes_filter && name.is_some() && !blocked,
+                name: name.unwrap_or_default(),
+                executable: executable.map(|path| path.display().to_string()),
+                passes_filter,
+                blocked,
+            }
+        })
+        .collect();
+    candidates.sort_by(|a, b| b.tracked.cmp(&a.tracked).then_with(|| a.name.cmp(&b.name)));
+    candidates
 }
 
 #[derive(Clone)]
 struct ProcessSnapshot {
     identity: AppIdentity,
+    window_title: Option<String>,
 }
 
 impl ProcessSnapshot {
-    fn from_process(process: &sysinfo::Process) -> Option<Self> {
+    fn from_process(
+        process: &sysinfo::Process,
+        blocklist: &ProcessBlocklist,
+        canonical_names: bool,
+    ) -> Option<Self> {
         if !should_track_process(process) {
             return None;
         }
 
-        let name = process_name(process)?;
-
         let executable = executable_from_process(process);
+        let name = tracked_name(
+            process_name(process)?,
+            executable.as_deref(),
+            canonical_names,
+            blocklist,
+        )?;
 
         Some(Self {
             identity: AppIdentity { name, executable },
+            window_title: None,
         })
     }
 
@@ -258,6 +905,7 @@ impl ProcessSnapshot {
                 name: name.to_string(),
                 executable: executable.map(PathBuf::from),
             },
+            window_title: None,
         }
     }
 }
@@ -327,7 +975,7 @@ fn duration_to_ms(duration: Duration) -> u64 {
         .unwrap_or(u64::MAX)
 }
 
-fn system_time_to_ms(time: SystemTime) -> u64 {
+pub(crate) fn system_time_to_ms(time: SystemTime) -> u64 {
     time.duration_since(UNIX_EPOCH)
         .unwrap_or_default()
         .as_millis()
@@ -389,6 +1037,75 @@ mod tests {
         assert!(!record.active);
     }
 
+    #[test]
+    fn window_title_follows_the_latest_poll() {
+        let recorder = AppUsageRecorder::new();
+        let instant_start = Instant::now();
+        let system_start = SystemTime::now();
+        // A windowless helper process of the same app comes first, as it often does.
+        let titled = |title: Option<&str>| {
+            let mut main = ProcessSnapshot::for_tests("Code", None);
+            main.window_title = title.map(str::to_string);
+            vec![ProcessSnapshot::for_tests("Code", None), main]
+        };
+        let title_of = |recorder: &AppUsageRecorder, instant: Instant, system: SystemTime| {
+            recorder.records_at(instant, system)[0].window_title.clone()
+        };
+
+        recorder.record_mock_snapshot(titled(Some("main.rs — Code")), instant_start, system_start);
+        assert_eq!(
+            title_of(&recorder, instant_start, system_start),
+            Some("main.rs — Code".to_string())
+        );
+
+        let instant_next = instant_start + Duration::from_secs(5);
+        let system_next = system_start + Duration::from_secs(5);
+        recorder.record_mock_snapshot(titled(None), instant_next, system_next);
+        assert_eq!(title_of(&recorder, instant_next, system_next), None);
+
+        let instant_gone = instant_next + Duration::from_secs(5);
+        let system_gone = system_next + Duration::from_secs(5);
+        recorder.record_mock_snapshot(titled(Some("lib.rs — Code")), instant_gone, system_gone);
+        recorder.record_mock_snapshot(Vec::new(), instant_gone, system_gone);
+        assert_eq!(title_of(&recorder, instant_gone, system_gone), None);
+    }
+
+    #[test]
+    fn sessions_shorter_than_the_minimum_are_discarded() {
+        let recorder = AppUsageRecorder::new();
+        recorder.set_min_session_duration(Duration::from_secs(10));
+        let instant_start = Instant::now();
+        let system_start = UNIX_EPOCH + Duration::from_secs(1_000);
+        let at = |secs: u64| {
+            (
+                instant_start + Duration::from_secs(secs),
+                system_start + Duration::from_secs(secs),
+            )
+        };
+        let focus = || ProcessSnapshot::for_tests("Focus", None);
+
+        // An accidental two-second open.
+        for (secs, snapshot) in [(0, vec![focus()]), (2, vec![focus()]), (4, Vec::new())] {
+            let (instant, system) = at(secs);
+            recorder.record_mock_snapshot(snapshot, instant, system);
+        }
+        let (instant, system) = at(4);
+        assert!(recorder.records_at(instant, system).is_empty());
+        assert!(recorder.flush_at(instant, system).is_empty());
+
+        // A real session is kept in full.
+        for (secs, snapshot) in [(10, vec![focus()]), (40, vec![focus()]), (50, Vec::new())] {
+            let (instant, system) = at(secs);
+            recorder.record_mock_snapshot(snapshot, instant, system);
+        }
+        let (instant, system) = at(50);
+        assert_eq!(
+            recorder.records_at(instant, system)[0].total_active_ms,
+            40_000
+        );
+        assert_eq!(recorder.flush_at(instant, system).len(), 1);
+    }
+
     #[test]
     fn records_reports_tracked_processes() {
         let recorder = AppUsageRecorder::new();
@@ -418,4 +1135,495 @@ mod tests {
         assert!(record.total_active_ms >= 20);
         assert!(!record.active);
     }
+
+    #[test]
+    fn inactive_apps_are_hidden_but_keep_their_total() {
+        let recorder = AppUsageRecorder::new();
+        let instant_start = Instant::now();
+        let system_start = UNIX_EPOCH + Duration::from_secs(1_000);
+        let at = |secs: u64| {
+            (
+                instant_start + Duration::from_secs(secs),
+                system_start + Duration::from_secs(secs),
+            )
+        };
+        let focus = || ProcessSnapshot::for_tests("Focus", None);
+
+        let (instant, system) = at(0);
+        recorder.record_mock_snapshot(vec![focus()], instant, system);
+        let (instant, system) = at(60);
+        recorder.record_mock_snapshot(vec![focus()], instant, system);
+        let (instant, system) = at(120);
+        recorder.record_mock_snapshot(Vec::new(), instant, system);
+
+        // Well past the display cutoff the app is hidden, but not forgotten.
+        let (instant, system) = at(120 + 30 * 60);
+        recorder.record_mock_snapshot(Vec::new(), instant, system);
+        assert!(recorder.records_at(instant, system).is_empty());
+
+        let (instant, system) = at(120 + 31 * 60);
+        recorder.record_mock_snapshot(vec![focus()], instant, system);
+        let (instant, system) = at(120 + 32 * 60);
+        recorder.record_mock_snapshot(vec![focus()], instant, system);
+        let records = recorder.records_at(instant, system);
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].total_active_ms, 180_000);
+        assert!(records[0].active);
+    }
+
+    #[test]
+    fn updated_executables_inherit_the_previous_total() {
+        let recorder = AppUsageRecorder::new();
+        let instant_start = Instant::now();
+        let system_start = UNIX_EPOCH + Duration::from_secs(1_000);
+        let at = |secs: u64| {
+            (
+                instant_start + Duration::from_secs(secs),
+                system_start + Duration::from_secs(secs),
+            )
+        };
+        let old = || ProcessSnapshot::for_tests("Chat", Some("/opt/Chat/app-1.0.9/chat"));
+        let updated = || ProcessSnapshot::for_tests("Chat", Some("/opt/Chat/app-1.0.10/chat"));
+
+        let (instant, system) = at(0);
+        recorder.record_mock_snapshot(vec![old()], instant, system);
+        let (instant, system) = at(60);
+        recorder.record_mock_snapshot(vec![old()], instant, system);
+        let (instant, system) = at(120);
+        recorder.record_mock_snapshot(Vec::new(), instant, system);
+        let (instant, system) = at(180);
+        recorder.record_mock_snapshot(vec![updated()], instant, system);
+        let (instant, system) = at(240);
+        recorder.record_mock_snapshot(vec![updated()], instant, system);
+
+        let records = recorder.records_at(instant, system);
+        assert_eq!(records.len(), 1);
+        assert_eq!(
+            records[0].executable.as_deref(),
+            Some("/opt/Chat/app-1.0.10/chat")
+        );
+        assert_eq!(records[0].total_active_ms, 180_000);
+    }
+
+    #[test]
+    fn canonical_names_match_across_macos_and_windows() {
+        let mac_code = Path::new("/Applications/Visual Studio Code.app/Contents/MacOS/Electron");
+        let windows_code = Path::new(r"C:\Users\me\AppData\Local\Programs\Code\Code.exe");
+        assert_eq!(canonical_app_name("Electron", Some(mac_code)), "Code");
+        assert_eq!(canonical_app_name("Code.exe", Some(windows_code)), "Code");
+
+        let mac_chrome = Path::new("/Applications/Google Chrome.app/Contents/MacOS/Google Chrome");
+        assert_eq!(
+            canonical_app_name("Google Chrome", Some(mac_chrome)),
+            "Google Chrome"
+        );
+        assert_eq!(canonical_app_name("chrome.exe", None), "Google Chrome");
+
+        assert_eq!(canonical_app_name("Notion.EXE", None), "Notion");
+        assert_eq!(canonical_app_name("Notion", None), "Notion");
+        assert_eq!(canonical_app_name(".exe", None), "");
+    }
+
+    #[test]
+    fn versionless_paths_only_ignore_version_numbers() {
+        let chat = AppIdentity {
+            name: "Chat".to_string(),
+            executable: Some(PathBuf::from("/opt/Chat/1.2.3/chat")),
+        };
+        let chat_update = AppIdentity {
+            name: "Chat".to_string(),
+            executable: Some(PathBuf::from("/opt/Chat/1.3.0/chat")),
+        };
+        let other = AppIdentity {
+            name: "Chat".to_string(),
+            executable: Some(PathBuf::from("/opt/Other/1.3.0/chat")),
+        };
+        assert!(chat.is_renamed_as(&chat_update));
+        assert!(!chat.is_renamed_as(&chat));
+        assert!(!chat.is_renamed_as(&other));
+    }
+
+    #[test]
+    fn reopening_reports_the_gap_but_first_sightings_do_not() {
+        let recorder = AppUsageRecorder::new();
+        let instant_start = Instant::now();
+        let system_start = UNIX_EPOCH + Duration::from_secs(1_000);
+        let at = |secs: u64| {
+            (
+                instant_start + Duration::from_secs(secs),
+                system_start + Duration::from_secs(secs),
+            )
+        };
+        let focus = || ProcessSnapshot::for_tests("Focus", None);
+
+        let (instant, system) = at(0);
+        recorder.record_mock_snapshot(vec![focus()], instant, system);
+        let (instant, system) = at(15);
+        recorder.record_mock_snapshot(vec![focus()], instant, system);
+        assert!(recorder.drain_reopened().is_empty());
+
+        let (instant, system) = at(30);
+        recorder.record_mock_snapshot(Vec::new(), instant, system);
+        let (instant, system) = at(3_615);
+        recorder.record_mock_snapshot(vec![focus()], instant, system);
+
+        assert_eq!(
+            recorder.drain_reopened(),
+            vec![ReopenedApp {
+                name: "Focus".to_string(),
+                gap: Duration::from_secs(3_600),
+            }]
+        );
+        assert!(recorder.drain_reopened().is_empty());
+    }
+
+    #[test]
+    fn reopening_after_eviction_or_a_restart_still_reports_the_gap() {
+        let recorder = AppUsageRecorder::new();
+        let instant_start = Instant::now();
+        let system_start = UNIX_EPOCH + Duration::from_secs(1_000);
+        let at = |secs: u64| {
+            (
+                instant_start + Duration::from_secs(secs),
+                system_start + Duration::from_secs(secs),
+            )
+        };
+        let focus = || ProcessSnapshot::for_tests("Focus", None);
+        let three_days = 3 * 24 * 60 * 60;
+
+        let (instant, system) = at(0);
+        recorder.record_mock_snapshot(vec![focus()], instant, system);
+        let (instant, system) = at(15);
+        recorder.record_mock_snapshot(Vec::new(), instant, system);
+        // Long enough that the inactive entry is evicted before the app returns.
+        let (instant, system) = at(three_days);
+        recorder.record_mock_snapshot(Vec::new(), instant, system);
+        assert!(recorder.records_at(instant, system).is_empty());
+
+        let (instant, system) = at(three_days + 15);
+        recorder.record_mock_snapshot(vec![focus()], instant, system);
+        assert_eq!(
+            recorder.drain_reopened(),
+            vec![ReopenedApp {
+                name: "Focus".to_string(),
+                gap: Duration::from_secs(three_days + 15),
+            }]
+        );
+
+        let restarted = AppUsageRecorder::new();
+        restarted.seed_last_seen(&[("Focus".to_string(), 1_000_000)]);
+        let (instant, system) = at(10_000);
+        restarted.record_mock_snapshot(vec![focus()], instant, system);
+        assert_eq!(
+            restarted.drain_reopened(),
+            vec![ReopenedApp {
+                name: "Focus".to_string(),
+                gap: Duration::from_secs(10_000),
+            }]
+        );
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    fn candidate_list_flags_tracked_and_rejected_processes() {
+        let blocklist = ProcessBlocklist::new(false, vec!["Noise".to_string()]);
+        let raw = vec![
+            (
+                Some("Noise".to_string()),
+                Some(PathBuf::from("/bin/noise")),
+                true,
+            ),
+            (Some("helper".to_string()), None, false),
+            (None, Some(PathBuf::from("/bin/unnamed")), true),
+            (
+                Some("Focus".to_string()),
+                Some(PathBuf::from("/bin/focus")),
+                true,
+            ),
+        ];
+
+        let candidates = candidate_list(raw, &blocklist, false);
+        let flags: Vec<_> = candidates
+            .iter()
+            .map(|candidate| {
+                (
+                    candidate.name.as_str(),
+                    candidate.passes_filter,
+                    candidate.blocked,
+                    candidate.tracked,
+                )
+            })
+            .collect();
+        assert_eq!(
+            flags,
+            vec![
+                ("Focus", true, false, true),
+                ("", true, false, false),
+                ("Noise", true, true, false),
+                ("helper", false, false, false),
+            ]
+        );
+        assert_eq!(candidates[0].executable.as_deref(), Some("/bin/focus"));
+    }
+
+    #[test]
+    fn flush_counts_the_interval_since_the_last_poll() {
+        let recorder = AppUsageRecorder::new();
+        let instant_start = Instant::now();
+        let system_start = UNIX_EPOCH + Duration::from_secs(1_000);
+        let focus = || ProcessSnapshot::for_tests("Focus", None);
+
+        recorder.record_mock_snapshot(vec![focus()], instant_start, system_start);
+        recorder.record_mock_snapshot(
+            vec![focus()],
+            instant_start + Duration::from_secs(5),
+            system_start + Duration::from_secs(5),
+        );
+
+        let instant_exit = instant_start + Duration::from_secs(8);
+        let system_exit = system_start + Duration::from_secs(8);
+        let sessions = recorder.flush_at(instant_exit, system_exit);
+
+        let records = recorder.records_at(instant_exit, system_exit);
+        assert_eq!(records[0].total_active_ms, 8_000);
+        assert!(!records[0].active);
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].start_ms, system_time_to_ms(system_start));
+        assert_eq!(sessions[0].end_ms, system_time_to_ms(system_exit));
+        assert!(recorder.drain_completed_sessions().is_empty());
+    }
+
+    #[test]
+    fn top_apps_sum_stored_pending_and_open_sessions_within_the_boundary() {
+        let dir = tempfile::tempdir().unwrap();
+        let history = UsageHistory::with_storage_path(dir.path().join("usage.sqlite"));
+        let recorder = AppUsageRecorder::new();
+        let instant_start = Instant::now();
+        let system_start = UNIX_EPOCH + Duration::from_secs(1_000);
+        let start_ms = system_time_to_ms(system_start);
+        let at = |secs: u64| {
+            (
+                instant_start + Duration::from_secs(secs),
+                system_start + Duration::from_secs(secs),
+            )
+        };
+        let focus = || ProcessSnapshot::for_tests("Focus", None);
+        let mail = || ProcessSnapshot::for_tests("Mail", None);
+        history
+            .record_sessions(&[UsageSession {
+                name: "Notes".to_string(),
+                executable: None,
+                start_ms: start_ms + 10_000,
+                end_ms: start_ms + 50_000,
+            }])
+            .unwrap();
+
+        let (instant, system) = at(0);
+        recorder.record_mock_snapshot(vec![focus()], instant, system);
+        let (instant, system) = at(60);
+        recorder.record_mock_snapshot(vec![focus(), mail()], instant, system);
+        let (instant, system) = at(120);
+        recorder.record_mock_snapshot(vec![mail()], instant, system);
+
+        let expected = vec![
+            ("Mail".to_string(), 120_000),
+            ("Focus".to_string(), 90_000),
+            ("Notes".to_string(), 20_000),
+        ];
+        // Focus has closed but is not drained yet, so it only exists in the recorder.
+        assert_eq!(
+            recorder.top_apps_since(&history, 5, start_ms + 30_000, start_ms + 180_000),
+            expected
+        );
+        // Persisting it moves it to the history without counting it twice.
+        history
+            .record_sessions(&recorder.drain_completed_sessions())
+            .unwrap();
+        assert_eq!(
+            recorder.top_apps_since(&history, 5, start_ms + 30_000, start_ms + 180_000),
+            expected
+        );
+        assert_eq!(
+            recorder.top_apps_since(&history, 1, start_ms + 30_000, start_ms + 180_000),
+            vec![("Mail".to_string(), 120_000)]
+        );
+    }
+
+    #[test]
+    fn closing_an_entry_emits_a_completed_session() {
+        let recorder = AppUsageRecorder::new();
+        let instant_start = Instant::now();
+        let system_start = UNIX_EPOCH + Duration::from_secs(1_000);
+
+        recorder.record_mock_snapshot(
+            vec![ProcessSnapshot::for_tests("Focus", None)],
+            instant_start,
+            system_start,
+        );
+        assert_eq!(recorder.open_sessions().len(), 1);
+        assert!(recorder.drain_completed_sessions().is_empty());
+
+        recorder.record_mock_snapshot(
+            Vec::new(),
+            instant_start + Duration::from_secs(30),
+            system_start + Duration::from_secs(30),
+        );
+
+        let sessions = recorder.drain_completed_sessions();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].name, "Focus");
+        assert_eq!(sessions[0].start_ms, 1_000_000);
+        assert_eq!(sessions[0].end_ms, 1_030_000);
+        assert!(recorder.drain_completed_sessions().is_empty());
+        assert!(recorder.open_sessions().is_empty());
+    }
+
+    #[test]
+    fn built_in_blocklist_hides_system_processes_by_default() {
+        let system_name = BUILT_IN_BLOCKLIST[0];
+        let blocklist = ProcessBlocklist::new(true, Vec::new());
+        assert!(blocklist.blocks(system_name));
+        assert!(blocklist.blocks(&system_name.to_ascii_uppercase()));
+        assert!(!blocklist.blocks("Focus"));
+    }
+
+    #[test]
+    fn disabling_built_in_blocklist_keeps_custom_entries() {
+        let system_name = BUILT_IN_BLOCKLIST[0];
+        let blocklist = ProcessBlocklist::new(false, vec!["Focus".to_string()]);
+        assert!(!blocklist.blocks(system_name));
+        assert!(blocklist.blocks("focus"));
+    }
+
+    #[test]
+    fn recorder_keeps_working_after_a_panic_poisons_it() {
+        let recorder = AppUsageRecorder::new();
+        let holder = recorder.clone();
+        let _ = std::thread::spawn(move || {
+            let _guard = holder.inner.lock().unwrap();
+            panic!("poll task panicked while holding the recorder");
+        })
+        .join();
+        assert!(recorder.inner.is_poisoned());
+
+        let instant_start = Instant::now();
+        let system_start = SystemTime::now();
+        for offset in [0, 5] {
+            recorder.record_mock_snapshot(
+                vec![ProcessSnapshot::for_tests("Focus", None)],
+                instant_start + Duration::from_secs(offset),
+                system_start + Duration::from_secs(offset),
+            );
+        }
+
+        assert!(!recorder.inner.is_poisoned());
+        let records = recorder.records_at(
+            instant_start + Duration::from_secs(5),
+            system_start + Duration::from_secs(5),
+        );
+        assert!(records.iter().any(|record| record.name == "Focus"));
+    }
+
+    #[test]
+    fn aligned_sleep_waits_for_the_next_boundary() {
+        assert_eq!(next_aligned_sleep(61_000, 15_000), Duration::from_secs(14));
+        assert_eq!(next_aligned_sleep(74_999, 15_000), Duration::from_millis(1));
+        assert_eq!(next_aligned_sleep(75_000, 15_000), Duration::from_secs(15));
+        assert_eq!(next_aligned_sleep(0, 15_000), Duration::from_secs(15));
+    }
+
+    #[test]
+    fn allowlist_blocks_everything_it_does_not_name() {
+        let blocklist = ProcessBlocklist::new(false, vec!["Chat".to_string()])
+            .with_allowlist(vec!["Focus".to_string(), "Chat".to_string()]);
+        assert!(!blocklist.blocks("focus"));
+        assert!(blocklist.blocks("Chat"));
+        assert!(blocklist.blocks("Mail"));
+    }
+
+    #[test]
+    fn canonical_names_match_allowlist_and_blocklist_entries() {
+        let allowlist =
+            ProcessBlocklist::new(false, Vec::new()).with_allowlist(vec!["Code".to_string()]);
+        assert_eq!(
+            tracked_name("Code.exe".to_string(), None, true, &allowlist),
+            Some("Code".to_string())
+        );
+        assert_eq!(
+            tracked_name("Code.exe".to_string(), None, false, &allowlist),
+            None
+        );
+
+        let blocklist = ProcessBlocklist::new(false, vec!["Google Chrome".to_string()]);
+        assert_eq!(
+            tracked_name("chrome.exe".to_string(), None, true, &blocklist),
+            None
+        );
+        assert_eq!(
+            tracked_name("Notion.exe".to_string(), None, true, &blocklist),
+            Some("Notion".to_string())
+        );
+    }
+
+    #[test]
+    fn seeding_the_allowlist_adds_only_missing_names() {
+        let running = vec!["Focus".to_string(), "Mail".to_string()];
+
+        let mut allowlist = vec!["focus".to_string()];
+        assert_eq!(extend_allowlist(&mut allowlist, running.clone()), 1);
+        for name in &running {
+            assert!(contains_name(&allowlist, name));
+        }
+        assert_eq!(extend_allowlist(&mut allowlist, running), 0);
+    }
+
+    #[test]
+    fn short_lived_processes_need_consecutive_polls_to_surface() {
+        let recorder = AppUsageRecorder::new();
+        recorder.set_min_observed_polls(2);
+        let instant_start = Instant::now();
+        let system_start = UNIX_EPOCH + Duration::from_secs(1_000);
+        let poll = |index: u32| {
+            (
+                instant_start + APP_USAGE_POLL_INTERVAL * index,
+                system_start + APP_USAGE_POLL_INTERVAL * index,
+            )
+        };
+
+        let (instant, system) = poll(0);
+        recorder.record_mock_snapshot(
+            vec![
+                ProcessSnapshot::for_tests("cargo", None),
+                ProcessSnapshot::for_tests("Focus", None),
+            ],
+            instant,
+            system,
+        );
+        assert!(recorder.records_at(instant, system).is_empty());
+
+        let (instant, system) = poll(1);
+        recorder.record_mock_snapshot(
+            vec![ProcessSnapshot::for_tests("Focus", None)],
+            instant,
+            system,
+        );
+
+        let (instant, system) = poll(2);
+        recorder.record_mock_snapshot(Vec::new(), instant, system);
+        let (instant, system) = poll(3);
+        recorder.record_mock_snapshot(
+            vec![ProcessSnapshot::for_tests("cargo", None)],
+            instant,
+            system,
+        );
+
+        let records = recorder.records_at(instant, system);
+        let names: Vec<_> = records.iter().map(|record| record.name.as_str()).collect();
+        assert_eq!(names, vec!["Focus"]);
+        assert_eq!(
+            records[0].total_active_ms,
+            duration_to_ms(APP_USAGE_POLL_INTERVAL * 2)
+        );
+        assert_eq!(records[0].first_seen_at_ms, 1_000_000);
+    }
 }
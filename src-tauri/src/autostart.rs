@@ -0,0 +1,165 @@
+//! Launch-on-boot: the user's intended setting plus a temporary pause, reconciled with the OS registration.
+
+use std::time::SystemTime;
+
+use serde_json::json;
+use tauri::State;
+use tauri_plugin_autostart::AutoLaunchManager;
+
+use crate::app_usage::system_time_to_ms;
+use crate::preferences::{Preferences, PreferencesStore};
+
+/// Argument passed to launches registered for launch-on-boot.
+pub const AUTOSTART_ARG: &str = "--autostart";
+
+/// Returns true when this process was started by the launch-on-boot registration.
+pub fn launched_by_autostart() -> bool {
+    std::env::args().any(|arg| arg == AUTOSTART_ARG)
+}
+
+/// Whether launch-on-boot should be registered, or `None` when no intent was ever recorded.
+pub fn effective_autostart(
+    intended: Option<bool>,
+    paused_until_ms: Option<u64>,
+    now_ms: u64,
+) -> Option<bool> {
+    let intended = intended?;
+    Some(intended && !is_paused(paused_until_ms, now_ms))
+}
+
+/// Returns true while a temporary pause is still in effect.
+pub fn is_paused(paused_until_ms: Option<u64>, now_ms: u64) -> bool {
+    paused_until_ms.is_some_and(|until| now_ms < until)
+}
+
+/// Returns true once a recorded pause has run out and still needs to be lifted.
+pub fn pause_expired(paused_until_ms: Option<u64>, now_ms: u64) -> bool {
+    paused_until_ms.is_some() && !is_paused(paused_until_ms, now_ms)
+}
+
+fn apply(autostart: &AutoLaunchManager, enabled: bool) -> Result<bool, String> {
+    let result = if enabled {
+        autostart.enable()
+    } else {
+        autostart.disable()
+    };
+    result
+        .and_then(|_| autostart.is_enabled())
+        .map_err(|err| err.to_string())
+}
+
+/// Brings the OS registration in line with the stored intent and drops an expired pause.
+pub fn reconcile_autostart(
+    autostart: &AutoLaunchManager,
+    preferences: &PreferencesStore,
+) -> Result<(), String> {
+    let now_ms = system_time_to_ms(SystemTime::now());
+    let stored = preferences.get();
+    if pause_expired(stored.autostart_paused_until_ms, now_ms) {
+        preferences.set("autostart_paused_until_ms", serde_json::Value::Null)?;
+    }
+    let registered = autostart.is_enabled().map_err(|err| err.to_string())?;
+    // Without a recorded intent the existing registration stands.
+    let enabled = effective_autostart(
+        stored.autostart_intended,
+        stored.autostart_paused_until_ms,
+        now_ms,
+    )
+    .unwrap_or(registered);
+    // Registering again while enabled rewrites entries from older versions so they pass `AUTOSTART_ARG`.
+    if enabled || registered {
+        apply(autostart, enabled)?;
+    }
+    Ok(())
+}
+
+/// Re-registers launch-on-boot when a pause ran out while the app was running.
+pub fn resume_expired_pause(
+    autostart: &AutoLaunchManager,
+    preferences: &PreferencesStore,
+) -> Result<(), String> {
+    let now_ms = system_time_to_ms(SystemTime::now());
+    if pause_expired(preferences.get().autostart_paused_until_ms, now_ms) {
+        reconcile_autostart(autostart, preferences)?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+/// Tauri command reporting whether launch-on-boot is currently registered.
+pub async fn get_autostart_enabled(
+    autostart: State<'_, AutoLaunchManager>,
+) -> Result<bool, String> {
+    autostart.is_enabled().map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+/// Tauri command recording the intended launch-on-boot setting, ending any pause.
+pub async fn set_autostart_enabled(
+    autostart: State<'_, AutoLaunchManager>,
+    preferences: State<'_, PreferencesStore>,
+    enabled: bool,
+) -> Result<bool, String> {
+    preferences.set("autostart_intended", json!(enabled))?;
+    preferences.set("autostart_paused_until_ms", serde_json::Value::Null)?;
+    apply(&autostart, enabled)
+}
+
+#[tauri::command]
+/// Tauri command pausing launch-on-boot until `until_ms`, or resuming it when `None`.
+pub async fn set_autostart_paused_until(
+    autostart: State<'_, AutoLaunchManager>,
+    preferences: State<'_, PreferencesStore>,
+    until_ms: Option<u64>,
+) -> Result<Preferences, String> {
+    if preferences.get().autostart_intended.is_none() {
+        let registered = autostart.is_enabled().map_err(|err| err.to_string())?;
+        preferences.set("autostart_intended", json!(registered))?;
+    }
+    let updated = preferences.set("autostart_paused_until_ms", json!(until_ms))?;
+    let now_ms = system_time_to_ms(SystemTime::now());
+    if let Some(enabled) = effective_autostart(
+        updated.autostart_intended,
+        updated.autostart_paused_until_ms,
+        now_ms,
+    ) {
+        apply(&autostart, enabled)?;
+    }
+    Ok(updated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pause_overrides_intent_until_it_expires() {
+        assert_eq!(
+            effective_autostart(Some(true), Some(2_000), 1_000),
+            Some(false)
+        );
+        assert_eq!(
+            effective_autostart(Some(true), Some(2_000), 2_000),
+            Some(true)
+        );
+        assert_eq!(effective_autostart(Some(true), None, 1_000), Some(true));
+        assert_eq!(
+            effective_autostart(Some(false), Some(500), 1_000),
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn only_a_recorded_pause_that_ran_out_has_expired() {
+        assert!(!pause_expired(None, 1_000));
+        assert!(!pause_expired(Some(2_000), 1_000));
+        assert!(pause_expired(Some(2_000), 2_000));
+        assert!(pause_expired(Some(500), 1_000));
+    }
+
+    #[test]
+    fn unrecorded_intent_leaves_registration_alone() {
+        assert_eq!(effective_autostart(None, Some(2_000), 1_000), None);
+        assert_eq!(effective_autostart(None, None, 1_000), None);
+    }
+}
@@ -0,0 +1,132 @@
+//! Scheduled backups that write a zip bundle of every stored artifact into a chosen folder.
+
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+use tauri::{AppHandle, Manager};
+
+use crate::app_usage::system_time_to_ms;
+use crate::bundle::export_to;
+use crate::error_log::log_error;
+use crate::preferences::{BackupSchedule, Preferences, PreferencesStore};
+use crate::startup_metrics::StartupMetrics;
+use crate::storage::ensure_writable_dir;
+use crate::usage_history::UsageHistory;
+
+/// How often the background task checks whether a backup is due.
+const BACKUP_CHECK_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+const DAY_MS: u64 = 24 * 60 * 60 * 1_000;
+
+/// Time between backups for `schedule`, or `None` when backups are off.
+fn backup_interval_ms(schedule: BackupSchedule) -> Option<u64> {
+    match schedule {
+        BackupSchedule::Off => None,
+        BackupSchedule::Daily => Some(DAY_MS),
+        BackupSchedule::Weekly => Some(7 * DAY_MS),
+    }
+}
+
+/// Returns true when `schedule` calls for a backup at `now_ms`, given when the last one was written.
+pub fn is_backup_due(now_ms: u64, schedule: BackupSchedule, last_backup_ms: Option<u64>) -> bool {
+    let Some(interval_ms) = backup_interval_ms(schedule) else {
+        return false;
+    };
+    last_backup_ms.is_none_or(|last| now_ms.saturating_sub(last) >= interval_ms)
+}
+
+/// File name of a backup written at `now_ms`, sortable by time, such as `time-wise-backup-20240131-221500.zip`.
+fn backup_file_name(now_ms: u64) -> String {
+    let written_at = chrono::DateTime::from_timestamp_millis(now_ms.min(i64::MAX as u64) as i64)
+        .unwrap_or_default();
+    format!(
+        "time-wise-backup-{}.zip",
+        written_at.format("%Y%m%d-%H%M%S")
+    )
+}
+
+/// Writes a bundle into the backup folder and records when, returning the updated preferences.
+fn back_up(app: &AppHandle, now_ms: u64) -> Result<Preferences, String> {
+    let preferences = app.state::<PreferencesStore>();
+    let backup_dir = preferences.get().backup_dir;
+    let backup_dir = Path::new(backup_dir.trim());
+    if backup_dir.as_os_str().is_empty() {
+        return Err("choose a backup folder first".to_string());
+    }
+    ensure_writable_dir(backup_dir)?;
+    export_to(
+        &backup_dir.join(backup_file_name(now_ms)),
+        &app.state::<StartupMetrics>(),
+        &app.state::<UsageHistory>(),
+        &preferences,
+    )?;
+    preferences.set("last_backup_ms", serde_json::json!(now_ms))
+}
+
+/// Checks periodically whether a scheduled backup is due and writes it.
+pub fn spawn_backup_task(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let preferences = app.state::<PreferencesStore>().get();
+            let now_ms = system_time_to_ms(SystemTime::now());
+            if !preferences.backup_dir.trim().is_empty()
+                && is_backup_due(
+                    now_ms,
+                    preferences.backup_schedule,
+                    preferences.last_backup_ms,
+                )
+            {
+                if let Err(err) = back_up(&app, now_ms) {
+                    log_error(&app, &format!("scheduled backup failed: {err}"));
+                }
+            }
+            tokio::time::sleep(BACKUP_CHECK_INTERVAL).await;
+        }
+    });
+}
+
+#[tauri::command]
+/// Tauri command writing a backup right away and returning the preferences with its time.
+pub fn back_up_now(app: AppHandle) -> Result<Preferences, String> {
+    back_up(&app, system_time_to_ms(SystemTime::now()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backups_are_due_once_the_schedule_interval_has_passed() {
+        let now_ms = 30 * DAY_MS;
+        assert!(!is_backup_due(now_ms, BackupSchedule::Off, None));
+        assert!(is_backup_due(now_ms, BackupSchedule::Daily, None));
+        assert!(is_backup_due(
+            now_ms,
+            BackupSchedule::Daily,
+            Some(now_ms - DAY_MS)
+        ));
+        assert!(!is_backup_due(
+            now_ms,
+            BackupSchedule::Daily,
+            Some(now_ms - DAY_MS + 1)
+        ));
+        assert!(!is_backup_due(
+            now_ms,
+            BackupSchedule::Weekly,
+            Some(now_ms - 6 * DAY_MS)
+        ));
+        assert!(is_backup_due(
+            now_ms,
+            BackupSchedule::Weekly,
+            Some(now_ms - 7 * DAY_MS)
+        ));
+    }
+
+    #[test]
+    fn backup_file_names_sort_by_time() {
+        assert_eq!(
+            backup_file_name(DAY_MS + 90_000),
+            "time-wise-backup-19700102-000130.zip"
+        );
+    }
+}
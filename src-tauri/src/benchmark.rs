@@ -0,0 +1,110 @@
+//! Debug-only startup benchmark: relaunches the app binary repeatedly and collects each measured startup.
+
+use std::process::Command;
+
+use serde::Serialize;
+use time_wise_shared::stats::median_ms;
+
+/// Set on benchmark children so they report their startup and exit once ready.
+pub const BENCHMARK_ENV: &str = "TIME_WISE_BENCHMARK";
+
+/// Prefix of the stdout line a benchmark child uses to report its startup duration.
+const RESULT_PREFIX: &str = "time-wise-benchmark-ms=";
+
+/// Upper bound on relaunches per benchmark run.
+const MAX_ITERATIONS: u32 = 50;
+
+/// Startup durations collected by a benchmark run with their spread.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchmarkSummary {
+    pub durations_ms: Vec<u64>,
+    pub min_ms: u64,
+    pub median_ms: u64,
+    pub max_ms: u64,
+    pub mean_ms: u64,
+}
+
+/// Returns true when this process was launched by `benchmark_startup`.
+pub fn is_benchmark_child() -> bool {
+    std::env::var_os(BENCHMARK_ENV).is_some()
+}
+
+/// Line a benchmark child prints so the parent can read its startup duration.
+pub fn report_line(duration_ms: u64) -> String {
+    format!("{RESULT_PREFIX}{duration_ms}")
+}
+
+/// Extracts the reported startup duration from a benchmark child's stdout.
+fn parse_report(output: &str) -> Option<u64> {
+    output
+        .lines()
+        .find_map(|line| line.trim().strip_prefix(RESULT_PREFIX))
+        .and_then(|value| value.parse().ok())
+}
+
+/// Aggregates measured durations, keeping them in run order.
+fn summarize(durations_ms: Vec<u64>) -> Option<BenchmarkSummary> {
+    let mut sorted = durations_ms.clone();
+    sorted.sort_unstable();
+    let min_ms = *sorted.first()?;
+    let max_ms = *sorted.last()?;
+    let median_ms = median_ms(&sorted)?;
+    let mean_ms = sorted.iter().sum::<u64>() / sorted.len() as u64;
+    Some(BenchmarkSummary {
+        durations_ms,
+        min_ms,
+        median_ms,
+        max_ms,
+        mean_ms,
+    })
+}
+
+/// Tauri command relaunching the app binary `iterations` times and summarizing each measured startup.
+#[tauri::command]
+pub async fn benchmark_startup(iterations: u32) -> Result<BenchmarkSummary, String> {
+    if iterations == 0 || iterations > MAX_ITERATIONS {
+        return Err(format!("iterations must be between 1 and {MAX_ITERATIONS}"));
+    }
+    let executable = std::env::current_exe().map_err(|err| err.to_string())?;
+
+    let mut durations_ms = Vec::with_capacity(iterations as usize);
+    for run in 1..=iterations {
+        let output = tauri::async_runtime::spawn_blocking({
+            let executable = executable.clone();
+            move || Command::new(executable).env(BENCHMARK_ENV, "1").output()
+        })
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(|err| err.to_string())?;
+        let duration_ms = parse_report(&String::from_utf8_lossy(&output.stdout))
+            .ok_or_else(|| format!("benchmark run {run} did not report a startup time"))?;
+        durations_ms.push(duration_ms);
+    }
+
+    summarize(durations_ms).ok_or_else(|| "benchmark produced no measurements".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summarize_reports_spread_in_run_order() {
+        let summary = summarize(vec![420, 380, 510, 400]).unwrap();
+        assert_eq!(summary.durations_ms, vec![420, 380, 510, 400]);
+        assert_eq!(summary.min_ms, 380);
+        assert_eq!(summary.median_ms, 410);
+        assert_eq!(summary.max_ms, 510);
+        assert_eq!(summary.mean_ms, 427);
+        assert_eq!(summarize(vec![300]).unwrap().median_ms, 300);
+        assert_eq!(summarize(Vec::new()), None);
+    }
+
+    #[test]
+    fn parse_report_reads_the_child_line() {
+        let stdout = format!("starting\n{}\n", report_line(612));
+        assert_eq!(parse_report(&stdout), Some(612));
+        assert_eq!(parse_report("no report"), None);
+    }
+}
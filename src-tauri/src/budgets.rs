@@ -0,0 +1,184 @@
+//! Daily usage budgets per app, with notifications as today's usage approaches and reaches them.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::app_usage::{system_time_to_ms, AppUsageRecorder};
+use crate::calendar::{day_start_ms, local_day_offset_minutes};
+use crate::preferences::{Preferences, PreferencesStore};
+use crate::usage_history::{usage_in_range, UsageHistory};
+
+/// Percentages of a budget at which a notification fires, lowest first.
+const BUDGET_THRESHOLDS: [u8; 2] = [80, 100];
+
+/// Today's usage of an app measured against its daily budget.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetUsage {
+    pub name: String,
+    pub used_ms: u64,
+    pub budget_ms: u64,
+}
+
+/// Remembers the highest threshold notified per app, keyed by the local day start.
+#[derive(Default)]
+pub struct BudgetAlertState {
+    notified: Mutex<HashMap<String, (u64, u8)>>,
+}
+
+impl BudgetAlertState {
+    /// Returns the highest threshold reached by `percent` if it was not notified yet today.
+    fn newly_crossed(&self, app: &str, day_start: u64, percent: u64) -> Option<u8> {
+        let reached = BUDGET_THRESHOLDS
+            .into_iter()
+            .rev()
+            .find(|threshold| percent >= u64::from(*threshold))?;
+        let mut notified = self.notified.lock().ok()?;
+        if let Some((day, highest)) = notified.get(app) {
+            if *day == day_start && *highest >= reached {
+                return None;
+            }
+        }
+        notified.insert(app.to_string(), (day_start, reached));
+        Some(reached)
+    }
+}
+
+/// Share of the budget used so far, uncapped so overruns stay visible.
+pub fn budget_percent(used_ms: u64, budget_ms: u64) -> u64 {
+    if budget_ms == 0 {
+        return 0;
+    }
+    used_ms.saturating_mul(100) / budget_ms
+}
+
+/// Measures today's usage of every app with a budget.
+fn budget_usage(app: &AppHandle, preferences: &Preferences, now_ms: u64) -> Vec<BudgetUsage> {
+    if preferences.app_budgets.is_empty() {
+        return Vec::new();
+    }
+
+    let today = day_start_ms(
+        now_ms,
+        local_day_offset_minutes(preferences.day_rollover_hour),
+    );
+    let mut sessions = app.state::<UsageHistory>().sessions_between(today, now_ms);
+    sessions.extend(app.state::<AppUsageRecorder>().open_sessions());
+    let mut used_by_app: HashMap<String, u64> = HashMap::new();
+    for record in usage_in_range(&sessions, today, now_ms) {
+        *used_by_app.entry(record.name).or_default() += record.total_active_ms;
+    }
+
+    preferences
+        .app_budgets
+        .iter()
+        .filter(|(_, minutes)| **minutes > 0)
+        .map(|(name, minutes)| BudgetUsage {
+            name: name.clone(),
+            used_ms: used_by_app.get(name).copied().unwrap_or(0),
+            budget_ms: u64::from(*minutes) * 60_000,
+        })
+        .collect()
+}
+
+/// Notifies once per threshold per day when an app's usage approaches or reaches its budget.
+pub fn check_usage_budgets(app: &AppHandle) {
+    let preferences = app.state::<PreferencesStore>().get();
+    let now_ms = system_time_to_ms(SystemTime::now());
+    let today = day_start_ms(
+        now_ms,
+        local_day_offset_minutes(preferences.day_rollover_hour),
+    );
+    let state = app.state::<BudgetAlertState>();
+
+    for usage in budget_usage(app, &preferences, now_ms) {
+        let percent = budget_percent(usage.used_ms, usage.budget_ms);
+        let Some(threshold) = state.newly_crossed(&usage.name, today, percent) else {
+            continue;
+        };
+        let body = if threshold >= 100 {
+            format!("You've reached today's budget for {}", usage.name)
+        } else {
+            format!(
+                "You've used {threshold}% of today's budget for {}",
+                usage.name
+            )
+        };
+        if let Err(err) = app
+            .notification()
+            .builder()
+            .title("Time Wise")
+            .body(body)
+            .show()
+        {
+            eprintln!("failed to show budget notification: {err}");
+        }
+    }
+}
+
+#[tauri::command]
+/// Tauri command returning today's usage for every app with a budget.
+pub fn fetch_budget_usage(app: AppHandle) -> Vec<BudgetUsage> {
+    let preferences = app.state::<PreferencesStore>().get();
+    budget_usage(&app, &preferences, system_time_to_ms(SystemTime::now()))
+}
+
+#[tauri::command]
+/// Tauri command setting an app's daily budget in minutes; zero removes it.
+pub fn set_app_budget(
+    state: tauri::State<'_, PreferencesStore>,
+    name: String,
+    minutes: u32,
+) -> Result<Preferences, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("app name must not be empty".to_string());
+    }
+    let mut budgets = state.get().app_budgets;
+    if minutes == 0 {
+        budgets.remove(name);
+    } else {
+        budgets.insert(name.to_string(), minutes);
+    }
+    let value = serde_json::to_value(budgets).map_err(|err| err.to_string())?;
+    state.set("app_budgets", value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY_MS: u64 = 24 * 60 * 60 * 1_000;
+
+    #[test]
+    fn budget_percent_reports_overruns() {
+        assert_eq!(budget_percent(30 * 60_000, 60 * 60_000), 50);
+        assert_eq!(budget_percent(90 * 60_000, 60 * 60_000), 150);
+        assert_eq!(budget_percent(1_000, 0), 0);
+    }
+
+    #[test]
+    fn thresholds_fire_once_per_day() {
+        let state = BudgetAlertState::default();
+        assert_eq!(state.newly_crossed("Game", DAY_MS, 50), None);
+        assert_eq!(state.newly_crossed("Game", DAY_MS, 80), Some(80));
+        assert_eq!(state.newly_crossed("Game", DAY_MS, 95), None);
+        assert_eq!(state.newly_crossed("Game", DAY_MS, 100), Some(100));
+        assert_eq!(state.newly_crossed("Game", DAY_MS, 140), None);
+
+        assert_eq!(state.newly_crossed("Game", 2 * DAY_MS, 85), Some(80));
+        assert_eq!(state.newly_crossed("Chat", 2 * DAY_MS, 100), Some(100));
+    }
+
+    #[test]
+    fn jumping_past_every_threshold_fires_only_the_highest() {
+        let state = BudgetAlertState::default();
+        assert_eq!(state.newly_crossed("Game", DAY_MS, 120), Some(100));
+        assert_eq!(state.newly_crossed("Game", DAY_MS, 120), None);
+    }
+}
@@ -0,0 +1,319 @@
+//! Exports startup records, usage sessions, and preferences to one zip archive, and restores them from it.
+
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
+
+use tauri::State;
+use zip::write::SimpleFileOptions;
+use zip::{ZipArchive, ZipWriter};
+
+use crate::preferences::{validate, Preferences, PreferencesStore};
+use crate::startup_metrics::{StartupMetrics, StartupRecord};
+use crate::usage_history::{UsageHistory, UsageSession};
+
+const STARTUP_ENTRY: &str = "startup.json";
+const USAGE_ENTRY: &str = "usage.json";
+const PREFERENCES_ENTRY: &str = "preferences.json";
+
+/// Everything a bundle carries, parsed and ready to apply.
+struct Bundle {
+    startup: Vec<StartupRecord>,
+    usage: Vec<UsageSession>,
+    preferences: Preferences,
+}
+
+fn write_bundle(path: &Path, bundle: &Bundle) -> Result<(), String> {
+    let file = File::create(path).map_err(|err| err.to_string())?;
+    let mut writer = ZipWriter::new(file);
+    let entries = [
+        (STARTUP_ENTRY, serde_json::to_vec_pretty(&bundle.startup)),
+        (USAGE_ENTRY, serde_json::to_vec_pretty(&bundle.usage)),
+        (
+            PREFERENCES_ENTRY,
+            serde_json::to_vec_pretty(&bundle.preferences),
+        ),
+    ];
+    for (name, contents) in entries {
+        let contents = contents.map_err(|err| err.to_string())?;
+        writer
+            .start_file(name, SimpleFileOptions::default())
+            .map_err(|err| err.to_string())?;
+        writer.write_all(&contents).map_err(|err| err.to_string())?;
+    }
+    writer.finish().map_err(|err| err.to_string())?;
+    Ok(())
+}
+
+fn read_entry<T: serde::de::DeserializeOwned>(
+    archive: &mut ZipArchive<File>,
+    name: &str,
+) -> Result<T, String> {
+    let mut entry = archive
+        .by_name(name)
+        .map_err(|_| format!("bundle is missing {name}"))?;
+    let mut contents = String::new();
+    entry
+        .read_to_string(&mut contents)
+        .map_err(|err| format!("failed to read {name}: {err}"))?;
+    serde_json::from_str(&contents).map_err(|err| format!("invalid {name}: {err}"))
+}
+
+/// Opens and parses every entry, so nothing is applied unless the whole bundle is valid.
+fn read_bundle(path: &Path) -> Result<Bundle, String> {
+    let file = File::open(path).map_err(|err| err.to_string())?;
+    let mut archive = ZipArchive::new(file).map_err(|err| format!("not a bundle: {err}"))?;
+    let startup = read_entry(&mut archive, STARTUP_ENTRY)?;
+    let usage = read_entry(&mut archive, USAGE_ENTRY)?;
+    let preferences = read_entry(&mut archive, PREFERENCES_ENTRY)?;
+    validate(&preferences).map_err(|err| format!("invalid {PREFERENCES_ENTRY}: {err}"))?;
+    Ok(Bundle {
+        startup,
+        usage,
+        preferences,
+    })
+}
+
+/// Writes every startup record, stored usage session, and the preferences to `path`.
+pub fn export_to(
+    path: &Path,
+    metrics: &StartupMetrics,
+    history: &UsageHistory,
+    preferences: &PreferencesStore,
+) -> Result<(), String> {
+    write_bundle(
+        path,
+        &Bundle {
+            startup: metrics.records(),
+            usage: history.sessions_between(0, u64::MAX),
+            preferences: preferences.get(),
+        },
+    )
+}
+
+/// Replaces startup records, usage sessions, and preferences with the bundle at `path` once it has been validated.
+///
+/// Both databases are staged in open transactions and the preferences are written last, so a
+/// failure anywhere leaves every store as it was. Returns the imported preferences.
+pub fn import_from(
+    path: &Path,
+    metrics: &StartupMetrics,
+    history: &UsageHistory,
+    preferences: &PreferencesStore,
+) -> Result<Preferences, String> {
+    let bundle = read_bundle(path)?;
+    let previous = preferences.get();
+    let mut preferences_written = false;
+    let imported = metrics.replace_records(&bundle.startup, || {
+        history.replace_sessions(&bundle.usage, || {
+            preferences.replace(bundle.preferences.clone())?;
+            preferences_written = true;
+            Ok(())
+        })
+    });
+    if let Err(err) = imported {
+        // A commit failed after the preferences were written, so put the old ones back.
+        if preferences_written {
+            if let Err(restore_err) = preferences.replace(previous) {
+                eprintln!("failed to restore preferences after a failed import: {restore_err}");
+            }
+        }
+        return Err(err);
+    }
+    Ok(bundle.preferences)
+}
+
+#[tauri::command]
+/// Tauri command exporting startup records, usage, and preferences to a zip bundle at `path`.
+pub fn export_bundle(
+    metrics: State<'_, StartupMetrics>,
+    history: State<'_, UsageHistory>,
+    preferences: State<'_, PreferencesStore>,
+    path: String,
+) -> Result<(), String> {
+    export_to(Path::new(&path), &metrics, &history, &preferences)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::environment::EnvironmentSnapshot;
+    use crate::preferences::StartupMeasurement;
+    use serde_json::json;
+    use std::time::Duration;
+
+    struct Stores {
+        metrics: StartupMetrics,
+        history: UsageHistory,
+        preferences: PreferencesStore,
+    }
+
+    fn stores(dir: &Path) -> Stores {
+        Stores {
+            metrics: StartupMetrics::with_storage_path(dir.join("startup.sqlite")),
+            history: UsageHistory::with_storage_path(dir.join("usage.sqlite")),
+            preferences: PreferencesStore::with_storage_path(dir.join("preferences.json")),
+        }
+    }
+
+    #[test]
+    fn bundles_round_trip_every_artifact() {
+        let source_dir = tempfile::tempdir().unwrap();
+        let source = stores(source_dir.path());
+        source
+            .metrics
+            .record_startup(
+                Duration::from_millis(640),
+                "Dock".to_string(),
+                EnvironmentSnapshot::default(),
+                StartupMeasurement::Process,
+                0,
+            )
+            .unwrap();
+        source
+            .history
+            .record_sessions(&[UsageSession {
+                name: "Code".to_string(),
+                executable: Some("/usr/bin/code".to_string()),
+                start_ms: 1_000,
+                end_ms: 61_000,
+            }])
+            .unwrap();
+        source
+            .preferences
+            .set("recent_chart_bars", json!(9))
+            .unwrap();
+
+        let bundle_path = source_dir.path().join("backup.zip");
+        export_to(
+            &bundle_path,
+            &source.metrics,
+            &source.history,
+            &source.preferences,
+        )
+        .unwrap();
+
+        let target_dir = tempfile::tempdir().unwrap();
+        let target = stores(target_dir.path());
+        import_from(
+            &bundle_path,
+            &target.metrics,
+            &target.history,
+            &target.preferences,
+        )
+        .unwrap();
+
+        assert_eq!(target.metrics.records(), source.metrics.records());
+        assert_eq!(
+            target.history.sessions_between(0, u64::MAX),
+            source.history.sessions_between(0, u64::MAX)
+        );
+        assert_eq!(target.preferences.get().recent_chart_bars, 9);
+    }
+
+    #[test]
+    fn incomplete_bundles_change_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = stores(dir.path());
+        target
+            .history
+            .record_sessions(&[UsageSession {
+                name: "Mail".to_string(),
+                executable: None,
+                start_ms: 0,
+                end_ms: 5_000,
+            }])
+            .unwrap();
+
+        let bundle_path = dir.path().join("partial.zip");
+        let mut writer = ZipWriter::new(File::create(&bundle_path).unwrap());
+        writer
+            .start_file(USAGE_ENTRY, SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(b"[]").unwrap();
+        writer.finish().unwrap();
+
+        let err = import_from(
+            &bundle_path,
+            &target.metrics,
+            &target.history,
+            &target.preferences,
+        )
+        .unwrap_err();
+        assert!(err.contains(STARTUP_ENTRY));
+        assert_eq!(target.history.sessions_between(0, u64::MAX).len(), 1);
+    }
+
+    #[test]
+    fn out_of_range_preferences_reject_the_bundle() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = stores(dir.path());
+        let bundle_path = dir.path().join("tampered.zip");
+        write_bundle(
+            &bundle_path,
+            &Bundle {
+                startup: Vec::new(),
+                usage: Vec::new(),
+                preferences: Preferences {
+                    duration_precision: 9,
+                    ..Preferences::default()
+                },
+            },
+        )
+        .unwrap();
+
+        let err = import_from(
+            &bundle_path,
+            &target.metrics,
+            &target.history,
+            &target.preferences,
+        )
+        .unwrap_err();
+        assert!(err.contains(PREFERENCES_ENTRY));
+        assert_eq!(target.preferences.get(), Preferences::default());
+    }
+
+    #[test]
+    fn failed_preference_write_rolls_back_both_databases() {
+        let source_dir = tempfile::tempdir().unwrap();
+        let source = stores(source_dir.path());
+        source
+            .history
+            .record_sessions(&[UsageSession {
+                name: "Code".to_string(),
+                executable: None,
+                start_ms: 1_000,
+                end_ms: 61_000,
+            }])
+            .unwrap();
+        let bundle_path = source_dir.path().join("backup.zip");
+        export_to(
+            &bundle_path,
+            &source.metrics,
+            &source.history,
+            &source.preferences,
+        )
+        .unwrap();
+
+        let target_dir = tempfile::tempdir().unwrap();
+        let target = stores(target_dir.path());
+        target
+            .metrics
+            .record_startup(
+                Duration::from_millis(640),
+                "Dock".to_string(),
+                EnvironmentSnapshot::default(),
+                StartupMeasurement::Process,
+                0,
+            )
+            .unwrap();
+        // A file where the preferences' folder should be makes the preferences write fail.
+        let blocker = target_dir.path().join("blocker");
+        std::fs::write(&blocker, b"").unwrap();
+        let preferences = PreferencesStore::with_storage_path(blocker.join("preferences.json"));
+
+        assert!(import_from(&bundle_path, &target.metrics, &target.history, &preferences).is_err());
+        assert_eq!(target.metrics.records().len(), 1);
+        assert!(target.history.sessions_between(0, u64::MAX).is_empty());
+    }
+}
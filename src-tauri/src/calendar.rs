@@ -0,0 +1,123 @@
+//! Local calendar helpers shared by the per-day computations.
+
+const MS_PER_MINUTE: i64 = 60_000;
+const MS_PER_DAY: i64 = 24 * 60 * MS_PER_MINUTE;
+
+/// Offset of the local timezone from UTC in minutes.
+pub fn local_offset_minutes() -> i32 {
+    chrono::Local::now().offset().local_minus_utc() / 60
+}
+
+/// Folds a day rollover hour into a timezone offset so `day_start_ms` starts days at that hour.
+///
+/// With a rollover of 4, anything before 04:00 local time counts toward the previous day.
+pub fn day_offset_minutes(offset_minutes: i32, rollover_hour: u32) -> i32 {
+    offset_minutes - rollover_hour.min(23) as i32 * 60
+}
+
+/// Offset of the local day, honoring the user's rollover hour, from UTC in minutes.
+pub fn local_day_offset_minutes(rollover_hour: u32) -> i32 {
+    day_offset_minutes(local_offset_minutes(), rollover_hour)
+}
+
+/// Returns the epoch milliseconds of the local midnight that starts the day containing `timestamp_ms`.
+pub fn day_start_ms(timestamp_ms: u64, offset_minutes: i32) -> u64 {
+    let offset_ms = i64::from(offset_minutes) * MS_PER_MINUTE;
+    let local_ms = timestamp_ms.min(i64::MAX as u64) as i64 + offset_ms;
+    let local_day_start = local_ms.div_euclid(MS_PER_DAY) * MS_PER_DAY;
+    (local_day_start - offset_ms).max(0) as u64
+}
+
+/// Local weekday of `timestamp_ms`, counting Monday as 0 and Sunday as 6.
+pub fn weekday_index(timestamp_ms: u64, offset_minutes: i32) -> usize {
+    let local_ms =
+        timestamp_ms.min(i64::MAX as u64) as i64 + i64::from(offset_minutes) * MS_PER_MINUTE;
+    // The epoch fell on a Thursday.
+    (local_ms.div_euclid(MS_PER_DAY) + 3).rem_euclid(7) as usize
+}
+
+/// Local hour of day (0–23) of `timestamp_ms`.
+pub fn hour_of_day(timestamp_ms: u64, offset_minutes: i32) -> usize {
+    let local_ms =
+        timestamp_ms.min(i64::MAX as u64) as i64 + i64::from(offset_minutes) * MS_PER_MINUTE;
+    (local_ms.rem_euclid(MS_PER_DAY) / (60 * MS_PER_MINUTE)) as usize
+}
+
+/// Returns the start of the local week containing `timestamp_ms`, with weeks starting on `first_weekday` (Monday = 0).
+pub fn week_start_ms(timestamp_ms: u64, offset_minutes: i32, first_weekday: usize) -> u64 {
+    let days_into_week = (weekday_index(timestamp_ms, offset_minutes) + 7 - first_weekday % 7) % 7;
+    day_start_ms(timestamp_ms, offset_minutes)
+        .saturating_sub(days_into_week as u64 * MS_PER_DAY as u64)
+}
+
+/// Returns the local midnight `days` days before the day containing `now_ms`.
+pub fn days_ago_start_ms(now_ms: u64, offset_minutes: i32, days: u32) -> u64 {
+    day_start_ms(now_ms, offset_minutes).saturating_sub(u64::from(days) * MS_PER_DAY as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn day_start_floors_to_utc_midnight_without_offset() {
+        let noon = 10 * MS_PER_DAY as u64 + 12 * 60 * 60_000;
+        assert_eq!(day_start_ms(noon, 0), 10 * MS_PER_DAY as u64);
+    }
+
+    #[test]
+    fn day_start_respects_positive_and_negative_offsets() {
+        // 23:30 UTC is already the next day at UTC+9 and still the same day at UTC-5.
+        let late_evening = 10 * MS_PER_DAY as u64 + 23 * 60 * 60_000 + 30 * 60_000;
+        assert_eq!(
+            day_start_ms(late_evening, 9 * 60),
+            11 * MS_PER_DAY as u64 - 9 * 60 * 60_000
+        );
+        assert_eq!(
+            day_start_ms(late_evening, -5 * 60),
+            10 * MS_PER_DAY as u64 + 5 * 60 * 60_000
+        );
+    }
+
+    #[test]
+    fn weekday_index_counts_from_monday_in_local_time() {
+        // 1970-01-01 was a Thursday; 1970-01-05 a Monday.
+        assert_eq!(weekday_index(0, 0), 3);
+        assert_eq!(weekday_index(4 * MS_PER_DAY as u64, 0), 0);
+        // 20:00 UTC on that Monday is already Tuesday at UTC+9.
+        assert_eq!(
+            weekday_index(4 * MS_PER_DAY as u64 + 20 * 60 * 60_000, 9 * 60),
+            1
+        );
+    }
+
+    #[test]
+    fn days_ago_start_steps_back_whole_local_days() {
+        let noon = 10 * MS_PER_DAY as u64 + 12 * 60 * 60_000;
+        assert_eq!(days_ago_start_ms(noon, 0, 3), 7 * MS_PER_DAY as u64);
+        assert_eq!(days_ago_start_ms(noon, 0, 30), 0);
+    }
+
+    #[test]
+    fn rollover_hour_moves_the_day_boundary() {
+        let day = 10 * MS_PER_DAY as u64;
+        let hour = 60 * 60_000;
+        let offset = day_offset_minutes(0, 4);
+
+        // 03:59 still belongs to the previous day; 04:00 starts a new one.
+        assert_eq!(
+            day_start_ms(day + 4 * hour - 60_000, offset),
+            day - MS_PER_DAY as u64 + 4 * hour
+        );
+        assert_eq!(day_start_ms(day + 4 * hour, offset), day + 4 * hour);
+        assert_eq!(day_start_ms(day + 23 * hour, offset), day + 4 * hour);
+
+        // The rollover applies in local time, on top of the timezone offset.
+        assert_eq!(
+            day_start_ms(day + 2 * hour, day_offset_minutes(9 * 60, 4)),
+            day - 5 * hour
+        );
+        assert_eq!(day_offset_minutes(60, 0), 60);
+        assert_eq!(day_offset_minutes(0, 99), -23 * 60);
+    }
+}
@@ -0,0 +1,343 @@
+//! Groups app usage under user-assigned categories.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::time::SystemTime;
+
+use serde::Serialize;
+use time_wise_shared::preferences::UNCATEGORIZED;
+
+use crate::app_usage::{system_time_to_ms, AppUsageRecord, AppUsageRecorder};
+use crate::calendar::{day_start_ms, local_day_offset_minutes};
+use crate::preferences::{Preferences, PreferencesStore};
+use crate::usage_history::{usage_in_range, UsageHistory};
+
+/// A category with its apps and their combined usage.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageCategory {
+    pub category: String,
+    pub total_ms: u64,
+    pub apps: Vec<AppUsageRecord>,
+}
+
+/// Nests records under their category, sorted by category total and then by app usage.
+pub fn group_usage_by_category(
+    records: &[AppUsageRecord],
+    categories: &BTreeMap<String, String>,
+) -> Vec<UsageCategory> {
+    let mut groups: HashMap<String, UsageCategory> = HashMap::new();
+
+    for record in records {
+        let category = categories
+            .get(&record.name)
+            .map(|category| category.trim())
+            .filter(|category| !category.is_empty())
+            .unwrap_or(UNCATEGORIZED);
+        let group = groups
+            .entry(category.to_string())
+            .or_insert_with(|| UsageCategory {
+                category: category.to_string(),
+                total_ms: 0,
+                apps: Vec::new(),
+            });
+        group.total_ms = group.total_ms.saturating_add(record.total_active_ms);
+        group.apps.push(record.clone());
+    }
+
+    let mut tree: Vec<_> = groups.into_values().collect();
+    for group in &mut tree {
+        group.apps.sort_by(|a, b| {
+            b.total_active_ms
+                .cmp(&a.total_active_ms)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+    }
+    tree.sort_by(|a, b| {
+        b.total_ms
+            .cmp(&a.total_ms)
+            .then_with(|| a.category.cmp(&b.category))
+    });
+    tree
+}
+
+/// Combined usage of one category.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryTotal {
+    pub category: String,
+    pub total_ms: u64,
+}
+
+/// Sums usage per category, largest first.
+pub fn category_totals(
+    records: &[AppUsageRecord],
+    categories: &BTreeMap<String, String>,
+) -> Vec<CategoryTotal> {
+    group_usage_by_category(records, categories)
+        .into_iter()
+        .map(|group| CategoryTotal {
+            category: group.category,
+            total_ms: group.total_ms,
+        })
+        .collect()
+}
+
+/// Today's usage per category, with the day starting at local midnight shifted by `tz_offset`.
+pub fn usage_by_category(
+    history: &UsageHistory,
+    recorder: &AppUsageRecorder,
+    categories: &BTreeMap<String, String>,
+    tz_offset: i32,
+    now_ms: u64,
+) -> Vec<CategoryTotal> {
+    let today = day_start_ms(now_ms, tz_offset);
+    let mut sessions = history.sessions_between(today, now_ms);
+    sessions.extend(recorder.open_sessions());
+    category_totals(&usage_in_range(&sessions, today, now_ms), categories)
+}
+
+#[tauri::command]
+/// Tauri command returning today's usage totals per category.
+pub fn fetch_usage_by_category(
+    history: tauri::State<'_, UsageHistory>,
+    recorder: tauri::State<'_, AppUsageRecorder>,
+    preferences: tauri::State<'_, PreferencesStore>,
+) -> Vec<CategoryTotal> {
+    let preferences = preferences.get();
+    usage_by_category(
+        &history,
+        &recorder,
+        &preferences.app_categories,
+        local_day_offset_minutes(preferences.day_rollover_hour),
+        system_time_to_ms(SystemTime::now()),
+    )
+}
+
+#[tauri::command]
+/// Tauri command assigning an app to a category; a blank category removes the assignment.
+pub fn set_app_category(
+    state: tauri::State<'_, PreferencesStore>,
+    name: String,
+    category: String,
+) -> Result<Preferences, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("app name must not be empty".to_string());
+    }
+    let mut categories = state.get().app_categories;
+    match category.trim() {
+        "" => categories.remove(name),
+        category => categories.insert(name.to_string(), category.to_string()),
+    };
+    let value = serde_json::to_value(categories).map_err(|err| err.to_string())?;
+    state.set("app_categories", value)
+}
+
+/// Applies many name-to-category assignments in one write, returning how many were applied.
+///
+/// Blank app names are skipped; a blank category removes that app's assignment.
+pub fn assign_categories(
+    store: &PreferencesStore,
+    assignments: &[(String, String)],
+) -> Result<usize, String> {
+    let mut categories = store.get().app_categories;
+    let mut applied = 0;
+    for (name, category) in assignments {
+        let name = name.trim();
+        if name.is_empty() {
+            continue;
+        }
+        match category.trim() {
+            "" => categories.remove(name),
+            category => categories.insert(name.to_string(), category.to_string()),
+        };
+        applied += 1;
+    }
+    let value = serde_json::to_value(categories).map_err(|err| err.to_string())?;
+    store.set("app_categories", value)?;
+    Ok(applied)
+}
+
+#[tauri::command]
+/// Tauri command assigning categories to many apps at once, returning how many were applied.
+pub fn set_app_categories(
+    state: tauri::State<'_, PreferencesStore>,
+    assignments: Vec<(String, String)>,
+) -> Result<usize, String> {
+    assign_categories(&state, &assignments)
+}
+
+/// Every app that can be given a category: those in the stored history, those tracked now, and those already assigned one.
+pub fn categorizable_apps(
+    history_names: impl IntoIterator<Item = String>,
+    records: &[AppUsageRecord],
+    categories: &BTreeMap<String, String>,
+) -> Vec<String> {
+    let names: BTreeSet<String> = history_names
+        .into_iter()
+        .chain(records.iter().map(|record| record.name.clone()))
+        .chain(categories.keys().cloned())
+        .collect();
+    names.into_iter().collect()
+}
+
+#[tauri::command]
+/// Tauri command listing the apps the category table offers, including ones not seen recently.
+pub fn fetch_categorizable_apps(
+    history: tauri::State<'_, UsageHistory>,
+    recorder: tauri::State<'_, AppUsageRecorder>,
+    preferences: tauri::State<'_, PreferencesStore>,
+) -> Vec<String> {
+    categorizable_apps(
+        history.last_seen_by_app().into_iter().map(|(name, _)| name),
+        &recorder.records(),
+        &preferences.get().app_categories,
+    )
+}
+
+#[tauri::command]
+/// Tauri command returning the current usage nested by category.
+pub fn fetch_usage_tree(
+    recorder: tauri::State<'_, AppUsageRecorder>,
+    preferences: tauri::State<'_, PreferencesStore>,
+) -> Vec<UsageCategory> {
+    group_usage_by_category(&recorder.records(), &preferences.get().app_categories)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(name: &str, total_active_ms: u64) -> AppUsageRecord {
+        AppUsageRecord {
+            name: name.to_string(),
+            executable: None,
+            total_active_ms,
+            last_seen_at_ms: 0,
+            first_seen_at_ms: 0,
+            active: false,
+            window_title: None,
+        }
+    }
+
+    #[test]
+    fn categorizable_apps_include_history_and_assigned_apps() {
+        let categories = BTreeMap::from([("Journal".to_string(), "Personal".to_string())]);
+        let apps = categorizable_apps(
+            ["Mail".to_string(), "Code".to_string()],
+            &[record("Code", 1_000), record("Terminal", 500)],
+            &categories,
+        );
+
+        assert_eq!(apps, vec!["Code", "Journal", "Mail", "Terminal"]);
+    }
+
+    #[test]
+    fn groups_records_by_assigned_category() {
+        let categories = BTreeMap::from([
+            ("Code".to_string(), "Work".to_string()),
+            ("Terminal".to_string(), "Work".to_string()),
+            ("Chat".to_string(), "Social".to_string()),
+        ]);
+        let records = vec![
+            record("Chat", 4_000),
+            record("Code", 3_000),
+            record("Terminal", 2_000),
+            record("Game", 500),
+        ];
+
+        let tree = group_usage_by_category(&records, &categories);
+
+        let summary: Vec<_> = tree
+            .iter()
+            .map(|group| (group.category.as_str(), group.total_ms))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![("Work", 5_000), ("Social", 4_000), (UNCATEGORIZED, 500)]
+        );
+        let work_apps: Vec<_> = tree[0].apps.iter().map(|app| app.name.as_str()).collect();
+        assert_eq!(work_apps, vec!["Code", "Terminal"]);
+        assert_eq!(tree[2].apps[0].name, "Game");
+    }
+
+    #[test]
+    fn category_totals_sum_apps_sharing_a_category() {
+        let categories = BTreeMap::from([
+            ("Code".to_string(), "Work".to_string()),
+            ("Mail".to_string(), "Work".to_string()),
+            ("Chat".to_string(), "Social".to_string()),
+        ]);
+        let records = vec![
+            record("Code", 3_000),
+            record("Mail", 1_500),
+            record("Chat", 2_000),
+        ];
+
+        assert_eq!(
+            category_totals(&records, &categories),
+            vec![
+                CategoryTotal {
+                    category: "Work".to_string(),
+                    total_ms: 4_500,
+                },
+                CategoryTotal {
+                    category: "Social".to_string(),
+                    total_ms: 2_000,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn bulk_assignments_persist_and_drive_category_totals() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("preferences.json");
+        let store = PreferencesStore::with_storage_path(path.clone());
+        let assignments = vec![
+            ("Code".to_string(), "Work".to_string()),
+            ("Mail".to_string(), " Work ".to_string()),
+            ("Chat".to_string(), "Social".to_string()),
+            ("  ".to_string(), "Ignored".to_string()),
+        ];
+
+        assert_eq!(assign_categories(&store, &assignments), Ok(3));
+
+        let reloaded = PreferencesStore::with_storage_path(path).get();
+        let records = vec![
+            record("Code", 3_000),
+            record("Mail", 1_000),
+            record("Chat", 2_500),
+            record("Game", 500),
+        ];
+        assert_eq!(
+            category_totals(&records, &reloaded.app_categories),
+            vec![
+                CategoryTotal {
+                    category: "Work".to_string(),
+                    total_ms: 4_000,
+                },
+                CategoryTotal {
+                    category: "Social".to_string(),
+                    total_ms: 2_500,
+                },
+                CategoryTotal {
+                    category: UNCATEGORIZED.to_string(),
+                    total_ms: 500,
+                },
+            ]
+        );
+
+        // A blank category in a later batch clears the earlier assignment.
+        let cleared = vec![("Chat".to_string(), String::new())];
+        assert_eq!(assign_categories(&store, &cleared), Ok(1));
+        assert!(!store.get().app_categories.contains_key("Chat"));
+    }
+
+    #[test]
+    fn blank_categories_fall_back_to_uncategorized() {
+        let categories = BTreeMap::from([("Code".to_string(), "  ".to_string())]);
+        let tree = group_usage_by_category(&[record("Code", 1_000)], &categories);
+        assert_eq!(tree[0].category, UNCATEGORIZED);
+    }
+}
@@ -0,0 +1,110 @@
+//! Captures battery, thermal, and CPU conditions at launch so slow starts can be correlated with them.
+
+use starship_battery::units::ratio::percent;
+use sysinfo::{get_current_pid, ProcessRefreshKind, ProcessesToUpdate, System};
+
+/// System conditions observed when a startup was recorded; `None` when the platform cannot tell.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EnvironmentSnapshot {
+    pub battery_percent: Option<u8>,
+    pub thermal_pressure: Option<bool>,
+    /// CPU time this process had used by the time it was ready, in milliseconds.
+    pub cpu_time_ms: Option<u64>,
+}
+
+impl EnvironmentSnapshot {
+    /// Reads the current battery level and thermal state.
+    pub fn capture() -> Self {
+        Self {
+            battery_percent: battery_percent(),
+            thermal_pressure: thermal_pressure(),
+            cpu_time_ms: None,
+        }
+    }
+}
+
+/// CPU time the current process has used so far, in milliseconds.
+pub fn process_cpu_time_ms() -> Option<u64> {
+    let pid = get_current_pid().ok()?;
+    let mut system = System::new();
+    system.refresh_processes_specifics(
+        ProcessesToUpdate::Some(&[pid]),
+        false,
+        ProcessRefreshKind::nothing().with_cpu(),
+    );
+    system
+        .process(pid)
+        .map(|process| process.accumulated_cpu_time())
+}
+
+fn battery_percent() -> Option<u8> {
+    let manager = starship_battery::Manager::new().ok()?;
+    let battery = manager.batteries().ok()?.flatten().next()?;
+    let level = battery.state_of_charge().get::<percent>();
+    Some(level.round().clamp(0.0, 100.0) as u8)
+}
+
+#[cfg(target_os = "macos")]
+fn thermal_pressure() -> Option<bool> {
+    let output = std::process::Command::new("pmset")
+        .args(["-g", "therm"])
+        .output()
+        .ok()?;
+    parse_cpu_speed_limit(&String::from_utf8_lossy(&output.stdout))
+}
+
+#[cfg(target_os = "linux")]
+fn thermal_pressure() -> Option<bool> {
+    let mut any_zone = false;
+    for zone in std::fs::read_dir("/sys/class/thermal").ok()?.flatten() {
+        let path = zone.path();
+        let read_milli = |file: &str| -> Option<i64> {
+            std::fs::read_to_string(path.join(file))
+                .ok()?
+                .trim()
+                .parse()
+                .ok()
+        };
+        let (Some(temp), Some(passive)) = (read_milli("temp"), read_milli("trip_point_0_temp"))
+        else {
+            continue;
+        };
+        any_zone = true;
+        if passive > 0 && temp >= passive {
+            return Some(true);
+        }
+    }
+    any_zone.then_some(false)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+fn thermal_pressure() -> Option<bool> {
+    None
+}
+
+/// Parses `pmset -g therm`; a CPU speed limit below 100% means the system is throttling.
+#[cfg_attr(not(any(target_os = "macos", test)), allow(dead_code))]
+fn parse_cpu_speed_limit(output: &str) -> Option<bool> {
+    output.lines().find_map(|line| {
+        let (key, value) = line.split_once('=')?;
+        if key.trim() != "CPU_Speed_Limit" {
+            return None;
+        }
+        let limit: u32 = value.trim().parse().ok()?;
+        Some(limit < 100)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cpu_speed_limit_below_full_speed_is_thermal_pressure() {
+        let throttled =
+            "Note: No thermal warning level has been recorded\n\tCPU_Speed_Limit \t= 70\n";
+        assert_eq!(parse_cpu_speed_limit(throttled), Some(true));
+        assert_eq!(parse_cpu_speed_limit("CPU_Speed_Limit = 100"), Some(false));
+        assert_eq!(parse_cpu_speed_limit("No thermal data"), None);
+    }
+}
@@ -0,0 +1,109 @@
+//! Keeps the most recent backend errors in memory so Settings can show them for bug reports.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use tauri::{AppHandle, Manager};
+
+/// Most errors kept; the oldest are dropped first so memory stays bounded.
+const MAX_ERROR_LOG_ENTRIES: usize = 200;
+
+/// Thread-safe ring buffer of recent error messages.
+pub struct ErrorLog {
+    capacity: usize,
+    entries: Mutex<VecDeque<String>>,
+}
+
+impl Default for ErrorLog {
+    fn default() -> Self {
+        Self::with_capacity(MAX_ERROR_LOG_ENTRIES)
+    }
+}
+
+impl ErrorLog {
+    /// Creates a log that keeps at most `capacity` entries.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    /// Appends `entry`, dropping the oldest one when the log is full.
+    pub fn push(&self, entry: String) {
+        let Ok(mut entries) = self.entries.lock() else {
+            return;
+        };
+        if self.capacity == 0 {
+            return;
+        }
+        while entries.len() >= self.capacity {
+            entries.pop_front();
+        }
+        entries.push_back(entry);
+    }
+
+    /// Returns up to `limit` entries, newest first.
+    pub fn recent(&self, limit: usize) -> Vec<String> {
+        match self.entries.lock() {
+            Ok(entries) => entries.iter().rev().take(limit).cloned().collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// Forgets every entry.
+    pub fn clear(&self) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.clear();
+        }
+    }
+}
+
+/// Prints `message` to stderr and keeps it, with a UTC timestamp, in the app's error log.
+pub fn log_error(app: &AppHandle, message: &str) {
+    eprintln!("{message}");
+    if let Some(log) = app.try_state::<ErrorLog>() {
+        log.push(format!(
+            "{} {message}",
+            chrono::Utc::now().format("%Y-%m-%d %H:%M:%S")
+        ));
+    }
+}
+
+#[tauri::command]
+/// Tauri command returning up to `limit` recent errors, newest first.
+pub fn recent_errors(log: tauri::State<'_, ErrorLog>, limit: usize) -> Vec<String> {
+    log.recent(limit)
+}
+
+#[tauri::command]
+/// Tauri command emptying the in-memory error log.
+pub fn clear_error_log(log: tauri::State<'_, ErrorLog>) {
+    log.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn full_log_drops_the_oldest_entries() {
+        let log = ErrorLog::with_capacity(3);
+        for index in 1..=5 {
+            log.push(format!("error {index}"));
+        }
+        assert_eq!(log.recent(10), vec!["error 5", "error 4", "error 3"]);
+        assert_eq!(log.recent(1), vec!["error 5"]);
+    }
+
+    #[test]
+    fn clear_empties_the_log_and_keeps_it_usable() {
+        let log = ErrorLog::with_capacity(2);
+        log.push("first".to_string());
+        log.clear();
+        assert!(log.recent(10).is_empty());
+
+        log.push("second".to_string());
+        assert_eq!(log.recent(10), vec!["second"]);
+    }
+}
@@ -0,0 +1,133 @@
+//! Counts unexpected exits with a marker file that only exists while the app is running.
+
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use crate::app_usage::system_time_to_ms;
+use crate::calendar::{local_day_offset_minutes, week_start_ms};
+use crate::preferences::PreferencesStore;
+
+/// Most unclean exits remembered; older ones are dropped first.
+const MAX_UNCLEAN_EXITS: usize = 100;
+
+/// Marker and history files used to detect runs that ended without a graceful exit.
+pub struct ExitTracker {
+    marker_path: PathBuf,
+    log_path: PathBuf,
+}
+
+impl ExitTracker {
+    pub fn new(marker_path: PathBuf, log_path: PathBuf) -> Self {
+        Self {
+            marker_path,
+            log_path,
+        }
+    }
+
+    /// Marks this run as started, first recording an unclean exit at `now_ms` when the previous run left its marker behind.
+    ///
+    /// Returns whether the previous run ended unexpectedly.
+    pub fn begin_run(&self, now_ms: u64) -> Result<bool, String> {
+        let unclean = self.marker_path.exists();
+        if unclean {
+            let mut exits = self.unclean_exits();
+            exits.push(now_ms);
+            let excess = exits.len().saturating_sub(MAX_UNCLEAN_EXITS);
+            exits.drain(..excess);
+            let data = serde_json::to_string(&exits).map_err(|err| err.to_string())?;
+            fs::write(&self.log_path, data).map_err(|err| err.to_string())?;
+        }
+        if let Some(parent) = self.marker_path.parent() {
+            fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+        }
+        fs::write(&self.marker_path, b"").map_err(|err| err.to_string())?;
+        Ok(unclean)
+    }
+
+    /// Removes the marker so the next start knows this run exited gracefully.
+    pub fn end_run(&self) -> Result<(), String> {
+        match fs::remove_file(&self.marker_path) {
+            Err(err) if err.kind() != ErrorKind::NotFound => Err(err.to_string()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Number of unclean exits detected at or after `since_ms`.
+    pub fn unclean_exits_since(&self, since_ms: u64) -> usize {
+        self.unclean_exits()
+            .into_iter()
+            .filter(|detected_at_ms| *detected_at_ms >= since_ms)
+            .count()
+    }
+
+    fn unclean_exits(&self) -> Vec<u64> {
+        fs::read_to_string(&self.log_path)
+            .ok()
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_default()
+    }
+}
+
+#[tauri::command]
+/// Tauri command returning how many unexpected exits were detected since the week began, or `None` when not tracked.
+///
+/// The count starts over on the configured `week_start`, the same boundary the weekly summary uses.
+pub fn unclean_exits_this_week(
+    tracker: tauri::State<'_, ExitTracker>,
+    preferences: tauri::State<'_, PreferencesStore>,
+) -> Option<usize> {
+    let preferences = preferences.get();
+    preferences.track_unclean_exits.then(|| {
+        let now_ms = system_time_to_ms(SystemTime::now());
+        tracker.unclean_exits_since(week_start_ms(
+            now_ms,
+            local_day_offset_minutes(preferences.day_rollover_hour),
+            preferences.week_start.weekday_index(),
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WEEK_MS: u64 = 7 * 24 * 60 * 60 * 1_000;
+
+    fn tracker(dir: &std::path::Path) -> ExitTracker {
+        ExitTracker::new(dir.join("running.marker"), dir.join("unclean_exits.json"))
+    }
+
+    #[test]
+    fn leftover_marker_counts_as_an_unclean_exit() {
+        let dir = tempfile::tempdir().unwrap();
+        let tracker = tracker(dir.path());
+
+        assert_eq!(tracker.begin_run(1_000), Ok(false));
+        assert_eq!(tracker.begin_run(2_000), Ok(true));
+        assert_eq!(tracker.unclean_exits_since(0), 1);
+
+        tracker.end_run().unwrap();
+        assert_eq!(tracker.begin_run(3_000), Ok(false));
+        assert_eq!(tracker.unclean_exits_since(0), 1);
+    }
+
+    #[test]
+    fn only_recent_unclean_exits_are_counted() {
+        let dir = tempfile::tempdir().unwrap();
+        let tracker = tracker(dir.path());
+
+        for now_ms in [1_000, WEEK_MS, WEEK_MS + 5_000, 2 * WEEK_MS] {
+            tracker.begin_run(now_ms).unwrap();
+        }
+        assert_eq!(tracker.unclean_exits_since(WEEK_MS), 3);
+        assert_eq!(tracker.unclean_exits_since(2 * WEEK_MS), 1);
+    }
+
+    #[test]
+    fn ending_without_a_marker_is_not_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(tracker(dir.path()).end_run(), Ok(()));
+    }
+}
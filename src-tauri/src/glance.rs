@@ -0,0 +1,123 @@
+//! Today's launches, usage, current app, and latest startup in one payload for the glance window and tray tooltip.
+
+use std::time::SystemTime;
+
+use serde::Serialize;
+
+use crate::app_usage::{system_time_to_ms, AppUsageRecorder};
+use crate::calendar::{day_start_ms, local_day_offset_minutes};
+use crate::preferences::PreferencesStore;
+use crate::startup_metrics::StartupMetrics;
+use crate::usage_history::{sessions_including_open, UsageHistory};
+
+/// A compact summary of the current local day.
+#[derive(Debug, Clone, Default, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TodayGlance {
+    pub launches_today: u64,
+    pub usage_today_ms: u64,
+    /// The app opened most recently among those still running.
+    pub active_app: Option<String>,
+    pub last_startup_ms: Option<u64>,
+}
+
+/// Aggregates the day containing `now_ms`, whose boundary falls at `rollover_hour` local time.
+pub fn today_glance(
+    metrics: &StartupMetrics,
+    history: &UsageHistory,
+    recorder: &AppUsageRecorder,
+    now_ms: u64,
+    rollover_hour: u32,
+) -> TodayGlance {
+    let day_offset = local_day_offset_minutes(rollover_hour);
+    let today_ms = day_start_ms(now_ms, day_offset);
+    TodayGlance {
+        launches_today: metrics.records_today(now_ms, day_offset),
+        usage_today_ms: sessions_including_open(history, recorder, today_ms, now_ms)
+            .iter()
+            .map(|session| session.overlap_ms(today_ms, now_ms))
+            .sum(),
+        active_app: recorder.latest_active_app(),
+        last_startup_ms: metrics.records().first().map(|record| record.duration_ms),
+    }
+}
+
+#[tauri::command]
+/// Tauri command returning today's glance summary.
+pub fn fetch_today_glance(
+    metrics: tauri::State<'_, StartupMetrics>,
+    history: tauri::State<'_, UsageHistory>,
+    recorder: tauri::State<'_, AppUsageRecorder>,
+    preferences: tauri::State<'_, PreferencesStore>,
+) -> TodayGlance {
+    today_glance(
+        &metrics,
+        &history,
+        &recorder,
+        system_time_to_ms(SystemTime::now()),
+        preferences.get().day_rollover_hour,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::{params, Connection};
+    use std::time::{Duration, Instant, UNIX_EPOCH};
+
+    const DAY_MS: u64 = 24 * 60 * 60 * 1_000;
+
+    #[test]
+    fn glance_combines_todays_startups_and_usage() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage_path = dir.path().join("records.sqlite");
+        let metrics = StartupMetrics::with_storage_path(storage_path.clone());
+        // Local noon, so the seeded runs stay on the same local day in any timezone.
+        let offset_ms = i64::from(local_day_offset_minutes(0)) * 60_000;
+        let now_ms = ((20 * DAY_MS + DAY_MS / 2) as i64 - offset_ms) as u64;
+
+        let seed_connection = Connection::open(&storage_path).unwrap();
+        for (recorded_at_ms, duration_ms) in [
+            (now_ms - DAY_MS, 900i64),
+            (now_ms - 60_000, 700),
+            (now_ms - 1_000, 420),
+        ] {
+            seed_connection
+                .execute(
+                    "INSERT INTO startup_records (recorded_at_ms, duration_ms, launcher) VALUES (?1, ?2, ?3)",
+                    params![recorded_at_ms as i64, duration_ms, "Dock"],
+                )
+                .unwrap();
+        }
+
+        let recorder = AppUsageRecorder::new();
+        let instant_start = Instant::now();
+        let system_start = UNIX_EPOCH + Duration::from_millis(now_ms - 120_000);
+        let at = |secs: u64| {
+            (
+                instant_start + Duration::from_secs(secs),
+                system_start + Duration::from_secs(secs),
+            )
+        };
+        let (instant, system) = at(0);
+        recorder.record_mock_apps(&["Code"], instant, system);
+        let (instant, system) = at(60);
+        recorder.record_mock_apps(&["Code", "Mail"], instant, system);
+        let (instant, system) = at(120);
+        recorder.record_mock_apps(&["Mail"], instant, system);
+        let history = UsageHistory::with_storage_path(dir.path().join("usage.sqlite"));
+        history
+            .record_sessions(&recorder.drain_completed_sessions())
+            .unwrap();
+
+        assert_eq!(
+            today_glance(&metrics, &history, &recorder, now_ms, 0),
+            TodayGlance {
+                launches_today: 2,
+                usage_today_ms: 180_000,
+                active_app: Some("Mail".to_string()),
+                last_startup_ms: Some(420),
+            }
+        );
+    }
+}
@@ -0,0 +1,286 @@
+//! Works out which app launched Time Wise by walking up the process tree.
+
+use std::path::Path;
+use std::sync::Mutex;
+
+use sysinfo::{get_current_pid, Pid, ProcessRefreshKind, ProcessesToUpdate, RefreshKind, System};
+use time_wise_shared::build_environment::{
+    DEBUG_ENVIRONMENT, DEV_ENVIRONMENT, RELEASE_ENVIRONMENT,
+};
+
+/// Launcher recorded when nothing better could be detected.
+pub const UNKNOWN_LAUNCHER: &str = "unknown";
+
+/// Longest launcher name stored with a startup record, in characters.
+const MAX_LAUNCHER_CHARS: usize = 64;
+
+/// How many ancestors are inspected before giving up.
+const MAX_ANCESTORS: usize = 10;
+
+/// Shells and terminal helpers that sit between the real launcher and the app.
+const SHELL_NAMES: [&str; 11] = [
+    "bash",
+    "cmd",
+    "dash",
+    "fish",
+    "login",
+    "nu",
+    "powershell",
+    "pwsh",
+    "sh",
+    "tmux",
+    "zsh",
+];
+
+/// Development tools that launch the app during `tauri dev` or `trunk serve`.
+const DEV_TOOL_NAMES: [&str; 9] = [
+    "bun", "cargo", "deno", "node", "npm", "pnpm", "tauri", "trunk", "yarn",
+];
+
+/// Read access to the process tree, so detection can run against a fake tree in tests.
+pub trait ProcessTree {
+    fn parent(&self, pid: Pid) -> Option<Pid>;
+    fn executable(&self, pid: Pid) -> Option<String>;
+    fn name(&self, pid: Pid) -> Option<String>;
+}
+
+impl ProcessTree for System {
+    fn parent(&self, pid: Pid) -> Option<Pid> {
+        self.process(pid)?.parent()
+    }
+
+    fn executable(&self, pid: Pid) -> Option<String> {
+        self.process(pid)?.exe()?.to_str().map(str::to_string)
+    }
+
+    fn name(&self, pid: Pid) -> Option<String> {
+        let name = self.process(pid)?.name().to_string_lossy();
+        let trimmed = name.trim();
+        (!trimmed.is_empty()).then(|| trimmed.to_string())
+    }
+}
+
+/// Names the closest ancestor of `pid` that is an app bundle or executable, else the nearest named one.
+pub fn detect_launcher<T: ProcessTree>(tree: &T, pid: Pid) -> String {
+    let mut pid = pid;
+    let mut fallback: Option<String> = None;
+
+    for _ in 0..MAX_ANCESTORS {
+        let Some(parent_pid) = tree.parent(pid) else {
+            break;
+        };
+
+        if let Some(app_name) = tree
+            .executable(parent_pid)
+            .and_then(|path| extract_app_name(&path))
+        {
+            return app_name;
+        }
+
+        if let Some(name) = tree.name(parent_pid) {
+            fallback = Some(name);
+        }
+
+        pid = parent_pid;
+    }
+
+    fallback.unwrap_or_else(|| UNKNOWN_LAUNCHER.to_string())
+}
+
+/// Detects the launcher of the current process from a fresh process snapshot.
+pub fn resolve_launcher_name() -> String {
+    let refresh = RefreshKind::nothing().with_processes(ProcessRefreshKind::everything());
+    let mut system = System::new_with_specifics(refresh);
+    system.refresh_processes(ProcessesToUpdate::All, true);
+
+    match get_current_pid() {
+        Ok(pid) => detect_launcher(&system, pid),
+        Err(_) => UNKNOWN_LAUNCHER.to_string(),
+    }
+}
+
+/// Tags a run as `dev` when a development tool launched it, otherwise by build profile.
+pub fn classify_environment(debug_build: bool, launcher: &str) -> &'static str {
+    let launcher = launcher.trim().to_ascii_lowercase();
+    let launcher = launcher.strip_suffix(".exe").unwrap_or(&launcher);
+    let from_dev_tool = launcher
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .any(|part| DEV_TOOL_NAMES.contains(&part));
+    if from_dev_tool {
+        DEV_ENVIRONMENT
+    } else if debug_build {
+        DEBUG_ENVIRONMENT
+    } else {
+        RELEASE_ENVIRONMENT
+    }
+}
+
+/// Trims and collapses whitespace and caps the length, mapping empty or unknown names to `UNKNOWN_LAUNCHER`.
+pub fn normalize_launcher(raw: &str) -> String {
+    let collapsed = raw.split_whitespace().collect::<Vec<_>>().join(" ");
+    if collapsed.is_empty() || collapsed.eq_ignore_ascii_case(UNKNOWN_LAUNCHER) {
+        return UNKNOWN_LAUNCHER.to_string();
+    }
+    collapsed
+        .chars()
+        .take(MAX_LAUNCHER_CHARS)
+        .collect::<String>()
+        .trim_end()
+        .to_string()
+}
+
+fn extract_app_name(path: &str) -> Option<String> {
+    if let Some(index) = path.find(".app/") {
+        let prefix = &path[..index];
+        if let Some(app_name) = prefix.rsplit('/').next() {
+            if !app_name.is_empty() {
+                return Some(app_name.to_string());
+            }
+        }
+    }
+
+    if path.ends_with(".exe") {
+        return Path::new(path)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string());
+    }
+
+    None
+}
+
+/// Returns true for launchers that say nothing about how the app was opened.
+fn is_placeholder_launcher(name: &str) -> bool {
+    let name = name.trim().to_ascii_lowercase();
+    let name = name.strip_suffix(".exe").unwrap_or(&name);
+    name.is_empty() || name == UNKNOWN_LAUNCHER || SHELL_NAMES.contains(&name)
+}
+
+/// Launcher detected for this run, upgradable until the startup is recorded.
+pub struct LauncherState {
+    name: Mutex<String>,
+}
+
+impl LauncherState {
+    pub fn new(name: String) -> Self {
+        Self {
+            name: Mutex::new(name),
+        }
+    }
+
+    pub fn get(&self) -> String {
+        self.name
+            .lock()
+            .map(|name| name.clone())
+            .unwrap_or_else(|_| UNKNOWN_LAUNCHER.to_string())
+    }
+
+    /// Replaces an unknown or shell launcher with `candidate` when it is a real name; returns the kept launcher.
+    pub fn upgrade(&self, candidate: String) -> String {
+        let Ok(mut name) = self.name.lock() else {
+            return UNKNOWN_LAUNCHER.to_string();
+        };
+        if is_placeholder_launcher(&name) && !is_placeholder_launcher(&candidate) {
+            *name = candidate;
+        }
+        name.clone()
+    }
+}
+
+#[tauri::command]
+/// Tauri command walking the process tree again and upgrading a placeholder launcher.
+pub fn redetect_launcher(state: tauri::State<'_, LauncherState>) -> String {
+    state.upgrade(resolve_launcher_name())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[derive(Default)]
+    struct FakeTree {
+        parents: HashMap<Pid, Pid>,
+        executables: HashMap<Pid, String>,
+        names: HashMap<Pid, String>,
+    }
+
+    impl ProcessTree for FakeTree {
+        fn parent(&self, pid: Pid) -> Option<Pid> {
+            self.parents.get(&pid).copied()
+        }
+
+        fn executable(&self, pid: Pid) -> Option<String> {
+            self.executables.get(&pid).cloned()
+        }
+
+        fn name(&self, pid: Pid) -> Option<String> {
+            self.names.get(&pid).cloned()
+        }
+    }
+
+    #[test]
+    fn environment_is_dev_when_a_development_tool_launched_the_app() {
+        for launcher in ["cargo", "cargo-tauri", "node.exe", "Trunk", "tauri dev"] {
+            assert_eq!(classify_environment(false, launcher), DEV_ENVIRONMENT);
+            assert_eq!(classify_environment(true, launcher), DEV_ENVIRONMENT);
+        }
+        for launcher in ["Finder", "Dock", "explorer.exe", UNKNOWN_LAUNCHER] {
+            assert_eq!(classify_environment(true, launcher), DEBUG_ENVIRONMENT);
+            assert_eq!(classify_environment(false, launcher), RELEASE_ENVIRONMENT);
+        }
+        // Tool names only count as whole words.
+        assert_eq!(classify_environment(false, "Nodebook"), RELEASE_ENVIRONMENT);
+    }
+
+    #[test]
+    fn redetection_upgrades_unknown_to_a_real_launcher() {
+        let app = Pid::from(30);
+        let mut tree = FakeTree::default();
+        assert_eq!(detect_launcher(&tree, app), UNKNOWN_LAUNCHER);
+
+        let state = LauncherState::new(detect_launcher(&tree, app));
+
+        // The parent chain settles: app <- zsh <- Terminal.app.
+        tree.parents.insert(app, Pid::from(20));
+        tree.parents.insert(Pid::from(20), Pid::from(10));
+        tree.names.insert(Pid::from(20), "zsh".to_string());
+        tree.executables.insert(
+            Pid::from(10),
+            "/Applications/Terminal.app/Contents/MacOS/Terminal".to_string(),
+        );
+
+        assert_eq!(state.upgrade(detect_launcher(&tree, app)), "Terminal");
+        assert_eq!(state.get(), "Terminal");
+    }
+
+    #[test]
+    fn normalize_launcher_tidies_whitespace_length_and_unknowns() {
+        assert_eq!(
+            normalize_launcher("  Google   Chrome\tHelper "),
+            "Google Chrome Helper"
+        );
+        assert_eq!(normalize_launcher(""), UNKNOWN_LAUNCHER);
+        assert_eq!(normalize_launcher("   "), UNKNOWN_LAUNCHER);
+        assert_eq!(normalize_launcher("Unknown"), UNKNOWN_LAUNCHER);
+        assert_eq!(normalize_launcher("Dock"), "Dock");
+
+        let long = "x".repeat(100);
+        assert_eq!(
+            normalize_launcher(&long).chars().count(),
+            MAX_LAUNCHER_CHARS
+        );
+        let multibyte = "é".repeat(100);
+        assert_eq!(
+            normalize_launcher(&multibyte).chars().count(),
+            MAX_LAUNCHER_CHARS
+        );
+    }
+
+    #[test]
+    fn upgrade_keeps_real_launchers_and_ignores_shells() {
+        let state = LauncherState::new("unknown".to_string());
+        assert_eq!(state.upgrade("bash".to_string()), "unknown");
+        assert_eq!(state.upgrade("Finder".to_string()), "Finder");
+        assert_eq!(state.upgrade("Dock".to_string()), "Finder");
+    }
+}
@@ -1,28 +1,103 @@
 mod app_usage;
+mod autostart;
+mod backup;
+#[cfg(debug_assertions)]
+mod benchmark;
+mod budgets;
+mod bundle;
+mod calendar;
+mod categories;
+mod environment;
+mod error_log;
+mod exit_marker;
+mod glance;
+mod launcher;
+mod maintenance;
+mod placement;
+mod preferences;
+mod reminders;
 mod startup_metrics;
-
+mod steady_use;
+mod storage;
+mod tracking;
+mod tray_summary;
+mod usage_history;
+mod weekly_summary;
+mod welcome_back;
+mod window_titles;
+
+use std::collections::HashMap;
 use std::env;
-use std::path::Path;
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::time::Instant;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
 
-use app_usage::{AppUsageRecord, AppUsageRecorder, APP_USAGE_POLL_INTERVAL};
-use startup_metrics::{fetch_startup_records, StartupMetrics};
+use app_usage::{
+    extend_allowlist, next_aligned_sleep, system_time_to_ms, AppUsageRecord, AppUsageRecorder,
+    ProcessBlocklist, APP_USAGE_POLL_INTERVAL,
+};
+use autostart::{
+    get_autostart_enabled, launched_by_autostart, reconcile_autostart, resume_expired_pause,
+    set_autostart_enabled, set_autostart_paused_until, AUTOSTART_ARG,
+};
+use backup::{back_up_now, spawn_backup_task};
+use budgets::{check_usage_budgets, fetch_budget_usage, set_app_budget, BudgetAlertState};
+use bundle::{export_bundle, import_from};
+use categories::{
+    fetch_categorizable_apps, fetch_usage_by_category, fetch_usage_tree, set_app_categories,
+    set_app_category,
+};
+use environment::{process_cpu_time_ms, EnvironmentSnapshot};
+use error_log::{clear_error_log, log_error, recent_errors, ErrorLog};
+use exit_marker::{unclean_exits_this_week, ExitTracker};
+use glance::fetch_today_glance;
+use launcher::{redetect_launcher, resolve_launcher_name, LauncherState};
+use maintenance::vacuum_database;
+use placement::{monitor_label, preferred_monitor, window_position, Anchor, MonitorArea};
+use preferences::{
+    fetch_preferences, get_last_view, set_last_view, set_preference, Preferences, PreferencesStore,
+    StartupMilestone, TileLabelSource, PREFERENCES_CHANGED_EVENT,
+};
+use reminders::{check_habit_reminders, HabitReminderState};
+use startup_metrics::{
+    delete_startup_record, fetch_chart_data, fetch_launcher_averages, fetch_launcher_distribution,
+    fetch_startup_records, fetch_time_saved, forecast_next_startup, has_record_today,
+    is_first_launch_today, last_record_failure, launcher_slowdown_ranking, relabel_launcher,
+    set_launcher_ignored, startup_duration, startup_headline, undo_delete_startup_record,
+    RecordFailure, StartupClock, StartupMetrics,
+};
+use steady_use::fetch_focus_score;
+use storage::validate_data_dir;
+use sysinfo::System;
 use tauri::{
     image::Image,
-    menu::{MenuBuilder, MenuItem},
+    menu::{MenuBuilder, MenuItem, SubmenuBuilder},
     path::BaseDirectory,
     tray::TrayIconBuilder,
-    Manager, RunEvent, State, WebviewUrl, WebviewWindow, Window,
+    window::{Effect, EffectsBuilder},
+    Emitter, Manager, RunEvent, State, WebviewUrl, WebviewWindow, Window,
+};
+use usage_history::{
+    fetch_app_usage_detail, fetch_busiest_hour, fetch_daily_usage, fetch_stale_apps,
+    fetch_usage_by_weekday, fetch_usage_in_range, fetch_usage_streaks, sessions_including_open,
+    UsageHistory,
 };
+use weekly_summary::spawn_weekly_summary_task;
+use welcome_back::notify_reopened_apps;
+use window_titles::window_titles_by_pid;
 
 #[cfg(not(target_os = "macos"))]
 use tauri::{PhysicalPosition, Position};
 
-use sysinfo::{get_current_pid, ProcessRefreshKind, ProcessesToUpdate, RefreshKind, System};
 #[cfg(not(target_os = "linux"))]
 use tauri::tray::TrayIconEvent;
 use tauri_plugin_autostart::{AutoLaunchManager, MacosLauncher};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+use tauri_plugin_dialog::DialogExt;
+use tracking::{toggle_tracking, tracking_menu_label, TrackingState};
+use tray_summary::{
+    refresh_tray_summary, TrayTopApps, EMPTY_TOP_APP_LABEL, TRAY_ICON_ID, TRAY_TOP_APP_COUNT,
+};
 
 trait WindowLike {
     fn hide_window(&self);
@@ -55,150 +130,691 @@ pub const TRAY_QUIT_ID: &str = "quit";
 pub const TRAY_OPEN_ID: &str = "toggle";
 /// 設定画面表示用 ID
 pub const TRAY_SETTINGS_ID: &str = "settings";
+/// 今日の概要ウィンドウ表示用 ID
+pub const TRAY_GLANCE_ID: &str = "glance";
+/// 今日の概要ウィンドウのラベル
+pub const GLANCE_WINDOW_LABEL: &str = "glance";
+/// トラッキングの一時停止・再開用 ID
+pub const TRAY_TRACKING_ID: &str = "tracking";
+/// ダッシュボード表示時にフロントエンドへ送るイベント名
+pub const WINDOW_SHOWN_EVENT: &str = "window-shown";
+/// 起動時間を記録した後にフロントエンドへ送るイベント名
+pub const STARTUP_RECORDED_EVENT: &str = "startup-recorded";
+/// 設定ファイルで定義される最初のダッシュボードのラベル
+pub const MAIN_WINDOW_LABEL: &str = "main";
+/// 「このディスプレイで開く」メニュー項目 ID の接頭辞
+pub const TRAY_DISPLAY_PREFIX: &str = "display:";
+
+/// Auto-hide bookkeeping for one dashboard: which show started the timer, and the latest interaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct AutoHideTimer {
+    generation: u64,
+    last_interaction: Instant,
+}
 
+/// What a running auto-hide timer should do next.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AutoHideStep {
+    /// The dashboard was hidden or shown again, so this timer is stale.
+    Cancel,
+    Hide,
+    /// Check again after this long.
+    WaitFor(Duration),
+}
+
+/// Decides the next step of the timer started by `generation`, hiding once `timeout` passes without interaction.
+fn auto_hide_step(
+    timer: Option<AutoHideTimer>,
+    generation: u64,
+    timeout: Duration,
+    now: Instant,
+) -> AutoHideStep {
+    match timer {
+        Some(timer) if timer.generation == generation => {
+            let idle = now.saturating_duration_since(timer.last_interaction);
+            if idle >= timeout {
+                AutoHideStep::Hide
+            } else {
+                AutoHideStep::WaitFor(timeout - idle)
+            }
+        }
+        _ => AutoHideStep::Cancel,
+    }
+}
+
+/// Visibility and pin state of each dashboard window, keyed by window label.
+#[derive(Default)]
 struct UsageWindowState {
-    visible: AtomicBool,
+    visible: Mutex<HashMap<String, bool>>,
+    pinned: Mutex<HashMap<String, bool>>,
+    auto_hide: Mutex<HashMap<String, AutoHideTimer>>,
 }
 
-impl Default for UsageWindowState {
-    fn default() -> Self {
-        Self {
-            visible: AtomicBool::new(false),
+impl UsageWindowState {
+    /// Starts a new auto-hide timer for `label`, replacing any earlier one, and returns its generation.
+    fn start_auto_hide(&self, label: &str, now: Instant) -> u64 {
+        let Ok(mut timers) = self.auto_hide.lock() else {
+            return 0;
+        };
+        let generation = timers
+            .get(label)
+            .map_or(1, |timer| timer.generation.wrapping_add(1));
+        timers.insert(
+            label.to_string(),
+            AutoHideTimer {
+                generation,
+                last_interaction: now,
+            },
+        );
+        generation
+    }
+
+    /// Restarts the idle countdown of a running auto-hide timer.
+    fn note_interaction(&self, label: &str, now: Instant) {
+        if let Ok(mut timers) = self.auto_hide.lock() {
+            if let Some(timer) = timers.get_mut(label) {
+                timer.last_interaction = now;
+            }
+        }
+    }
+
+    fn auto_hide_timer(&self, label: &str) -> Option<AutoHideTimer> {
+        self.auto_hide
+            .lock()
+            .ok()
+            .and_then(|timers| timers.get(label).copied())
+    }
+
+    fn cancel_auto_hide(&self, label: &str) {
+        if let Ok(mut timers) = self.auto_hide.lock() {
+            timers.remove(label);
+        }
+    }
+
+    fn is_visible(&self, label: &str) -> bool {
+        self.visible
+            .lock()
+            .map(|visible| visible.get(label).copied().unwrap_or(false))
+            .unwrap_or(false)
+    }
+
+    fn set_visible(&self, label: &str, visible: bool) {
+        if let Ok(mut states) = self.visible.lock() {
+            states.insert(label.to_string(), visible);
+        }
+    }
+
+    fn is_pinned(&self, label: &str) -> bool {
+        self.pinned
+            .lock()
+            .map(|pinned| pinned.get(label).copied().unwrap_or(false))
+            .unwrap_or(false)
+    }
+
+    fn set_pinned(&self, label: &str, pinned: bool) {
+        if let Ok(mut states) = self.pinned.lock() {
+            states.insert(label.to_string(), pinned);
         }
     }
+
+    /// Whether blur and auto-hide should leave `label` open: pinned by its header control or by the `dashboard_pinned` preference.
+    fn is_held_open(&self, label: &str, dashboard_pinned: bool) -> bool {
+        dashboard_pinned || self.is_pinned(label)
+    }
+
+    /// Whether a tray or hotkey toggle of `label` should leave it shown; either pin keeps it open.
+    fn toggle_shows(&self, label: &str, dashboard_pinned: bool) -> bool {
+        toggled_visible(
+            self.is_visible(label),
+            self.is_held_open(label, dashboard_pinned),
+        )
+    }
+}
+
+/// Label of the dashboard opened on the display at `index`: `main`, then `main-2`, `main-3`, ...
+pub fn dashboard_label(index: usize) -> String {
+    if index == 0 {
+        MAIN_WINDOW_LABEL.to_string()
+    } else {
+        format!("{MAIN_WINDOW_LABEL}-{}", index + 1)
+    }
+}
+
+/// Whether a window label belongs to one of the dashboards.
+pub fn is_dashboard_label(label: &str) -> bool {
+    label == MAIN_WINDOW_LABEL
+        || label
+            .strip_prefix(MAIN_WINDOW_LABEL)
+            .and_then(|rest| rest.strip_prefix('-'))
+            .is_some_and(|suffix| suffix.parse::<usize>().is_ok())
+}
+
+/// Display index encoded in a tray "open on this display" menu id.
+pub fn parse_display_menu_id(id: &str) -> Option<usize> {
+    id.strip_prefix(TRAY_DISPLAY_PREFIX)?.parse().ok()
+}
+
+/// Wall-clock start of the current application run.
+struct SessionClock {
+    started_at_ms: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TrackingCoverage {
+    run_ms: u64,
+    tracked_ms: u64,
+}
+
+/// 現在の可視状態から次の可視状態を決定（トグル）。ピン留め中は常に表示
+pub fn toggled_visible(current: bool, pinned: bool) -> bool {
+    !current || pinned
 }
 
-/// 現在の可視状態から次の可視状態を決定（トグル）
-pub fn toggled_visible(current: bool) -> bool {
-    !current
+/// Monitor to place `window` on: its current one, else the primary, else the first available.
+#[cfg(not(target_os = "macos"))]
+fn placement_monitor(window: &WebviewWindow) -> Option<MonitorArea> {
+    let monitor = window
+        .current_monitor()
+        .ok()
+        .flatten()
+        .or_else(|| window.primary_monitor().ok().flatten())
+        .or_else(|| {
+            window
+                .available_monitors()
+                .ok()
+                .and_then(|monitors| monitors.into_iter().next())
+        })?;
+    Some(MonitorArea::from(&monitor))
 }
 
 fn show_usage_window(window: &WebviewWindow, usage_state: &UsageWindowState) {
-    usage_state.visible.store(true, Ordering::SeqCst);
+    usage_state.set_visible(window.label(), true);
+    let preferences = window.app_handle().state::<PreferencesStore>().get();
 
     #[cfg(target_os = "linux")]
     {
-        if let (Ok(size), Ok(Some(monitor))) = (window.outer_size(), window.current_monitor()) {
-            let monitor_size = monitor.size();
-            let x = monitor_size.width as i32 - size.width as i32 - 24;
-            let y = 32;
+        if let Ok(size) = window.outer_size() {
+            let (x, y) = window_position(
+                placement_monitor(window),
+                size.width,
+                size.height,
+                Anchor::TopRight,
+            );
             let _ = window.set_position(Position::Physical(PhysicalPosition { x, y }));
         }
     }
 
+    let effects_enabled = window_effects_active(&window.app_handle().state::<PreferencesStore>());
+    apply_window_effects(window, effects_enabled);
+
     let _ = window.set_always_on_top(true);
     let _ = window.show();
     let _ = window.set_focus();
+
+    if preferences.auto_hide_seconds > 0
+        && !usage_state.is_held_open(window.label(), preferences.dashboard_pinned)
+    {
+        let generation = usage_state.start_auto_hide(window.label(), Instant::now());
+        schedule_auto_hide(
+            window.clone(),
+            generation,
+            Duration::from_secs(u64::from(preferences.auto_hide_seconds)),
+        );
+    }
+
+    if let Err(err) = window.emit(WINDOW_SHOWN_EVENT, ()) {
+        eprintln!("failed to emit {WINDOW_SHOWN_EVENT}: {err}");
+    }
+}
+
+/// Hides `window` once it sits idle for `timeout`, until the timer is cancelled or replaced.
+fn schedule_auto_hide(window: WebviewWindow, generation: u64, timeout: Duration) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let wait = {
+                let usage_state = window.app_handle().state::<UsageWindowState>();
+                let timer = usage_state.auto_hide_timer(window.label());
+                match auto_hide_step(timer, generation, timeout, Instant::now()) {
+                    AutoHideStep::Cancel => return,
+                    AutoHideStep::Hide => {
+                        hide_usage_window(&window, window.label(), &usage_state);
+                        return;
+                    }
+                    AutoHideStep::WaitFor(wait) => wait,
+                }
+            };
+            tokio::time::sleep(wait).await;
+        }
+    });
+}
+
+/// Restarts the calling dashboard's auto-hide countdown after a click, key press, or scroll.
+#[tauri::command]
+fn note_dashboard_interaction(window: WebviewWindow, usage_state: State<'_, UsageWindowState>) {
+    usage_state.note_interaction(window.label(), Instant::now());
 }
 
-fn hide_usage_window<W>(window: &W, usage_state: &UsageWindowState)
+/// Pins or unpins the calling dashboard; a pinned dashboard is brought to front instead of hidden by the tray toggle.
+#[tauri::command]
+fn set_dashboard_pinned(
+    window: WebviewWindow,
+    usage_state: State<'_, UsageWindowState>,
+    pinned: bool,
+) -> bool {
+    usage_state.set_pinned(window.label(), pinned);
+    if pinned {
+        usage_state.cancel_auto_hide(window.label());
+    }
+    pinned
+}
+
+/// Whether the calling dashboard is pinned against the tray toggle.
+#[tauri::command]
+fn is_dashboard_pinned(window: WebviewWindow, usage_state: State<'_, UsageWindowState>) -> bool {
+    usage_state.is_pinned(window.label())
+}
+
+/// Native blur is only available on macOS (vibrancy) and Windows (acrylic).
+///
+/// A window can only be made transparent when it is created, so every dashboard (the `main`
+/// window in `tauri.conf.json`, which also needs `macOSPrivateApi`, and the extra ones from
+/// `build_dashboard_window`) is always transparent; its opaque page background hides that until
+/// the user opts in. Settings and glance windows stay opaque.
+const WINDOW_EFFECTS_SUPPORTED: bool = cfg!(any(target_os = "macos", target_os = "windows"));
+
+fn window_effects_active(preferences: &PreferencesStore) -> bool {
+    WINDOW_EFFECTS_SUPPORTED && preferences.get().window_effects
+}
+
+fn apply_window_effects(window: &WebviewWindow, enabled: bool) {
+    if !WINDOW_EFFECTS_SUPPORTED {
+        return;
+    }
+    let effect = if cfg!(target_os = "macos") {
+        Effect::Sidebar
+    } else {
+        Effect::Acrylic
+    };
+    let effects = enabled.then(|| EffectsBuilder::new().effect(effect).build());
+    if let Err(err) = window.set_effects(effects) {
+        eprintln!("failed to apply window effects: {err}");
+    }
+}
+
+/// Lets the webview switch to a translucent background when the native effect is applied.
+#[tauri::command]
+fn fetch_window_effects_active(preferences: State<'_, PreferencesStore>) -> bool {
+    window_effects_active(&preferences)
+}
+
+/// Whether a dashboard losing focus should hide; a pinned dashboard never does.
+fn should_hide_on_blur(pinned: bool, auto_hide: bool) -> bool {
+    auto_hide && !pinned
+}
+
+fn hide_usage_window<W>(window: &W, label: &str, usage_state: &UsageWindowState)
 where
     W: WindowLike,
 {
-    usage_state.visible.store(false, Ordering::SeqCst);
+    usage_state.set_visible(label, false);
+    usage_state.cancel_auto_hide(label);
 
     window.set_always_on_top_window(false);
     window.hide_window();
 }
 
-fn toggle_main_window(app: &tauri::AppHandle) {
+/// Moves `window` to the top-right corner of the preferred monitor while that monitor is connected.
+///
+/// Only the tray toggle uses this, so opening a dashboard on a chosen display keeps its placement.
+fn place_on_preferred_monitor(window: &WebviewWindow) {
+    let preferences = window.app_handle().state::<PreferencesStore>().get();
+    let monitor = preferred_monitor(
+        &monitor_choices(window.app_handle()),
+        &preferences.preferred_monitor,
+    );
+    if let (Some(monitor), Ok(size)) = (monitor, window.outer_size()) {
+        let (x, y) = window_position(Some(monitor), size.width, size.height, Anchor::TopRight);
+        let _ = window.set_position(tauri::Position::Physical(tauri::PhysicalPosition { x, y }));
+    }
+}
+
+fn toggle_dashboard_window(app: &tauri::AppHandle, label: &str) {
     let usage_state = app.state::<UsageWindowState>();
-    if let Some(window) = app.get_webview_window("main") {
-        let currently_visible = usage_state.visible.load(Ordering::SeqCst);
-        if toggled_visible(currently_visible) {
+    let dashboard_pinned = app.state::<PreferencesStore>().get().dashboard_pinned;
+    if let Some(window) = app.get_webview_window(label) {
+        if usage_state.toggle_shows(label, dashboard_pinned) {
+            if label == MAIN_WINDOW_LABEL {
+                place_on_preferred_monitor(&window);
+            }
             show_usage_window(&window, &usage_state);
         } else {
-            hide_usage_window(&window, &usage_state);
+            hide_usage_window(&window, label, &usage_state);
         }
     }
 }
 
+/// Default dashboard size, in logical pixels.
+const DASHBOARD_WIDTH: f64 = 440.0;
+const DASHBOARD_HEIGHT: f64 = 520.0;
+/// Smallest dashboard size, in logical pixels, the layout still works at.
+const DASHBOARD_MIN_WIDTH: f64 = 320.0;
+const DASHBOARD_MIN_HEIGHT: f64 = 360.0;
+
+/// Physical size to restore a dashboard resized below the minimum to, or `None` when it is large enough.
+fn clamped_dashboard_size(width: u32, height: u32, scale_factor: f64) -> Option<(u32, u32)> {
+    let min_width = (DASHBOARD_MIN_WIDTH * scale_factor).round() as u32;
+    let min_height = (DASHBOARD_MIN_HEIGHT * scale_factor).round() as u32;
+    (width < min_width || height < min_height)
+        .then(|| (width.max(min_width), height.max(min_height)))
+}
+
+/// Every connected monitor with the label the tray menu and settings show for it.
+fn monitor_choices(app: &tauri::AppHandle) -> Vec<(String, MonitorArea)> {
+    app.available_monitors()
+        .unwrap_or_default()
+        .iter()
+        .enumerate()
+        .map(|(index, monitor)| {
+            (
+                monitor_label(monitor.name().map(String::as_str), index),
+                MonitorArea::from(monitor),
+            )
+        })
+        .collect()
+}
+
+/// Names of the connected monitors, in the order the system reports them.
+#[tauri::command]
+fn list_monitors(app: tauri::AppHandle) -> Vec<String> {
+    monitor_choices(&app)
+        .into_iter()
+        .map(|(label, _)| label)
+        .collect()
+}
+
+/// Builds an extra dashboard window sharing the main dashboard's page and size.
+fn build_dashboard_window(app: &tauri::AppHandle, label: &str) -> tauri::Result<WebviewWindow> {
+    tauri::WebviewWindowBuilder::new(app, label, WebviewUrl::App("index.html".into()))
+        .title("time-wise")
+        .inner_size(DASHBOARD_WIDTH, DASHBOARD_HEIGHT)
+        .min_inner_size(DASHBOARD_MIN_WIDTH, DASHBOARD_MIN_HEIGHT)
+        .visible(false)
+        // Matches the main window so the translucent background can be turned on later.
+        .transparent(true)
+        .skip_taskbar(true)
+        .build()
+}
+
+/// Shows the dashboard for the display at `index`, building it on first use, in that display's top-right corner.
+fn open_dashboard_on_display(app: &tauri::AppHandle, index: usize) {
+    let Some(monitor) = app
+        .available_monitors()
+        .ok()
+        .and_then(|monitors| monitors.into_iter().nth(index))
+    else {
+        return;
+    };
+
+    let label = dashboard_label(index);
+    let window = match app.get_webview_window(&label) {
+        Some(window) => window,
+        None => match build_dashboard_window(app, &label) {
+            Ok(window) => window,
+            Err(err) => {
+                eprintln!("failed to build dashboard {label}: {err}");
+                return;
+            }
+        },
+    };
+
+    if let Ok(size) = window.outer_size() {
+        let (x, y) = window_position(
+            Some(MonitorArea::from(&monitor)),
+            size.width,
+            size.height,
+            Anchor::TopRight,
+        );
+        let _ = window.set_position(tauri::Position::Physical(tauri::PhysicalPosition { x, y }));
+    }
+    show_usage_window(&window, &app.state::<UsageWindowState>());
+}
+
+/// Shows the settings window built hidden during setup; closing it only hides it again.
 fn show_settings_window(app: &tauri::AppHandle) {
     if let Some(window) = app.get_webview_window("settings") {
         let _ = window.show();
         let _ = window.set_focus();
-        return;
     }
+}
 
-    let _ = tauri::WebviewWindowBuilder::new(
+/// Shows the small today-at-a-glance window, building it on first use.
+fn show_glance_window(app: &tauri::AppHandle) {
+    if let Some(window) = app.get_webview_window(GLANCE_WINDOW_LABEL) {
+        let _ = window.show();
+        let _ = window.set_focus();
+        if let Err(err) = window.emit(WINDOW_SHOWN_EVENT, ()) {
+            eprintln!("failed to emit {WINDOW_SHOWN_EVENT}: {err}");
+        }
+        return;
+    }
+    if let Err(err) = tauri::WebviewWindowBuilder::new(
         app,
-        "settings",
-        WebviewUrl::App("/?view=settings".into()),
+        GLANCE_WINDOW_LABEL,
+        WebviewUrl::App("/?view=glance".into()),
     )
-    .title("Time Wise Settings")
-    .inner_size(420.0, 420.0)
+    .title("Today at a Glance")
+    .inner_size(280.0, 180.0)
     .resizable(false)
-    .skip_taskbar(false)
-    .visible(true)
-    .build();
+    .always_on_top(true)
+    .skip_taskbar(true)
+    .build()
+    {
+        eprintln!("failed to build glance window: {err}");
+    }
 }
 
 #[tauri::command]
-async fn get_autostart_enabled(autostart: State<'_, AutoLaunchManager>) -> Result<bool, String> {
-    autostart.is_enabled().map_err(|err| err.to_string())
+fn copy_text(app: tauri::AppHandle, text: String) -> Result<(), String> {
+    app.clipboard()
+        .write_text(text)
+        .map_err(|err| err.to_string())
 }
 
+/// Asks for a destination with the save dialog and writes `contents` there; `false` when cancelled.
 #[tauri::command]
-async fn set_autostart_enabled(
-    autostart: State<'_, AutoLaunchManager>,
-    enabled: bool,
+async fn save_text_file(
+    app: tauri::AppHandle,
+    contents: String,
+    file_name: String,
 ) -> Result<bool, String> {
-    let result = if enabled {
-        autostart.enable()
-    } else {
-        autostart.disable()
+    let extension = Path::new(&file_name)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or("txt")
+        .to_string();
+    let Some(destination) = app
+        .dialog()
+        .file()
+        .set_file_name(&file_name)
+        .add_filter(extension.to_uppercase(), &[extension.as_str()])
+        .blocking_save_file()
+    else {
+        return Ok(false);
     };
 
-    result
-        .and_then(|_| autostart.is_enabled())
-        .map_err(|err| err.to_string())
+    let path = destination.into_path().map_err(|err| err.to_string())?;
+    std::fs::write(path, contents).map_err(|err| err.to_string())?;
+    Ok(true)
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     let startup_instant = Instant::now();
+    let session_started_at_ms = system_time_to_ms(SystemTime::now());
 
     let builder = tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_clipboard_manager::init())
+        .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_notification::init())
         .plugin(tauri_plugin_autostart::init(
             MacosLauncher::LaunchAgent,
-            None,
+            Some(vec![AUTOSTART_ARG]),
         ))
         .invoke_handler(tauri::generate_handler![
+            back_up_now,
+            #[cfg(debug_assertions)]
+            benchmark::benchmark_startup,
+            clear_error_log,
+            copy_text,
+            delete_startup_record,
+            export_bundle,
+            fetch_app_usage_detail,
             fetch_app_usage_records,
+            fetch_budget_usage,
+            fetch_busiest_hour,
+            #[cfg(debug_assertions)]
+            fetch_candidate_processes,
+            fetch_categorizable_apps,
+            fetch_chart_data,
+            fetch_daily_usage,
+            fetch_focus_score,
+            fetch_launcher_averages,
+            fetch_launcher_distribution,
+            fetch_preferences,
+            fetch_stale_apps,
             fetch_startup_records,
+            fetch_time_saved,
+            fetch_today_glance,
+            fetch_tracking_coverage,
+            fetch_usage_by_category,
+            fetch_usage_by_weekday,
+            fetch_usage_in_range,
+            fetch_usage_streaks,
+            fetch_usage_tree,
+            fetch_window_effects_active,
+            forecast_next_startup,
+            frontend_ready,
             get_autostart_enabled,
-            set_autostart_enabled
+            get_last_view,
+            has_record_today,
+            import_bundle,
+            is_dashboard_pinned,
+            is_first_launch_today,
+            last_record_failure,
+            launcher_slowdown_ranking,
+            list_monitors,
+            note_dashboard_interaction,
+            recent_errors,
+            redetect_launcher,
+            relabel_launcher,
+            reset_preferences,
+            #[cfg(debug_assertions)]
+            startup_metrics::reset_startup_guard,
+            save_text_file,
+            seed_allowlist_from_current,
+            session_start_ms,
+            set_app_budget,
+            set_app_categories,
+            set_app_category,
+            set_autostart_enabled,
+            set_autostart_paused_until,
+            set_dashboard_pinned,
+            set_last_view,
+            set_launcher_ignored,
+            set_min_session_seconds,
+            set_preference,
+            startup_headline,
+            unclean_exits_this_week,
+            undo_delete_startup_record,
+            validate_data_dir,
+            vacuum_database
         ])
-        .setup(|app| {
+        .setup(move |app| {
+            app.manage(ErrorLog::default());
+            app.manage(StartupClock::new(startup_instant));
+            app.manage(RecordFailure::default());
             app.manage(UsageWindowState::default());
+            app.manage(SessionClock {
+                started_at_ms: session_started_at_ms,
+            });
 
-            let app_usage_recorder = AppUsageRecorder::default();
-            if let Err(err) = app_usage_recorder.record_current_processes() {
-                eprintln!("failed to seed app usage data: {err}");
+            app.manage(PreferencesStore::with_storage_path(resolve_storage_path(
+                app.handle(),
+                "preferences.json",
+            )));
+            // Benchmark children only report their startup, so they leave the user's data and the OS alone.
+            let benchmark_run = is_benchmark_run();
+            if !benchmark_run {
+                if let Err(err) = reconcile_autostart(
+                    &app.state::<AutoLaunchManager>(),
+                    &app.state::<PreferencesStore>(),
+                ) {
+                    log_error(
+                        app.handle(),
+                        &format!("failed to reconcile autostart: {err}"),
+                    );
+                }
+            }
+            let exit_tracker = ExitTracker::new(
+                resolve_storage_path(app.handle(), "running.marker"),
+                resolve_storage_path(app.handle(), "unclean_exits.json"),
+            );
+            // A benchmark child must not see, overwrite, or delete the parent's running marker.
+            if !benchmark_run {
+                let marked = if app.state::<PreferencesStore>().get().track_unclean_exits {
+                    exit_tracker
+                        .begin_run(system_time_to_ms(SystemTime::now()))
+                        .map(|_| ())
+                } else {
+                    exit_tracker.end_run()
+                };
+                if let Err(err) = marked {
+                    log_error(
+                        app.handle(),
+                        &format!("failed to update exit marker: {err}"),
+                    );
+                }
             }
+            app.manage(exit_tracker);
+            app.manage(HabitReminderState::default());
+            app.manage(BudgetAlertState::default());
+            app.manage(TrackingState::default());
 
-            let recorder_for_task = app_usage_recorder.clone();
-            tauri::async_runtime::spawn(async move {
-                loop {
-                    tokio::time::sleep(APP_USAGE_POLL_INTERVAL).await;
-                    if let Err(err) = recorder_for_task.record_current_processes() {
-                        eprintln!("failed to record app usage: {err}");
-                    }
+            let app_usage_recorder = AppUsageRecorder::default();
+            if benchmark_run {
+                app.manage(UsageHistory::in_memory());
+                app.manage(StartupMetrics::in_memory());
+            } else {
+                let usage_history = UsageHistory::with_storage_path(resolve_storage_path(
+                    app.handle(),
+                    "usage_history.sqlite",
+                ));
+                app_usage_recorder.seed_last_seen(&usage_history.last_seen_by_app());
+                app.manage(usage_history);
+
+                if let Err(err) = record_processes(app.handle(), &app_usage_recorder) {
+                    log_error(
+                        app.handle(),
+                        &format!("failed to seed app usage data: {err}"),
+                    );
                 }
-            });
 
+                app.manage(StartupMetrics::with_storage_path(resolve_storage_path(
+                    app.handle(),
+                    "startup_times.sqlite",
+                )));
+            }
             app.manage(app_usage_recorder);
-
-            let storage_path = app
-                .path()
-                .resolve("startup_times.sqlite", BaseDirectory::AppData)
-                .unwrap_or_else(|err| {
-                    eprintln!("failed to resolve startup metrics path: {err}");
-                    env::temp_dir().join("time-wise-startup-times.sqlite")
-                });
-            let metrics = StartupMetrics::with_storage_path(storage_path);
-            app.manage(metrics);
+            if !benchmark_run {
+                spawn_usage_poller(
+                    app.handle().clone(),
+                    app.state::<AppUsageRecorder>().inner().clone(),
+                );
+                spawn_backup_task(app.handle().clone());
+                spawn_weekly_summary_task(app.handle().clone());
+            }
 
             tauri::WebviewWindowBuilder::new(
                 app,
@@ -212,99 +828,16 @@ pub fn run() {
             .skip_taskbar(false)
             .build()?;
 
-            // 明示的にトレイアイコンを設定（macOS では必須）。
-            let tray_icon = Image::from_bytes(include_bytes!("../icons/32x32.png"))
-                .expect("failed to load tray icon");
-            let usage_item =
-                MenuItem::with_id(app, TRAY_OPEN_ID, "Open Usage", true, None::<&str>)?;
-            let containers_label = MenuItem::new(app, "Containers", false, None::<&str>)?;
-            // Placeholder desktop apps shown under Containers until runtime data is wired up.
-            let desktop_app_primary =
-                MenuItem::new(app, "Desktop App Aurora", false, None::<&str>)?;
-            let desktop_app_secondary =
-                MenuItem::new(app, "Desktop App Nimbus", false, None::<&str>)?;
-            let settings_item =
-                MenuItem::with_id(app, TRAY_SETTINGS_ID, "Settings...", true, None::<&str>)?;
-            let quit_item = MenuItem::with_id(app, TRAY_QUIT_ID, "Quit", true, None::<&str>)?;
-            let menu = MenuBuilder::new(app)
-                .item(&usage_item)
-                .separator()
-                .item(&containers_label)
-                .item(&desktop_app_primary)
-                .item(&desktop_app_secondary)
-                .separator()
-                .item(&settings_item)
-                .item(&quit_item)
-                .build()?;
-            TrayIconBuilder::new()
-                .icon(tray_icon)
-                .icon_as_template(true)
-                .menu(&menu)
-                .tooltip("Time Wise")
-                .on_menu_event(|app, event| match event.id.as_ref() {
-                    TRAY_QUIT_ID => app.exit(0),
-                    TRAY_OPEN_ID => toggle_main_window(app),
-                    TRAY_SETTINGS_ID => show_settings_window(app),
-                    _ => {}
-                })
-                .on_tray_icon_event(|tray, event| {
-                    #[cfg(target_os = "linux")]
-                    {
-                        let _ = (tray, event);
-                    }
+            if !benchmark_run {
+                build_tray(app)?;
+            }
 
-                    #[cfg(not(target_os = "linux"))]
-                    {
-                        if let TrayIconEvent::Click {
-                            button: tauri::tray::MouseButton::Left,
-                            position,
-                            rect,
-                            ..
-                        } = event
-                        {
-                            let app = tray.app_handle();
-                            let usage_state = app.state::<UsageWindowState>();
-                            if let Some(window) = app.get_webview_window("main") {
-                                if toggled_visible(usage_state.visible.load(Ordering::SeqCst)) {
-                                    #[cfg(target_os = "macos")]
-                                    {
-                                        let _ = (position, rect);
-                                    }
-                                    #[cfg(not(target_os = "macos"))]
-                                    {
-                                        if let Ok(size) = window.outer_size() {
-                                            let monitor_height = window
-                                                .current_monitor()
-                                                .ok()
-                                                .flatten()
-                                                .map(|monitor| monitor.size().height as f64)
-                                                .unwrap_or(position.y * 2.0);
-                                            let x = position.x - (size.width as f64 / 2.0);
-                                            let y = if position.y > monitor_height / 2.0 {
-                                                position.y - size.height as f64 - 12.0
-                                            } else {
-                                                position.y + rect.size.height + 12.0
-                                            };
-                                            let _ = window.set_position(Position::Physical(
-                                                PhysicalPosition {
-                                                    x: x.round() as i32,
-                                                    y: y.round() as i32,
-                                                },
-                                            ));
-                                        }
-                                    }
-
-                                    show_usage_window(&window, &usage_state);
-                                } else {
-                                    hide_usage_window(&window, &usage_state);
-                                }
-                            }
-                        }
-                    }
-                })
-                .build(app)?;
+            if let Some(window) = app.get_webview_window(MAIN_WINDOW_LABEL) {
+                let _ = window.set_min_size(Some(tauri::LogicalSize::new(
+                    DASHBOARD_MIN_WIDTH,
+                    DASHBOARD_MIN_HEIGHT,
+                )));
 
-            if let Some(window) = app.get_webview_window("main") {
                 #[cfg(target_os = "macos")]
                 {
                     let _ = window.set_skip_taskbar(true);
@@ -317,121 +850,484 @@ pub fn run() {
                 }
 
                 app.state::<UsageWindowState>()
-                    .visible
-                    .store(false, Ordering::SeqCst);
+                    .set_visible(MAIN_WINDOW_LABEL, false);
             }
             Ok(())
         })
-        .on_window_event(|window, event| {
-            if let tauri::WindowEvent::CloseRequested { api, .. } = event {
-                match window.label() {
-                    "main" => {
-                        let usage_state = window.app_handle().state::<UsageWindowState>();
-                        hide_usage_window(window, &usage_state);
-                        api.prevent_close();
-                    }
-                    "settings" => {
-                        let _ = window.hide();
-                        api.prevent_close();
-                    }
-                    _ => {}
+        .on_window_event(|window, event| match event {
+            tauri::WindowEvent::CloseRequested { api, .. } => match window.label() {
+                label if is_dashboard_label(label) => {
+                    let usage_state = window.app_handle().state::<UsageWindowState>();
+                    hide_usage_window(window, label, &usage_state);
+                    api.prevent_close();
                 }
+                "settings" | GLANCE_WINDOW_LABEL => {
+                    let _ = window.hide();
+                    api.prevent_close();
+                }
+                _ => {}
+            },
+            // Some window managers ignore the minimum size, so undersized dashboards are grown back.
+            tauri::WindowEvent::Resized(size) if is_dashboard_label(window.label()) => {
+                let scale_factor = window.scale_factor().unwrap_or(1.0);
+                if let Some((width, height)) =
+                    clamped_dashboard_size(size.width, size.height, scale_factor)
+                {
+                    let _ = window.set_size(tauri::PhysicalSize::new(width, height));
+                }
+            }
+            tauri::WindowEvent::Focused(true) if is_dashboard_label(window.label()) => {
+                window
+                    .app_handle()
+                    .state::<UsageWindowState>()
+                    .note_interaction(window.label(), Instant::now());
             }
+            tauri::WindowEvent::Focused(false) if is_dashboard_label(window.label()) => {
+                let preferences = window.app_handle().state::<PreferencesStore>().get();
+                let usage_state = window.app_handle().state::<UsageWindowState>();
+                let pinned = usage_state.is_held_open(window.label(), preferences.dashboard_pinned);
+                if should_hide_on_blur(pinned, preferences.auto_hide_on_blur) {
+                    hide_usage_window(window, window.label(), &usage_state);
+                }
+            }
+            _ => {}
         });
 
     let app = builder
         .build(tauri::generate_context!())
         .expect("error while building tauri application");
 
-    let launcher = resolve_launcher_name();
+    app.manage(LauncherState::new(resolve_launcher_name()));
+
+    app.run(move |app_handle, event| match event {
+        RunEvent::Ready => {
+            // Benchmark children only report their startup; nothing is recorded or maintained.
+            #[cfg(debug_assertions)]
+            if benchmark::is_benchmark_child() {
+                println!(
+                    "{}",
+                    benchmark::report_line(startup_instant.elapsed().as_millis() as u64)
+                );
+                app_handle.exit(0);
+                return;
+            }
 
-    app.run(move |app_handle, event| {
-        if let RunEvent::Ready = event {
-            let metrics = app_handle.state::<StartupMetrics>();
-            if let Err(err) = metrics.record_startup(startup_instant.elapsed(), launcher.clone()) {
-                eprintln!("failed to record startup time: {err}");
+            if records_startup_at(
+                &app_handle.state::<PreferencesStore>().get(),
+                StartupMilestone::ProcessReady,
+            ) {
+                if let Some(elapsed) = app_handle.state::<StartupClock>().finish(Instant::now()) {
+                    record_startup(app_handle, elapsed);
+                }
             }
+            spawn_maintenance(app_handle);
         }
+        RunEvent::Exit => {
+            if is_benchmark_run() {
+                return;
+            }
+            flush_usage(app_handle);
+            if let Err(err) = app_handle.state::<ExitTracker>().end_run() {
+                log_error(app_handle, &format!("failed to clear exit marker: {err}"));
+            }
+        }
+        _ => {}
     });
 }
 
-fn resolve_launcher_name() -> String {
-    let refresh = RefreshKind::nothing().with_processes(ProcessRefreshKind::everything());
-    let mut system = System::new_with_specifics(refresh);
-    system.refresh_processes(ProcessesToUpdate::All, true);
-
-    let mut pid = match get_current_pid() {
-        Ok(pid) => pid,
-        Err(_) => return "unknown".to_string(),
-    };
+/// Returns true in a child launched by the debug startup benchmark, which must not touch the user's data.
+fn is_benchmark_run() -> bool {
+    #[cfg(debug_assertions)]
+    {
+        benchmark::is_benchmark_child()
+    }
+    #[cfg(not(debug_assertions))]
+    {
+        false
+    }
+}
 
-    let mut fallback: Option<String> = None;
+/// Returns true when startups are collected at all and `milestone` is the one that ends the measurement.
+fn records_startup_at(preferences: &Preferences, milestone: StartupMilestone) -> bool {
+    preferences.show_startup_metrics && preferences.startup_milestone == milestone
+}
 
-    for _ in 0..10 {
-        let process = match system.process(pid) {
-            Some(process) => process,
-            None => break,
+/// Stores this run's startup time, counted from boot when configured, with the launcher and, when enabled, the launch conditions.
+fn record_startup(app: &tauri::AppHandle, elapsed: Duration) {
+    let preferences = app.state::<PreferencesStore>().get();
+    let (duration, measured) = startup_duration(
+        preferences.startup_measurement,
+        elapsed,
+        launched_by_autostart(),
+        System::boot_time().saturating_mul(1_000),
+        system_time_to_ms(SystemTime::now()),
+    );
+    if measured != preferences.startup_measurement {
+        eprintln!("startup was not launched on boot; recording in-process time instead");
+    }
+    // The battery and thermal reads can spawn `pmset`, so keep them off the main thread.
+    let app = app.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut environment = if preferences.capture_environment {
+            EnvironmentSnapshot::capture()
+        } else {
+            EnvironmentSnapshot::default()
         };
+        if preferences.capture_cpu_time {
+            environment.cpu_time_ms = process_cpu_time_ms();
+        }
+        let result = app.state::<StartupMetrics>().record_startup(
+            duration,
+            app.state::<LauncherState>().get(),
+            environment,
+            measured,
+            preferences.min_recordable_ms,
+        );
+        app.state::<RecordFailure>().track(&result);
+        match result {
+            Ok(Some(_)) => {
+                if let Err(err) = app.emit(STARTUP_RECORDED_EVENT, ()) {
+                    eprintln!("failed to emit {STARTUP_RECORDED_EVENT}: {err}");
+                }
+            }
+            Ok(None) => {}
+            Err(err) => log_error(&app, &format!("failed to record startup time: {err}")),
+        }
+    });
+}
 
-        let parent_pid = match process.parent() {
-            Some(parent) => parent,
-            None => break,
-        };
+/// Vacuums the databases when due, on a worker thread after Ready so it never counts toward the startup time.
+fn spawn_maintenance(app: &tauri::AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let now_ms = system_time_to_ms(SystemTime::now());
+        if let Err(err) = app.state::<StartupMetrics>().run_maintenance(now_ms) {
+            log_error(
+                &app,
+                &format!("failed to maintain startup metrics database: {err}"),
+            );
+        }
+        if let Err(err) = app.state::<UsageHistory>().run_maintenance(now_ms) {
+            log_error(
+                &app,
+                &format!("failed to maintain usage history database: {err}"),
+            );
+        }
+    });
+}
 
-        let parent_process = match system.process(parent_pid) {
-            Some(process) => process,
-            None => break,
-        };
+/// Records the startup time at the dashboard's first paint when that milestone is selected.
+#[tauri::command]
+fn frontend_ready(app: tauri::AppHandle) {
+    if !records_startup_at(
+        &app.state::<PreferencesStore>().get(),
+        StartupMilestone::FirstPaint,
+    ) {
+        return;
+    }
+    if let Some(elapsed) = app.state::<StartupClock>().finish(Instant::now()) {
+        record_startup(&app, elapsed);
+    }
+}
+
+/// Builds the tray icon with its menu of dashboards, today's top apps, tracking, and settings.
+fn build_tray(app: &tauri::App) -> tauri::Result<()> {
+    // 明示的にトレイアイコンを設定（macOS では必須）。
+    let tray_icon =
+        Image::from_bytes(include_bytes!("../icons/32x32.png")).expect("failed to load tray icon");
+    let usage_item = MenuItem::with_id(app, TRAY_OPEN_ID, "Open Usage", true, None::<&str>)?;
+    let top_apps_label = MenuItem::new(app, "Top apps today", false, None::<&str>)?;
+    let top_app_items = (0..TRAY_TOP_APP_COUNT)
+        .map(|_| MenuItem::new(app, EMPTY_TOP_APP_LABEL, false, None::<&str>))
+        .collect::<Result<Vec<_>, _>>()?;
+    let tracking_item = MenuItem::with_id(
+        app,
+        TRAY_TRACKING_ID,
+        tracking_menu_label(false),
+        true,
+        None::<&str>,
+    )?;
+    let mut displays_menu = SubmenuBuilder::new(app, "Open Dashboard on Display");
+    for (index, monitor) in app.available_monitors()?.iter().enumerate() {
+        let name = monitor_label(monitor.name().map(String::as_str), index);
+        displays_menu = displays_menu.item(&MenuItem::with_id(
+            app,
+            format!("{TRAY_DISPLAY_PREFIX}{index}"),
+            name,
+            true,
+            None::<&str>,
+        )?);
+    }
+    let displays_menu = displays_menu.build()?;
+    let glance_item =
+        MenuItem::with_id(app, TRAY_GLANCE_ID, "Today at a Glance", true, None::<&str>)?;
+    let settings_item =
+        MenuItem::with_id(app, TRAY_SETTINGS_ID, "Settings...", true, None::<&str>)?;
+    let quit_item = MenuItem::with_id(app, TRAY_QUIT_ID, "Quit", true, None::<&str>)?;
+    let mut menu = MenuBuilder::new(app)
+        .item(&usage_item)
+        .item(&displays_menu)
+        .item(&glance_item)
+        .separator()
+        .item(&top_apps_label);
+    for item in &top_app_items {
+        menu = menu.item(item);
+    }
+    let menu = menu
+        .separator()
+        .item(&tracking_item)
+        .item(&settings_item)
+        .item(&quit_item)
+        .build()?;
+    app.manage(TrayTopApps {
+        items: top_app_items,
+    });
+    TrayIconBuilder::with_id(TRAY_ICON_ID)
+        .icon(tray_icon)
+        .icon_as_template(true)
+        .menu(&menu)
+        .tooltip("Time Wise")
+        .on_menu_event(move |app, event| match event.id.as_ref() {
+            TRAY_QUIT_ID => app.exit(0),
+            TRAY_OPEN_ID => toggle_dashboard_window(app, MAIN_WINDOW_LABEL),
+            TRAY_SETTINGS_ID => show_settings_window(app),
+            TRAY_GLANCE_ID => show_glance_window(app),
+            TRAY_TRACKING_ID => {
+                let paused = toggle_tracking(app);
+                let _ = tracking_item.set_text(tracking_menu_label(paused));
+            }
+            id => {
+                if let Some(index) = parse_display_menu_id(id) {
+                    open_dashboard_on_display(app, index);
+                }
+            }
+        })
+        .on_tray_icon_event(|tray, event| {
+            #[cfg(target_os = "linux")]
+            {
+                let _ = (tray, event);
+            }
 
-        if let Some(path) = parent_process.exe() {
-            if let Some(path_str) = path.to_str() {
-                if let Some(app_name) = extract_app_name(path_str) {
-                    return app_name;
+            #[cfg(not(target_os = "linux"))]
+            {
+                if let TrayIconEvent::Click {
+                    button: tauri::tray::MouseButton::Left,
+                    position,
+                    rect,
+                    ..
+                } = event
+                {
+                    let app = tray.app_handle();
+                    let usage_state = app.state::<UsageWindowState>();
+                    let dashboard_pinned = app.state::<PreferencesStore>().get().dashboard_pinned;
+                    if let Some(window) = app.get_webview_window(MAIN_WINDOW_LABEL) {
+                        if usage_state.toggle_shows(MAIN_WINDOW_LABEL, dashboard_pinned) {
+                            #[cfg(target_os = "macos")]
+                            {
+                                let _ = (position, rect);
+                            }
+                            #[cfg(not(target_os = "macos"))]
+                            {
+                                if let Ok(size) = window.outer_size() {
+                                    let scale_factor = window.scale_factor().unwrap_or(1.0);
+                                    let icon_size = rect.size.to_physical::<f64>(scale_factor);
+                                    let (x, y) = window_position(
+                                        placement_monitor(&window),
+                                        size.width,
+                                        size.height,
+                                        Anchor::Tray {
+                                            x: position.x,
+                                            y: position.y,
+                                            icon_height: icon_size.height,
+                                        },
+                                    );
+                                    let _ =
+                                        window.set_position(Position::Physical(PhysicalPosition {
+                                            x,
+                                            y,
+                                        }));
+                                }
+                            }
+
+                            // A preferred monitor overrides the tray-relative placement while it is connected.
+                            place_on_preferred_monitor(&window);
+                            show_usage_window(&window, &usage_state);
+                        } else {
+                            hide_usage_window(&window, MAIN_WINDOW_LABEL, &usage_state);
+                        }
+                    }
                 }
             }
-        }
+        })
+        .build(app)?;
+    Ok(())
+}
 
-        if let Some(name) = process_name(parent_process) {
-            fallback = Some(name);
+/// Polls running apps on the usage interval, persisting closed sessions and running the per-poll checks.
+fn spawn_usage_poller(app_handle: tauri::AppHandle, recorder: AppUsageRecorder) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let interval = if app_handle.state::<PreferencesStore>().get().align_polls {
+                next_aligned_sleep(
+                    system_time_to_ms(SystemTime::now()),
+                    APP_USAGE_POLL_INTERVAL.as_millis() as u64,
+                )
+            } else {
+                APP_USAGE_POLL_INTERVAL
+            };
+            tokio::time::sleep(interval).await;
+            if !app_handle.state::<TrackingState>().is_paused() {
+                if let Err(err) = record_processes(&app_handle, &recorder) {
+                    log_error(&app_handle, &format!("failed to record app usage: {err}"));
+                }
+            }
+            let sessions = recorder.drain_completed_sessions();
+            if let Err(err) = app_handle
+                .state::<UsageHistory>()
+                .record_sessions(&sessions)
+            {
+                log_error(
+                    &app_handle,
+                    &format!("failed to persist app usage sessions: {err}"),
+                );
+            }
+            check_habit_reminders(&app_handle);
+            check_usage_budgets(&app_handle);
+            notify_reopened_apps(&app_handle);
+            refresh_tray_summary(&app_handle);
+            if let Err(err) = resume_expired_pause(
+                &app_handle.state::<AutoLaunchManager>(),
+                &app_handle.state::<PreferencesStore>(),
+            ) {
+                log_error(&app_handle, &format!("failed to resume autostart: {err}"));
+            }
         }
+    });
+}
 
-        pid = parent_pid;
+/// Counts usage since the last poll and persists it before the process exits.
+fn flush_usage(app: &tauri::AppHandle) {
+    let sessions = app.state::<AppUsageRecorder>().flush();
+    if let Err(err) = app.state::<UsageHistory>().record_sessions(&sessions) {
+        log_error(app, &format!("failed to persist usage on exit: {err}"));
     }
+}
 
-    fallback.unwrap_or_else(|| "unknown".to_string())
+/// Polls running processes using the blocklist, lifetime threshold, and display cutoff from the current preferences.
+fn record_processes(app: &tauri::AppHandle, recorder: &AppUsageRecorder) -> Result<(), String> {
+    let preferences = app.state::<PreferencesStore>().get();
+    recorder.set_min_observed_polls(preferences.min_observed_polls);
+    recorder.set_canonical_names(preferences.canonical_app_names);
+    recorder.set_min_session_duration(Duration::from_secs(u64::from(
+        preferences.min_session_seconds,
+    )));
+    recorder.set_inactive_display_cutoff(Duration::from_secs(
+        u64::from(preferences.inactive_display_minutes) * 60,
+    ));
+    let window_titles = if preferences.tile_label_source == TileLabelSource::WindowTitle {
+        window_titles_by_pid()
+    } else {
+        HashMap::new()
+    };
+    let blocklist = process_blocklist(preferences);
+    recorder.record_current_processes(&blocklist, &window_titles)
 }
 
-fn process_name(process: &sysinfo::Process) -> Option<String> {
-    let name = process.name();
-    if name.is_empty() {
-        return None;
-    }
-    let name = name.to_string_lossy();
-    let trimmed = name.trim();
-    if trimmed.is_empty() {
-        None
+/// Builds the tracking filter from the blocklist and, in allowlist mode, the allowed apps.
+fn process_blocklist(preferences: Preferences) -> ProcessBlocklist {
+    let blocklist = ProcessBlocklist::new(preferences.builtin_blocklist, preferences.blocked_apps);
+    if preferences.allowlist_mode {
+        blocklist.with_allowlist(preferences.allowed_apps)
     } else {
-        Some(trimmed.to_string())
+        blocklist
     }
 }
 
-fn extract_app_name(path: &str) -> Option<String> {
-    if let Some(index) = path.find(".app/") {
-        let prefix = &path[..index];
-        if let Some(app_name) = prefix.rsplit('/').next() {
-            if !app_name.is_empty() {
-                return Some(app_name.to_string());
-            }
+/// Stores the shortest app session that counts toward usage and applies it to the running recorder.
+#[tauri::command]
+fn set_min_session_seconds(
+    recorder: State<'_, AppUsageRecorder>,
+    preferences: State<'_, PreferencesStore>,
+    seconds: u32,
+) -> Result<Preferences, String> {
+    let updated = preferences.set("min_session_seconds", serde_json::json!(seconds))?;
+    recorder.set_min_session_duration(Duration::from_secs(u64::from(seconds)));
+    Ok(updated)
+}
+
+/// Brings runtime state in line with preferences that were replaced wholesale, then tells every
+/// window to reload them.
+fn apply_replaced_preferences(app: &tauri::AppHandle, preferences: &Preferences) {
+    if let Err(err) = reconcile_autostart(
+        &app.state::<AutoLaunchManager>(),
+        &app.state::<PreferencesStore>(),
+    ) {
+        log_error(app, &format!("failed to reconcile autostart: {err}"));
+    }
+    let effects_enabled = window_effects_active(&app.state::<PreferencesStore>());
+    for (label, window) in app.webview_windows() {
+        if is_dashboard_label(&label) {
+            apply_window_effects(&window, effects_enabled);
         }
     }
+    refresh_tray_summary(app);
+    if let Err(err) = app.emit(PREFERENCES_CHANGED_EVENT, preferences) {
+        eprintln!("failed to emit {PREFERENCES_CHANGED_EVENT}: {err}");
+    }
+}
+
+/// Restores startup records, usage, and preferences from the zip bundle at `path` and applies the imported preferences.
+#[tauri::command]
+fn import_bundle(
+    app: tauri::AppHandle,
+    metrics: State<'_, StartupMetrics>,
+    history: State<'_, UsageHistory>,
+    preferences: State<'_, PreferencesStore>,
+    path: String,
+) -> Result<(), String> {
+    let imported = import_from(Path::new(&path), &metrics, &history, &preferences)?;
+    apply_replaced_preferences(&app, &imported);
+    Ok(())
+}
+
+/// Restores every user setting to its default and applies the result like an imported bundle.
+#[tauri::command]
+fn reset_preferences(
+    app: tauri::AppHandle,
+    preferences: State<'_, PreferencesStore>,
+) -> Result<Preferences, String> {
+    let defaults = preferences.reset()?;
+    apply_replaced_preferences(&app, &defaults);
+    Ok(defaults)
+}
 
-    if path.ends_with(".exe") {
-        return Path::new(path)
-            .file_stem()
-            .map(|stem| stem.to_string_lossy().to_string());
+/// Adds every currently running app to the allowlist and returns how many were new.
+///
+/// Reads the process list directly, because in allowlist mode the recorder only knows the apps
+/// that are already allowed.
+#[tauri::command]
+fn seed_allowlist_from_current(
+    recorder: State<'_, AppUsageRecorder>,
+    preferences: State<'_, PreferencesStore>,
+) -> Result<usize, String> {
+    let current = preferences.get();
+    let blocklist = ProcessBlocklist::new(current.builtin_blocklist, current.blocked_apps);
+    let mut allowed = current.allowed_apps;
+    let added = extend_allowlist(&mut allowed, recorder.running_app_names(&blocklist));
+    if added > 0 {
+        preferences.set("allowed_apps", serde_json::json!(allowed))?;
     }
+    Ok(added)
+}
 
-    None
+/// Resolves a file in the app data directory, falling back to the temp dir.
+fn resolve_storage_path(app: &tauri::AppHandle, file_name: &str) -> PathBuf {
+    app.path()
+        .resolve(file_name, BaseDirectory::AppData)
+        .unwrap_or_else(|err| {
+            eprintln!("failed to resolve storage path for {file_name}: {err}");
+            env::temp_dir().join(format!("time-wise-{file_name}"))
+        })
 }
 
 #[tauri::command]
@@ -441,15 +1337,171 @@ async fn fetch_app_usage_records(
     Ok(state.records())
 }
 
+/// Lists running processes and why each is or is not tracked, for debugging the tracker.
+#[cfg(debug_assertions)]
+#[tauri::command]
+fn fetch_candidate_processes(
+    app: tauri::AppHandle,
+    recorder: State<'_, AppUsageRecorder>,
+) -> Result<Vec<app_usage::CandidateProcess>, String> {
+    let preferences = app.state::<PreferencesStore>().get();
+    let blocklist = process_blocklist(preferences);
+    recorder.candidate_processes(&blocklist)
+}
+
+/// Returns the wall-clock time, in epoch milliseconds, at which this run started.
+#[tauri::command]
+fn session_start_ms(session: State<'_, SessionClock>) -> u64 {
+    session.started_at_ms
+}
+
+/// Compares how long the app has run since `since_ms` with the summed app usage recorded in that time.
+#[tauri::command]
+fn fetch_tracking_coverage(
+    session: State<'_, SessionClock>,
+    history: State<'_, UsageHistory>,
+    recorder: State<'_, AppUsageRecorder>,
+    since_ms: u64,
+) -> TrackingCoverage {
+    let now_ms = system_time_to_ms(SystemTime::now());
+    let window_start_ms = since_ms.max(session.started_at_ms);
+    let sessions = sessions_including_open(&history, &recorder, window_start_ms, now_ms);
+    TrackingCoverage {
+        run_ms: now_ms.saturating_sub(window_start_ms),
+        tracked_ms: sessions
+            .iter()
+            .map(|session| session.overlap_ms(window_start_ms, now_ms))
+            .sum(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::sync::{atomic::Ordering, Mutex};
+
+    #[test]
+    fn startup_recording_is_skipped_when_startup_metrics_are_off() {
+        let mut preferences = Preferences::default();
+        assert!(records_startup_at(
+            &preferences,
+            StartupMilestone::ProcessReady
+        ));
+        assert!(!records_startup_at(
+            &preferences,
+            StartupMilestone::FirstPaint
+        ));
+
+        preferences.show_startup_metrics = false;
+        assert!(!records_startup_at(
+            &preferences,
+            StartupMilestone::ProcessReady
+        ));
+    }
+
+    #[test]
+    fn clamped_dashboard_size_only_grows_undersized_dimensions() {
+        assert_eq!(clamped_dashboard_size(440, 520, 1.0), None);
+        assert_eq!(clamped_dashboard_size(200, 520, 1.0), Some((320, 520)));
+        assert_eq!(clamped_dashboard_size(700, 100, 2.0), Some((700, 720)));
+        assert_eq!(clamped_dashboard_size(640, 720, 2.0), None);
+    }
+
+    #[test]
+    fn auto_hide_waits_for_idle_time_and_restarts_on_interaction() {
+        let timeout = Duration::from_secs(30);
+        let shown = Instant::now();
+        let state = UsageWindowState::default();
+        let generation = state.start_auto_hide(MAIN_WINDOW_LABEL, shown);
+
+        let timer = state.auto_hide_timer(MAIN_WINDOW_LABEL);
+        assert_eq!(
+            auto_hide_step(timer, generation, timeout, shown + Duration::from_secs(10)),
+            AutoHideStep::WaitFor(Duration::from_secs(20))
+        );
+        assert_eq!(
+            auto_hide_step(timer, generation, timeout, shown + timeout),
+            AutoHideStep::Hide
+        );
+
+        state.note_interaction(MAIN_WINDOW_LABEL, shown + Duration::from_secs(25));
+        let timer = state.auto_hide_timer(MAIN_WINDOW_LABEL);
+        assert_eq!(
+            auto_hide_step(timer, generation, timeout, shown + timeout),
+            AutoHideStep::WaitFor(Duration::from_secs(25))
+        );
+    }
+
+    #[test]
+    fn auto_hide_timers_stop_when_hidden_or_shown_again() {
+        let timeout = Duration::from_secs(30);
+        let shown = Instant::now();
+        let state = UsageWindowState::default();
+        let first = state.start_auto_hide(MAIN_WINDOW_LABEL, shown);
+        let second = state.start_auto_hide(MAIN_WINDOW_LABEL, shown);
+        let timer = state.auto_hide_timer(MAIN_WINDOW_LABEL);
+        assert_eq!(
+            auto_hide_step(timer, first, timeout, shown + timeout),
+            AutoHideStep::Cancel
+        );
+        assert_eq!(
+            auto_hide_step(timer, second, timeout, shown + timeout),
+            AutoHideStep::Hide
+        );
+
+        state.cancel_auto_hide(MAIN_WINDOW_LABEL);
+        assert_eq!(
+            auto_hide_step(
+                state.auto_hide_timer(MAIN_WINDOW_LABEL),
+                second,
+                timeout,
+                shown + timeout
+            ),
+            AutoHideStep::Cancel
+        );
+    }
 
     #[test]
     fn toggle_visible_should_invert() {
-        assert!(toggled_visible(false));
-        assert!(!toggled_visible(true));
+        assert!(toggled_visible(false, false));
+        assert!(!toggled_visible(true, false));
+    }
+
+    #[test]
+    fn pinned_dashboards_stay_shown_when_toggled() {
+        assert!(toggled_visible(false, true));
+        assert!(toggled_visible(true, true));
+
+        let state = UsageWindowState::default();
+        state.set_visible(MAIN_WINDOW_LABEL, true);
+        assert!(!state.toggle_shows(MAIN_WINDOW_LABEL, false));
+        assert!(state.toggle_shows(MAIN_WINDOW_LABEL, true));
+        state.set_pinned(MAIN_WINDOW_LABEL, true);
+        assert!(state.toggle_shows(MAIN_WINDOW_LABEL, false));
+        assert!(!state.is_pinned(&dashboard_label(1)));
+    }
+
+    #[test]
+    fn header_pin_and_pin_preference_both_hold_dashboards_open() {
+        let state = UsageWindowState::default();
+        let second = dashboard_label(1);
+        state.set_pinned(MAIN_WINDOW_LABEL, true);
+
+        assert!(state.is_held_open(MAIN_WINDOW_LABEL, false));
+        assert!(!state.is_held_open(&second, false));
+        assert!(state.is_held_open(&second, true));
+
+        assert!(!should_hide_on_blur(
+            state.is_held_open(MAIN_WINDOW_LABEL, false),
+            true
+        ));
+        assert!(should_hide_on_blur(
+            state.is_held_open(&second, false),
+            true
+        ));
+        assert!(!should_hide_on_blur(
+            state.is_held_open(&second, true),
+            true
+        ));
     }
 
     #[test]
@@ -462,10 +1514,51 @@ mod tests {
         assert_eq!(TRAY_OPEN_ID, "toggle");
     }
 
+    #[test]
+    fn blur_hides_only_unpinned_auto_hiding_dashboards() {
+        assert!(should_hide_on_blur(false, true));
+        assert!(!should_hide_on_blur(false, false));
+        assert!(!should_hide_on_blur(true, true));
+        assert!(!should_hide_on_blur(true, false));
+    }
+
     #[test]
     fn usage_window_state_defaults_to_hidden() {
         let state = UsageWindowState::default();
-        assert!(!state.visible.load(Ordering::SeqCst));
+        assert!(!state.is_visible(MAIN_WINDOW_LABEL));
+    }
+
+    #[test]
+    fn usage_window_state_tracks_each_dashboard_separately() {
+        let state = UsageWindowState::default();
+        let second = dashboard_label(1);
+
+        state.set_visible(
+            MAIN_WINDOW_LABEL,
+            state.toggle_shows(MAIN_WINDOW_LABEL, false),
+        );
+        assert!(state.is_visible(MAIN_WINDOW_LABEL));
+        assert!(!state.is_visible(&second));
+
+        state.set_visible(&second, state.toggle_shows(&second, false));
+        state.set_visible(
+            MAIN_WINDOW_LABEL,
+            state.toggle_shows(MAIN_WINDOW_LABEL, false),
+        );
+        assert!(!state.is_visible(MAIN_WINDOW_LABEL));
+        assert!(state.is_visible(&second));
+    }
+
+    #[test]
+    fn dashboard_labels_round_trip_through_menu_ids() {
+        assert_eq!(dashboard_label(0), "main");
+        assert_eq!(dashboard_label(1), "main-2");
+        assert!(is_dashboard_label("main"));
+        assert!(is_dashboard_label("main-2"));
+        assert!(!is_dashboard_label("settings"));
+        assert!(!is_dashboard_label("main-extra"));
+        assert_eq!(parse_display_menu_id("display:1"), Some(1));
+        assert_eq!(parse_display_menu_id("settings"), None);
     }
 
     struct MockWindow {
@@ -505,11 +1598,11 @@ mod tests {
     fn hide_usage_window_updates_state_and_invokes_window_actions() {
         let window = MockWindow::new();
         let usage_state = UsageWindowState::default();
-        usage_state.visible.store(true, Ordering::SeqCst);
+        usage_state.set_visible(MAIN_WINDOW_LABEL, true);
 
-        hide_usage_window(&window, &usage_state);
+        hide_usage_window(&window, MAIN_WINDOW_LABEL, &usage_state);
 
-        assert!(!usage_state.visible.load(Ordering::SeqCst));
+        assert!(!usage_state.is_visible(MAIN_WINDOW_LABEL));
         assert_eq!(window.hide_count(), 1);
         assert_eq!(window.last_always_on_top(), Some(false));
     }
@@ -0,0 +1,158 @@
+//! SQLite housekeeping: decides when to `VACUUM` and tracks the bookkeeping needed to decide.
+
+use std::time::SystemTime;
+
+use rusqlite::{params, Connection, OptionalExtension};
+use tauri::State;
+
+use crate::app_usage::system_time_to_ms;
+use crate::startup_metrics::StartupMetrics;
+use crate::usage_history::UsageHistory;
+
+/// Vacuum once the last run is older than this.
+const VACUUM_MAX_AGE_MS: u64 = 30 * 24 * 60 * 60 * 1_000;
+/// Vacuum once this many rows were deleted since the last run.
+const VACUUM_DELETE_THRESHOLD: u64 = 500;
+
+const LAST_VACUUM_KEY: &str = "last_vacuum_ms";
+const DELETES_SINCE_VACUUM_KEY: &str = "deletes_since_vacuum";
+
+/// Returns true when the database is due a `VACUUM`.
+pub fn should_vacuum(last_vacuum_ms: Option<u64>, deletes_since_vacuum: u64, now_ms: u64) -> bool {
+    if deletes_since_vacuum >= VACUUM_DELETE_THRESHOLD {
+        return true;
+    }
+    last_vacuum_ms.is_some_and(|last| now_ms.saturating_sub(last) >= VACUUM_MAX_AGE_MS)
+}
+
+/// Creates the metadata table holding vacuum bookkeeping.
+pub fn migrate(connection: &Connection) -> rusqlite::Result<()> {
+    connection.execute_batch(
+        "CREATE TABLE IF NOT EXISTS maintenance (
+            key TEXT PRIMARY KEY,
+            value INTEGER NOT NULL
+        );",
+    )
+}
+
+fn read_value(connection: &Connection, key: &str) -> rusqlite::Result<Option<u64>> {
+    connection
+        .query_row(
+            "SELECT value FROM maintenance WHERE key = ?1",
+            params![key],
+            |row| row.get::<_, i64>(0),
+        )
+        .optional()
+        .map(|value| value.map(|value| value.max(0) as u64))
+}
+
+fn write_value(connection: &Connection, key: &str, value: u64) -> rusqlite::Result<()> {
+    connection.execute(
+        "INSERT INTO maintenance (key, value) VALUES (?1, ?2)
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+        params![key, value.min(i64::MAX as u64) as i64],
+    )?;
+    Ok(())
+}
+
+/// Adds `deleted` rows to the count that triggers the next vacuum.
+pub fn record_deletes(connection: &Connection, deleted: usize) -> rusqlite::Result<()> {
+    if deleted == 0 {
+        return Ok(());
+    }
+    let current = read_value(connection, DELETES_SINCE_VACUUM_KEY)?.unwrap_or(0);
+    write_value(
+        connection,
+        DELETES_SINCE_VACUUM_KEY,
+        current.saturating_add(deleted as u64),
+    )
+}
+
+/// Vacuums when the metadata says it is due and returns the bytes reclaimed, if it ran.
+///
+/// A database without a recorded vacuum starts its age clock now instead of vacuuming.
+pub fn run_if_due(connection: &Connection, now_ms: u64) -> rusqlite::Result<Option<u64>> {
+    let last_vacuum = read_value(connection, LAST_VACUUM_KEY)?;
+    let deletes = read_value(connection, DELETES_SINCE_VACUUM_KEY)?.unwrap_or(0);
+    if should_vacuum(last_vacuum, deletes, now_ms) {
+        return vacuum(connection, now_ms).map(Some);
+    }
+    if last_vacuum.is_none() {
+        write_value(connection, LAST_VACUUM_KEY, now_ms)?;
+    }
+    Ok(None)
+}
+
+/// Runs `VACUUM`, resets the bookkeeping, and returns how many bytes the file shrank by.
+pub fn vacuum(connection: &Connection, now_ms: u64) -> rusqlite::Result<u64> {
+    let before = database_size(connection)?;
+    connection.execute_batch("VACUUM")?;
+    let after = database_size(connection)?;
+    write_value(connection, LAST_VACUUM_KEY, now_ms)?;
+    write_value(connection, DELETES_SINCE_VACUUM_KEY, 0)?;
+    Ok(before.saturating_sub(after))
+}
+
+fn database_size(connection: &Connection) -> rusqlite::Result<u64> {
+    let page_count: i64 = connection.query_row("PRAGMA page_count", [], |row| row.get(0))?;
+    let page_size: i64 = connection.query_row("PRAGMA page_size", [], |row| row.get(0))?;
+    Ok((page_count.max(0) as u64) * (page_size.max(0) as u64))
+}
+
+/// Vacuums every database on demand and reports the total bytes reclaimed.
+#[tauri::command]
+pub fn vacuum_database(
+    metrics: State<'_, StartupMetrics>,
+    history: State<'_, UsageHistory>,
+) -> Result<u64, String> {
+    let now_ms = system_time_to_ms(SystemTime::now());
+    Ok(metrics.vacuum(now_ms)? + history.vacuum(now_ms)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY_MS: u64 = 24 * 60 * 60 * 1_000;
+
+    #[test]
+    fn should_vacuum_after_max_age_or_delete_threshold() {
+        let now = 100 * DAY_MS;
+        assert!(!should_vacuum(None, 0, now));
+        assert!(!should_vacuum(Some(now - DAY_MS), 10, now));
+        assert!(should_vacuum(Some(now - 30 * DAY_MS), 0, now));
+        assert!(should_vacuum(None, VACUUM_DELETE_THRESHOLD, now));
+    }
+
+    #[test]
+    fn run_if_due_follows_metadata() {
+        let connection = Connection::open_in_memory().unwrap();
+        migrate(&connection).unwrap();
+        let start = 10 * DAY_MS;
+
+        // First run only starts the age clock.
+        assert_eq!(run_if_due(&connection, start).unwrap(), None);
+        assert_eq!(
+            read_value(&connection, LAST_VACUUM_KEY).unwrap(),
+            Some(start)
+        );
+
+        record_deletes(&connection, 300).unwrap();
+        assert_eq!(run_if_due(&connection, start + DAY_MS).unwrap(), None);
+
+        record_deletes(&connection, 200).unwrap();
+        assert!(run_if_due(&connection, start + DAY_MS).unwrap().is_some());
+        assert_eq!(
+            read_value(&connection, DELETES_SINCE_VACUUM_KEY).unwrap(),
+            Some(0)
+        );
+        assert_eq!(
+            read_value(&connection, LAST_VACUUM_KEY).unwrap(),
+            Some(start + DAY_MS)
+        );
+
+        assert!(run_if_due(&connection, start + 31 * DAY_MS)
+            .unwrap()
+            .is_some());
+    }
+}
@@ -0,0 +1,192 @@
+//! Placement math for the dashboard window, kept free of window handles so it can be tested.
+
+/// Gap between the window and the monitor edge or tray icon.
+const EDGE_MARGIN: i32 = 24;
+/// Distance from the top of the monitor, leaving room for a menu bar or panel.
+const TOP_OFFSET: i32 = 32;
+/// Gap between the tray icon and the window opened from it.
+const TRAY_GAP: f64 = 12.0;
+
+/// A monitor's position and size in physical pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MonitorArea {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl From<&tauri::Monitor> for MonitorArea {
+    fn from(monitor: &tauri::Monitor) -> Self {
+        Self {
+            x: monitor.position().x,
+            y: monitor.position().y,
+            width: monitor.size().width,
+            height: monitor.size().height,
+        }
+    }
+}
+
+/// Name shown for the monitor at `index`: its own name, else "Display N" counting from 1.
+pub fn monitor_label(name: Option<&str>, index: usize) -> String {
+    name.map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map_or_else(|| format!("Display {}", index + 1), str::to_string)
+}
+
+/// Area of the monitor labelled `preferred`, or `None` when no preference is set or that monitor is not connected.
+pub fn preferred_monitor(
+    monitors: &[(String, MonitorArea)],
+    preferred: &str,
+) -> Option<MonitorArea> {
+    let preferred = preferred.trim();
+    if preferred.is_empty() {
+        return None;
+    }
+    monitors
+        .iter()
+        .find(|(label, _)| label.eq_ignore_ascii_case(preferred))
+        .map(|(_, area)| *area)
+}
+
+/// What the window is positioned against.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Anchor {
+    /// The top-right corner of the monitor.
+    TopRight,
+    /// A tray icon clicked at `(x, y)` whose icon is `icon_height` tall.
+    #[cfg_attr(any(target_os = "macos", target_os = "linux"), allow(dead_code))]
+    Tray { x: f64, y: f64, icon_height: f64 },
+}
+
+/// Top-left position for a `width` x `height` window, kept on `monitor` when one is known.
+///
+/// Without a monitor the window stays at non-negative coordinates, near the anchor when there is one.
+pub fn window_position(
+    monitor: Option<MonitorArea>,
+    width: u32,
+    height: u32,
+    anchor: Anchor,
+) -> (i32, i32) {
+    let (x, y) = match (anchor, monitor) {
+        (Anchor::TopRight, Some(area)) => (
+            area.x + area.width as i32 - width as i32 - EDGE_MARGIN,
+            area.y + TOP_OFFSET,
+        ),
+        (Anchor::TopRight, None) => (EDGE_MARGIN, TOP_OFFSET),
+        (Anchor::Tray { x, y, icon_height }, monitor) => {
+            let opens_upward = match monitor {
+                Some(area) => y > f64::from(area.y) + f64::from(area.height) / 2.0,
+                None => y >= f64::from(height) + TRAY_GAP,
+            };
+            let top = if opens_upward {
+                y - f64::from(height) - TRAY_GAP
+            } else {
+                y + icon_height + TRAY_GAP
+            };
+            (
+                (x - f64::from(width) / 2.0).round() as i32,
+                top.round() as i32,
+            )
+        }
+    };
+
+    match monitor {
+        Some(area) => (
+            clamp_axis(x, area.x, area.width, width),
+            clamp_axis(y, area.y, area.height, height),
+        ),
+        None => (x.max(0), y.max(0)),
+    }
+}
+
+/// Keeps a span starting at `value` inside `[origin, origin + extent)`, pinning to the origin when it cannot fit.
+fn clamp_axis(value: i32, origin: i32, extent: u32, size: u32) -> i32 {
+    let max = origin + extent as i32 - size as i32;
+    value.min(max).max(origin)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MONITOR: MonitorArea = MonitorArea {
+        x: 1920,
+        y: 0,
+        width: 1920,
+        height: 1080,
+    };
+
+    #[test]
+    fn preferred_monitor_is_found_by_label_or_skipped_when_absent() {
+        let laptop = MonitorArea {
+            x: 0,
+            y: 0,
+            width: 1440,
+            height: 900,
+        };
+        let monitors = vec![
+            (monitor_label(Some("Built-in Retina Display"), 0), laptop),
+            (monitor_label(None, 1), MONITOR),
+        ];
+
+        assert_eq!(preferred_monitor(&monitors, "display 2"), Some(MONITOR));
+        assert_eq!(
+            preferred_monitor(&monitors, "Built-in Retina Display"),
+            Some(laptop)
+        );
+        // Undocked: the desk monitor is gone, so the default placement applies.
+        assert_eq!(preferred_monitor(&monitors, "DELL U2720Q"), None);
+        assert_eq!(preferred_monitor(&monitors, ""), None);
+    }
+
+    #[test]
+    fn top_right_uses_the_monitor_origin() {
+        assert_eq!(
+            window_position(Some(MONITOR), 440, 520, Anchor::TopRight),
+            (1920 + 1920 - 440 - 24, 32)
+        );
+    }
+
+    #[test]
+    fn tray_anchor_opens_toward_the_monitor_centre_and_stays_on_screen() {
+        let bottom_tray = Anchor::Tray {
+            x: 3830.0,
+            y: 1060.0,
+            icon_height: 20.0,
+        };
+        assert_eq!(
+            window_position(Some(MONITOR), 440, 520, bottom_tray),
+            (3840 - 440, 1060 - 520 - 12)
+        );
+
+        let top_tray = Anchor::Tray {
+            x: 2000.0,
+            y: 4.0,
+            icon_height: 20.0,
+        };
+        assert_eq!(
+            window_position(Some(MONITOR), 440, 520, top_tray),
+            (1920, 36)
+        );
+    }
+
+    #[test]
+    fn missing_monitor_never_places_the_window_off_screen() {
+        assert_eq!(window_position(None, 440, 520, Anchor::TopRight), (24, 32));
+
+        let near_origin = Anchor::Tray {
+            x: 100.0,
+            y: 10.0,
+            icon_height: 20.0,
+        };
+        assert_eq!(window_position(None, 440, 520, near_origin), (0, 42));
+
+        let low_tray = Anchor::Tray {
+            x: 1200.0,
+            y: 1000.0,
+            icon_height: 20.0,
+        };
+        assert_eq!(window_position(None, 440, 520, low_tray), (980, 468));
+    }
+}
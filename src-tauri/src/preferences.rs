@@ -0,0 +1,253 @@
+//! Stores user preferences as a JSON document in the app data directory.
+
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use tauri::Emitter;
+use time_wise_shared::preferences::clamp;
+pub use time_wise_shared::preferences::{
+    validate, BackupSchedule, Preferences, StartupMeasurement, StartupMilestone, TileLabelSource,
+};
+
+/// Event emitted to every window after the preferences were replaced wholesale.
+pub const PREFERENCES_CHANGED_EVENT: &str = "preferences-changed";
+
+/// Thread-safe handle to the persisted preferences.
+pub struct PreferencesStore {
+    storage_path: PathBuf,
+    current: Mutex<Preferences>,
+}
+
+impl PreferencesStore {
+    /// Loads preferences from disk, falling back to defaults when missing or unreadable.
+    pub fn with_storage_path(storage_path: PathBuf) -> Self {
+        let mut current = match std::fs::read_to_string(&storage_path) {
+            Ok(data) => serde_json::from_str(&data).unwrap_or_else(|err| {
+                eprintln!("failed to parse preferences, using defaults: {err}");
+                Preferences::default()
+            }),
+            Err(_) => Preferences::default(),
+        };
+        if let Err(err) = validate(&current) {
+            eprintln!("stored preferences are out of range, clamping: {err}");
+            clamp(&mut current);
+        }
+
+        Self {
+            storage_path,
+            current: Mutex::new(current),
+        }
+    }
+
+    /// Returns a snapshot of the current preferences.
+    pub fn get(&self) -> Preferences {
+        match self.current.lock() {
+            Ok(current) => current.clone(),
+            Err(_) => Preferences::default(),
+        }
+    }
+
+    /// Replaces a single preference by its serialized key and persists the result.
+    pub fn set(&self, key: &str, value: serde_json::Value) -> Result<Preferences, String> {
+        let mut current = self
+            .current
+            .lock()
+            .map_err(|_| "preferences mutex poisoned".to_string())?;
+
+        let mut document = serde_json::to_value(&*current).map_err(|err| err.to_string())?;
+        let fields = document
+            .as_object_mut()
+            .ok_or_else(|| "preferences must serialize to an object".to_string())?;
+        if !fields.contains_key(key) {
+            return Err(format!("unknown preference: {key}"));
+        }
+        fields.insert(key.to_string(), value);
+
+        let updated: Preferences = serde_json::from_value(document)
+            .map_err(|err| format!("invalid value for {key}: {err}"))?;
+        validate(&updated).map_err(|err| format!("invalid value for {key}: {err}"))?;
+        self.persist(&updated)?;
+        *current = updated.clone();
+        Ok(updated)
+    }
+
+    /// Replaces every preference at once and persists the result, rejecting out-of-range values.
+    pub fn replace(&self, preferences: Preferences) -> Result<(), String> {
+        validate(&preferences)?;
+        let mut current = self
+            .current
+            .lock()
+            .map_err(|_| "preferences mutex poisoned".to_string())?;
+        self.persist(&preferences)?;
+        *current = preferences;
+        Ok(())
+    }
+
+    /// Restores every user setting to its default, persists it, and returns the result.
+    ///
+    /// Bookkeeping the app keeps in the same document (autostart intent and pause, backup
+    /// location, and when the last backup and weekly summary ran) carries over unchanged.
+    pub fn reset(&self) -> Result<Preferences, String> {
+        let mut current = self
+            .current
+            .lock()
+            .map_err(|_| "preferences mutex poisoned".to_string())?;
+        let defaults = Preferences {
+            autostart_intended: current.autostart_intended,
+            autostart_paused_until_ms: current.autostart_paused_until_ms,
+            backup_dir: current.backup_dir.clone(),
+            last_backup_ms: current.last_backup_ms,
+            last_weekly_summary_ms: current.last_weekly_summary_ms,
+            ..Preferences::default()
+        };
+        self.persist(&defaults)?;
+        *current = defaults.clone();
+        Ok(defaults)
+    }
+
+    fn persist(&self, preferences: &Preferences) -> Result<(), String> {
+        if let Some(parent) = self.storage_path.parent() {
+            std::fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+        }
+        let data = serde_json::to_string_pretty(preferences).map_err(|err| err.to_string())?;
+        std::fs::write(&self.storage_path, data).map_err(|err| err.to_string())
+    }
+}
+
+#[tauri::command]
+/// Tauri command returning every stored preference.
+pub fn fetch_preferences(state: tauri::State<'_, PreferencesStore>) -> Preferences {
+    state.get()
+}
+
+#[tauri::command]
+/// Tauri command updating one preference, telling open windows to reload, and returning the full updated set.
+pub fn set_preference(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, PreferencesStore>,
+    key: String,
+    value: serde_json::Value,
+) -> Result<Preferences, String> {
+    let updated = state.set(&key, value)?;
+    if let Err(err) = app.emit(PREFERENCES_CHANGED_EVENT, &updated) {
+        eprintln!("failed to emit {PREFERENCES_CHANGED_EVENT}: {err}");
+    }
+    Ok(updated)
+}
+
+#[tauri::command]
+/// Tauri command returning the dashboard view selected last time.
+pub fn get_last_view(state: tauri::State<'_, PreferencesStore>) -> String {
+    state.get().last_view
+}
+
+#[tauri::command]
+/// Tauri command remembering the selected dashboard view and returning the stored value.
+pub fn set_last_view(
+    state: tauri::State<'_, PreferencesStore>,
+    view: String,
+) -> Result<String, String> {
+    state
+        .set("last_view", serde_json::Value::String(view))
+        .map(|preferences| preferences.last_view)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use time_wise_shared::preferences::MAX_DURATION_PRECISION;
+
+    #[test]
+    fn missing_file_yields_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = PreferencesStore::with_storage_path(dir.path().join("preferences.json"));
+        assert_eq!(store.get(), Preferences::default());
+    }
+
+    #[test]
+    fn set_persists_across_reloads() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("preferences.json");
+        let store = PreferencesStore::with_storage_path(path.clone());
+
+        store.set("habit_reminders", json!(true)).unwrap();
+        store.set("pinned_apps", json!(["Journal"])).unwrap();
+
+        let reloaded = PreferencesStore::with_storage_path(path);
+        assert!(reloaded.get().habit_reminders);
+        assert_eq!(reloaded.get().pinned_apps, vec!["Journal".to_string()]);
+    }
+
+    #[test]
+    fn out_of_range_values_are_rejected_on_replace_and_clamped_on_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("preferences.json");
+        let store = PreferencesStore::with_storage_path(path.clone());
+        let out_of_range = Preferences {
+            duration_precision: 9,
+            ..Preferences::default()
+        };
+        assert!(store.replace(out_of_range.clone()).is_err());
+
+        std::fs::write(&path, serde_json::to_string(&out_of_range).unwrap()).unwrap();
+        let reloaded = PreferencesStore::with_storage_path(path);
+        assert_eq!(reloaded.get().duration_precision, MAX_DURATION_PRECISION);
+    }
+
+    #[test]
+    fn reset_restores_every_default_and_persists_it() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("preferences.json");
+        let store = PreferencesStore::with_storage_path(path.clone());
+        store.set("habit_reminders", json!(true)).unwrap();
+        store.set("recent_chart_bars", json!(9)).unwrap();
+        store.set("blocked_apps", json!(["Slack"])).unwrap();
+        store.set("backup_schedule", json!("weekly")).unwrap();
+        store.set("autostart_intended", json!(true)).unwrap();
+        store
+            .set("autostart_paused_until_ms", json!(5_000))
+            .unwrap();
+        store.set("backup_dir", json!("/backups")).unwrap();
+        store.set("last_backup_ms", json!(1_000)).unwrap();
+        store.set("last_weekly_summary_ms", json!(2_000)).unwrap();
+
+        let bookkeeping = Preferences {
+            autostart_intended: Some(true),
+            autostart_paused_until_ms: Some(5_000),
+            backup_dir: "/backups".to_string(),
+            last_backup_ms: Some(1_000),
+            last_weekly_summary_ms: Some(2_000),
+            ..Preferences::default()
+        };
+        assert_eq!(store.reset().unwrap(), bookkeeping);
+        let preferences = store.get();
+        assert!(!preferences.habit_reminders);
+        assert_eq!(preferences.recent_chart_bars, 5);
+        assert!(preferences.blocked_apps.is_empty());
+        assert_eq!(preferences.backup_schedule, BackupSchedule::Off);
+        assert_eq!(preferences.autostart_intended, Some(true));
+        assert_eq!(preferences.autostart_paused_until_ms, Some(5_000));
+        assert_eq!(PreferencesStore::with_storage_path(path).get(), bookkeeping);
+    }
+
+    #[test]
+    fn set_rejects_unknown_keys_and_invalid_values() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = PreferencesStore::with_storage_path(dir.path().join("preferences.json"));
+
+        assert!(store.set("not_a_preference", json!(true)).is_err());
+        assert!(store.set("habit_reminders", json!("yes")).is_err());
+        assert!(store.set("tile_label_source", json!("icon")).is_err());
+        assert!(store.set("duration_precision", json!(4)).is_err());
+        assert_eq!(
+            store
+                .set("duration_precision", json!(MAX_DURATION_PRECISION))
+                .unwrap()
+                .duration_precision,
+            MAX_DURATION_PRECISION
+        );
+        store.set("duration_precision", json!(2)).unwrap();
+        assert_eq!(store.get(), Preferences::default());
+    }
+}
@@ -0,0 +1,174 @@
+//! Daily habit reminders for pinned apps that have not been opened yet today.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use tauri::{AppHandle, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::app_usage::{system_time_to_ms, AppUsageRecorder};
+use crate::calendar::{day_offset_minutes, day_start_ms, local_offset_minutes};
+use crate::preferences::PreferencesStore;
+use crate::usage_history::UsageHistory;
+
+/// Remembers which apps were already reminded about, keyed by the local day start.
+#[derive(Default)]
+pub struct HabitReminderState {
+    reminded: Mutex<HashMap<String, u64>>,
+}
+
+impl HabitReminderState {
+    /// Returns true the first time an app is reminded about on a given day.
+    fn mark_reminded(&self, app: &str, day_start: u64) -> bool {
+        let Ok(mut reminded) = self.reminded.lock() else {
+            return false;
+        };
+        if reminded.get(app) == Some(&day_start) {
+            return false;
+        }
+        reminded.insert(app.to_string(), day_start);
+        true
+    }
+}
+
+/// Decides whether a pinned app is due a reminder: the reminder time has passed and it was not seen today.
+///
+/// The reminder time counts from local midnight; "today" starts at `rollover_hour`.
+pub fn should_remind(
+    last_seen_ms: Option<u64>,
+    now_ms: u64,
+    offset_minutes: i32,
+    rollover_hour: u32,
+    remind_after_minutes: u32,
+) -> bool {
+    let midnight = day_start_ms(now_ms, offset_minutes);
+    if now_ms < midnight + u64::from(remind_after_minutes) * 60_000 {
+        return false;
+    }
+    let today = day_start_ms(now_ms, day_offset_minutes(offset_minutes, rollover_hour));
+    last_seen_ms.is_none_or(|seen| seen < today)
+}
+
+/// Fires a notification for each pinned app that is due a reminder.
+pub fn check_habit_reminders(app: &AppHandle) {
+    let preferences = app.state::<PreferencesStore>().get();
+    if !preferences.habit_reminders || preferences.pinned_apps.is_empty() {
+        return;
+    }
+
+    let now_ms = system_time_to_ms(SystemTime::now());
+    let offset_minutes = local_offset_minutes();
+    let rollover_hour = preferences.day_rollover_hour;
+    let today = day_start_ms(now_ms, day_offset_minutes(offset_minutes, rollover_hour));
+    let records = app.state::<AppUsageRecorder>().records();
+    let sessions = app.state::<UsageHistory>().sessions_between(today, now_ms);
+    let state = app.state::<HabitReminderState>();
+
+    for name in &preferences.pinned_apps {
+        let last_seen = records
+            .iter()
+            .filter(|record| &record.name == name)
+            .map(|record| record.last_seen_at_ms)
+            .chain(
+                sessions
+                    .iter()
+                    .filter(|session| &session.name == name)
+                    .map(|session| session.end_ms),
+            )
+            .max();
+
+        if !should_remind(
+            last_seen,
+            now_ms,
+            offset_minutes,
+            rollover_hour,
+            preferences.habit_reminder_minutes,
+        ) || !state.mark_reminded(name, today)
+        {
+            continue;
+        }
+
+        if let Err(err) = app
+            .notification()
+            .builder()
+            .title("Time Wise")
+            .body(format!("You haven't opened {name} today"))
+            .show()
+        {
+            eprintln!("failed to show habit reminder: {err}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR_MS: u64 = 60 * 60_000;
+    const DAY_MS: u64 = 24 * HOUR_MS;
+
+    #[test]
+    fn reminds_when_last_seen_before_today() {
+        let now = 10 * DAY_MS + 19 * HOUR_MS;
+        let yesterday_evening = 10 * DAY_MS - HOUR_MS;
+        assert!(should_remind(Some(yesterday_evening), now, 0, 0, 18 * 60));
+        assert!(should_remind(None, now, 0, 0, 18 * 60));
+    }
+
+    #[test]
+    fn skips_when_seen_after_midnight() {
+        let now = 10 * DAY_MS + 19 * HOUR_MS;
+        let just_after_midnight = 10 * DAY_MS + 1;
+        assert!(!should_remind(
+            Some(just_after_midnight),
+            now,
+            0,
+            0,
+            18 * 60
+        ));
+    }
+
+    #[test]
+    fn waits_for_the_configured_time() {
+        let before_reminder = 10 * DAY_MS + 17 * HOUR_MS;
+        assert!(!should_remind(None, before_reminder, 0, 0, 18 * 60));
+    }
+
+    #[test]
+    fn day_boundary_follows_local_offset() {
+        // 01:00 local at UTC+2 is 23:00 UTC of the previous day.
+        let now = 10 * DAY_MS + 12 * HOUR_MS;
+        let seen_local_early_morning = 9 * DAY_MS + 23 * HOUR_MS;
+        assert!(!should_remind(
+            Some(seen_local_early_morning),
+            now,
+            120,
+            0,
+            0
+        ));
+        assert!(should_remind(Some(seen_local_early_morning), now, 0, 0, 0));
+    }
+
+    #[test]
+    fn reminder_state_fires_once_per_day() {
+        let state = HabitReminderState::default();
+        assert!(state.mark_reminded("Journal", DAY_MS));
+        assert!(!state.mark_reminded("Journal", DAY_MS));
+        assert!(state.mark_reminded("Journal", 2 * DAY_MS));
+    }
+
+    #[test]
+    fn rollover_hour_counts_small_hours_toward_the_previous_day() {
+        let now = 10 * DAY_MS + 19 * HOUR_MS;
+        let just_after_midnight = 10 * DAY_MS + HOUR_MS;
+        assert!(!should_remind(
+            Some(just_after_midnight),
+            now,
+            0,
+            0,
+            18 * 60
+        ));
+        assert!(should_remind(Some(just_after_midnight), now, 0, 4, 18 * 60));
+    }
+}
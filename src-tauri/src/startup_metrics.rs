@@ -3,12 +3,78 @@
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Mutex;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use rusqlite::{params, Connection};
 use serde::{Deserialize, Serialize};
+use time_wise_shared::build_environment::UNKNOWN_ENVIRONMENT;
+use time_wise_shared::chart_data::{
+    category_totals, chart_points, distribution_points, forecast_next, format_clock_time,
+    format_duration, time_saved_vs_worst, CategoryTotals, ChartPoint, ChartSample, FORECAST_WINDOW,
+};
+
+use crate::app_usage::system_time_to_ms;
+use crate::calendar::{day_start_ms, local_day_offset_minutes, local_offset_minutes};
+use crate::environment::EnvironmentSnapshot;
+use crate::launcher::{classify_environment, normalize_launcher};
+use crate::maintenance;
+use crate::preferences::{Preferences, PreferencesStore, StartupMeasurement};
 
 const MAX_RECORDS: usize = 100;
+/// How long a deleted startup record can still be restored.
+const UNDO_WINDOW_MS: u64 = 10_000;
+
+/// Duration to store for this run and the measurement it actually used.
+///
+/// Counting from boot only applies to launch-on-boot starts with a known boot time; every other run keeps the in-process `elapsed`.
+pub fn startup_duration(
+    measurement: StartupMeasurement,
+    elapsed: Duration,
+    autostarted: bool,
+    boot_time_ms: u64,
+    ready_ms: u64,
+) -> (Duration, StartupMeasurement) {
+    if measurement == StartupMeasurement::SinceBoot
+        && autostarted
+        && boot_time_ms > 0
+        && ready_ms >= boot_time_ms
+    {
+        return (
+            Duration::from_millis(ready_ms - boot_time_ms),
+            StartupMeasurement::SinceBoot,
+        );
+    }
+    (elapsed, StartupMeasurement::Process)
+}
+
+/// When this run started, and whether its startup time was recorded yet.
+pub struct StartupClock {
+    started: Instant,
+    finished: AtomicBool,
+}
+
+impl StartupClock {
+    pub fn new(started: Instant) -> Self {
+        Self {
+            started,
+            finished: AtomicBool::new(false),
+        }
+    }
+
+    /// Time from launch to `now`, or `None` once this run's startup was already taken.
+    pub fn finish(&self, now: Instant) -> Option<Duration> {
+        if self.finished.swap(true, Ordering::SeqCst) {
+            return None;
+        }
+        Some(now.saturating_duration_since(self.started))
+    }
+
+    /// Lets `finish` measure again, still from launch.
+    #[cfg(debug_assertions)]
+    pub fn reset(&self) {
+        self.finished.store(false, Ordering::SeqCst);
+    }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 /// Represents a single startup measurement in milliseconds.
@@ -16,12 +82,256 @@ pub struct StartupRecord {
     pub recorded_at_ms: u64,
     pub duration_ms: u64,
     pub launcher: String,
+    pub battery_percent: Option<u8>,
+    pub thermal_pressure: Option<bool>,
+    pub cpu_time_ms: Option<u64>,
+    /// Whether the run was a release, debug, or development-tool launch.
+    #[serde(default = "unknown_build_environment")]
+    pub build_environment: String,
+    /// Whether the duration was counted from process start or from system boot.
+    #[serde(default)]
+    pub measurement: StartupMeasurement,
+}
+
+fn unknown_build_environment() -> String {
+    UNKNOWN_ENVIRONMENT.to_string()
+}
+
+/// Column value stored for a measurement source.
+fn measurement_name(measurement: StartupMeasurement) -> &'static str {
+    match measurement {
+        StartupMeasurement::Process => "process",
+        StartupMeasurement::SinceBoot => "since_boot",
+    }
+}
+
+/// Reads a stored measurement source, treating rows from before the column existed as in-process.
+fn parse_measurement(name: Option<&str>) -> StartupMeasurement {
+    match name {
+        Some("since_boot") => StartupMeasurement::SinceBoot,
+        _ => StartupMeasurement::Process,
+    }
+}
+
+impl From<&StartupRecord> for ChartSample {
+    fn from(record: &StartupRecord) -> Self {
+        Self {
+            recorded_at_ms: record.recorded_at_ms,
+            duration_ms: record.duration_ms,
+        }
+    }
+}
+
+/// Chart-ready startup data for consumers that cannot run the frontend aggregation.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ChartData {
+    pub recent: Vec<ChartPoint>,
+    pub distribution: Vec<ChartPoint>,
+    pub categories: CategoryTotals,
+}
+
+/// Aggregates newest-first records with the configured bar counts, labelling times `offset_minutes` east of UTC.
+pub fn chart_data(
+    records: &[StartupRecord],
+    preferences: &Preferences,
+    offset_minutes: i32,
+) -> ChartData {
+    let samples: Vec<ChartSample> = records.iter().map(ChartSample::from).collect();
+    ChartData {
+        recent: chart_points(&samples, preferences.recent_chart_bars as usize, |ms| {
+            format_clock_time(ms, offset_minutes)
+        }),
+        distribution: distribution_points(&samples, preferences.distribution_chart_bars as usize),
+        categories: category_totals(&samples),
+    }
+}
+
+/// Average startup duration of the runs started by one launcher.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LauncherAverage {
+    pub launcher: String,
+    pub average_ms: u64,
+    pub runs: u64,
+}
+
+/// Why the latest startup could not be recorded, kept until a later record is written.
+#[derive(Default)]
+pub struct RecordFailure(Mutex<Option<String>>);
+
+impl RecordFailure {
+    /// Keeps the error of a failed attempt and clears it once a record is written; skipped runs change nothing.
+    pub fn track(&self, result: &Result<Option<StartupRecord>, String>) {
+        let Ok(mut failure) = self.0.lock() else {
+            return;
+        };
+        match result {
+            Err(err) => *failure = Some(err.clone()),
+            Ok(Some(_)) => *failure = None,
+            Ok(None) => {}
+        }
+    }
+
+    pub fn get(&self) -> Option<String> {
+        self.0.lock().ok().and_then(|failure| failure.clone())
+    }
+}
+
+/// Share of the recorded starts opened by one launcher.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LauncherShare {
+    pub launcher: String,
+    pub count: usize,
+    pub fraction: f64,
+}
+
+/// How much slower one launcher's starts are than the average start.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LauncherSlowdown {
+    pub launcher: String,
+    pub avg_ms: u64,
+    pub count: u64,
+    /// Launcher average minus the overall average; positive means slower than usual.
+    pub delta_vs_overall_ms: i64,
+}
+
+/// Turns per-launcher run counts into shares of all starts, most-used first; ties keep name order.
+pub fn launcher_distribution(averages: &[LauncherAverage]) -> Vec<LauncherShare> {
+    let total: u64 = averages.iter().map(|average| average.runs).sum();
+    if total == 0 {
+        return Vec::new();
+    }
+    let mut shares: Vec<LauncherShare> = averages
+        .iter()
+        .filter(|average| average.runs > 0)
+        .map(|average| LauncherShare {
+            launcher: average.launcher.clone(),
+            count: average.runs as usize,
+            fraction: average.runs as f64 / total as f64,
+        })
+        .collect();
+    shares.sort_by(|a, b| {
+        b.count
+            .cmp(&a.count)
+            .then_with(|| a.launcher.to_lowercase().cmp(&b.launcher.to_lowercase()))
+    });
+    shares
+}
+
+/// Ranks launchers with at least `min_runs` runs by how far their average exceeds the average of every run, slowest first.
+pub fn rank_launcher_slowdowns(
+    averages: &[LauncherAverage],
+    min_runs: u64,
+) -> Vec<LauncherSlowdown> {
+    let total_runs: u64 = averages.iter().map(|average| average.runs).sum();
+    if total_runs == 0 {
+        return Vec::new();
+    }
+    let total_ms: u128 = averages
+        .iter()
+        .map(|average| u128::from(average.average_ms) * u128::from(average.runs))
+        .sum();
+    let overall_ms = (total_ms / u128::from(total_runs)) as i64;
+
+    let mut ranking: Vec<LauncherSlowdown> = averages
+        .iter()
+        .filter(|average| average.runs >= min_runs.max(1))
+        .map(|average| LauncherSlowdown {
+            launcher: average.launcher.clone(),
+            avg_ms: average.average_ms,
+            count: average.runs,
+            delta_vs_overall_ms: average.average_ms as i64 - overall_ms,
+        })
+        .collect();
+    ranking.sort_by(|a, b| {
+        b.delta_vs_overall_ms
+            .cmp(&a.delta_vs_overall_ms)
+            .then_with(|| a.launcher.cmp(&b.launcher))
+    });
+    ranking
+}
+
+/// One-line summary of every run for sharing, such as "Time Wise startup: 0.42 s avg over 87 runs (best 0.31 s)".
+pub fn startup_headline_text(records: &[StartupRecord]) -> String {
+    let Some(best_ms) = records.iter().map(|record| record.duration_ms).min() else {
+        return "No startup data yet".to_string();
+    };
+    let total_ms: u128 = records
+        .iter()
+        .map(|record| u128::from(record.duration_ms))
+        .sum();
+    let average_ms = (total_ms / records.len() as u128) as u64;
+    let runs_label = if records.len() == 1 { "run" } else { "runs" };
+    format!(
+        "Time Wise startup: {} avg over {} {runs_label} (best {})",
+        format_duration(average_ms),
+        records.len(),
+        format_duration(best_ms)
+    )
 }
 
 /// High-level manager that persists and serves startup metrics.
 pub struct StartupMetrics {
     connection: Mutex<Connection>,
     recorded_once: AtomicBool,
+    /// The most recently deleted record and when it was deleted, kept for undo.
+    last_deleted: Mutex<Option<(StartupRecord, u64)>>,
+}
+
+const RECORD_COLUMNS: &str =
+    "recorded_at_ms, duration_ms, launcher, battery_percent, thermal_pressure, cpu_time_ms, build_environment, measurement";
+
+/// Reads a record from a row selected with `RECORD_COLUMNS`.
+fn record_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<StartupRecord> {
+    Ok(StartupRecord {
+        recorded_at_ms: row.get::<_, i64>(0)?.max(0) as u64,
+        duration_ms: row.get::<_, i64>(1)?.max(0) as u64,
+        launcher: row
+            .get::<_, Option<String>>(2)?
+            .unwrap_or_else(|| "unknown".to_string()),
+        battery_percent: row.get(3)?,
+        thermal_pressure: row.get(4)?,
+        cpu_time_ms: row.get::<_, Option<i64>>(5)?.map(|ms| ms.max(0) as u64),
+        build_environment: row
+            .get::<_, Option<String>>(6)?
+            .unwrap_or_else(|| UNKNOWN_ENVIRONMENT.to_string()),
+        measurement: parse_measurement(row.get::<_, Option<String>>(7)?.as_deref()),
+    })
+}
+
+/// Deletes all but the newest `MAX_RECORDS` records and notes the deletes for maintenance.
+fn trim_records(connection: &Connection) -> rusqlite::Result<()> {
+    let trimmed = connection.execute(
+        "DELETE FROM startup_records
+         WHERE id NOT IN (
+             SELECT id FROM startup_records
+             ORDER BY recorded_at_ms DESC
+             LIMIT ?1
+         )",
+        params![MAX_RECORDS as i64],
+    )?;
+    maintenance::record_deletes(connection, trimmed)
+}
+
+fn insert_record(connection: &Connection, record: &StartupRecord) -> rusqlite::Result<usize> {
+    connection.execute(
+        &format!(
+            "INSERT INTO startup_records ({RECORD_COLUMNS}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)"
+        ),
+        params![
+            record.recorded_at_ms.min(i64::MAX as u64) as i64,
+            record.duration_ms.min(i64::MAX as u64) as i64,
+            record.launcher,
+            record.battery_percent,
+            record.thermal_pressure,
+            record.cpu_time_ms.map(|ms| ms.min(i64::MAX as u64) as i64),
+            record.build_environment,
+            measurement_name(record.measurement)
+        ],
+    )
 }
 
 impl StartupMetrics {
@@ -40,18 +350,28 @@ impl StartupMetrics {
             Ok(connection) => connection,
             Err(err) => {
                 eprintln!("failed to open startup metrics database: {err}");
-                let connection = Connection::open_in_memory()
-                    .expect("failed to open in-memory sqlite connection");
-                if let Err(migrate_err) = Self::migrate(&connection) {
-                    eprintln!("failed to initialize in-memory database: {migrate_err}");
-                }
-                connection
+                return Self::in_memory();
             }
         };
 
+        Self::with_connection(connection)
+    }
+
+    /// Opens a throwaway in-memory database, leaving any stored data untouched.
+    pub fn in_memory() -> Self {
+        let connection =
+            Connection::open_in_memory().expect("failed to open in-memory sqlite connection");
+        if let Err(err) = Self::migrate(&connection) {
+            eprintln!("failed to initialize in-memory database: {err}");
+        }
+        Self::with_connection(connection)
+    }
+
+    fn with_connection(connection: Connection) -> Self {
         Self {
             connection: Mutex::new(connection),
             recorded_once: AtomicBool::new(false),
+            last_deleted: Mutex::new(None),
         }
     }
 
@@ -69,21 +389,22 @@ impl StartupMetrics {
             ",
         )?;
 
-        Self::ensure_launcher_column(connection)
+        Self::ensure_launcher_column(connection)?;
+        Self::ensure_environment_columns(connection)?;
+        Self::ensure_build_environment_column(connection)?;
+        Self::ensure_measurement_column(connection)?;
+        maintenance::migrate(connection)
     }
 
-    fn ensure_launcher_column(connection: &Connection) -> rusqlite::Result<()> {
+    fn has_column(connection: &Connection, column: &str) -> rusqlite::Result<bool> {
         let mut statement = connection.prepare("PRAGMA table_info(startup_records)")?;
-        let mut has_launcher_column = false;
         let columns = statement.query_map([], |row| row.get::<_, String>(1))?;
-        for name in columns.flatten() {
-            if name == "launcher" {
-                has_launcher_column = true;
-                break;
-            }
-        }
+        let found = columns.flatten().any(|name| name == column);
+        Ok(found)
+    }
 
-        if !has_launcher_column {
+    fn ensure_launcher_column(connection: &Connection) -> rusqlite::Result<()> {
+        if !Self::has_column(connection, "launcher")? {
             connection.execute("ALTER TABLE startup_records ADD COLUMN launcher TEXT", [])?;
             connection.execute(
                 "UPDATE startup_records SET launcher = 'unknown' WHERE launcher IS NULL",
@@ -94,31 +415,122 @@ impl StartupMetrics {
         Ok(())
     }
 
+    fn ensure_environment_columns(connection: &Connection) -> rusqlite::Result<()> {
+        for column in ["battery_percent", "thermal_pressure", "cpu_time_ms"] {
+            if !Self::has_column(connection, column)? {
+                connection.execute(
+                    &format!("ALTER TABLE startup_records ADD COLUMN {column} INTEGER"),
+                    [],
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn ensure_build_environment_column(connection: &Connection) -> rusqlite::Result<()> {
+        if !Self::has_column(connection, "build_environment")? {
+            connection.execute(
+                "ALTER TABLE startup_records ADD COLUMN build_environment TEXT",
+                [],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn ensure_measurement_column(connection: &Connection) -> rusqlite::Result<()> {
+        if !Self::has_column(connection, "measurement")? {
+            connection.execute(
+                "ALTER TABLE startup_records ADD COLUMN measurement TEXT",
+                [],
+            )?;
+        }
+
+        Ok(())
+    }
+
     /// Records the startup duration once per application run and trims the table to `MAX_RECORDS`.
+    ///
+    /// Runs faster than `min_recordable_ms` are skipped, so warm relaunches stay out of the history.
     pub fn record_startup(
         &self,
         duration: Duration,
         launcher: String,
+        environment: EnvironmentSnapshot,
+        measurement: StartupMeasurement,
+        min_recordable_ms: u64,
     ) -> Result<Option<StartupRecord>, String> {
         if self.recorded_once.swap(true, Ordering::SeqCst) {
             return Ok(None);
         }
+        if duration < Duration::from_millis(min_recordable_ms) {
+            return Ok(None);
+        }
+        let launcher = normalize_launcher(&launcher);
+        let build_environment = classify_environment(cfg!(debug_assertions), &launcher);
 
-        let duration_ms_clamped = duration.as_millis().min(i64::MAX as u128);
-        let duration_ms = duration_ms_clamped as u64;
-        let recorded_at_ms_clamped = SystemTime::now()
+        let duration_ms = duration.as_millis().min(i64::MAX as u128) as u64;
+        let recorded_at_ms = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap_or_default()
             .as_millis()
-            .min(i64::MAX as u128);
-        let recorded_at_ms = recorded_at_ms_clamped as u64;
+            .min(i64::MAX as u128) as u64;
 
         let record = StartupRecord {
             recorded_at_ms,
             duration_ms,
-            launcher: launcher.clone(),
+            launcher,
+            battery_percent: environment.battery_percent,
+            thermal_pressure: environment.thermal_pressure,
+            cpu_time_ms: environment.cpu_time_ms,
+            build_environment: build_environment.to_string(),
+            measurement,
         };
 
+        let connection = self
+            .connection
+            .lock()
+            .map_err(|_| "startup metrics mutex poisoned".to_string())?;
+
+        insert_record(&connection, &record).map_err(|err| err.to_string())?;
+        trim_records(&connection).map_err(|err| err.to_string())?;
+
+        Ok(Some(record))
+    }
+
+    /// Lets the next `record_startup` of this run write again.
+    #[cfg(debug_assertions)]
+    pub fn reset_startup_guard(&self) {
+        self.recorded_once.store(false, Ordering::SeqCst);
+    }
+
+    /// Vacuums the database when its maintenance metadata says it is due.
+    pub fn run_maintenance(&self, now_ms: u64) -> Result<Option<u64>, String> {
+        let connection = self
+            .connection
+            .lock()
+            .map_err(|_| "startup metrics mutex poisoned".to_string())?;
+        maintenance::run_if_due(&connection, now_ms).map_err(|err| err.to_string())
+    }
+
+    /// Vacuums the database now, returning the bytes reclaimed.
+    pub fn vacuum(&self, now_ms: u64) -> Result<u64, String> {
+        let connection = self
+            .connection
+            .lock()
+            .map_err(|_| "startup metrics mutex poisoned".to_string())?;
+        maintenance::vacuum(&connection, now_ms).map_err(|err| err.to_string())
+    }
+
+    /// Renames every record whose launcher matches `from` ignoring case to the normalized `to`,
+    /// returning the rows changed.
+    pub fn relabel_launcher(&self, from: &str, to: &str) -> Result<usize, String> {
+        if to.trim().is_empty() {
+            return Err("launcher name must not be empty".to_string());
+        }
+        let to = normalize_launcher(to);
+
         let connection = self
             .connection
             .lock()
@@ -126,27 +538,174 @@ impl StartupMetrics {
 
         connection
             .execute(
-                "INSERT INTO startup_records (recorded_at_ms, duration_ms, launcher) VALUES (?1, ?2, ?3)",
+                "UPDATE startup_records SET launcher = ?1
+                 WHERE LOWER(COALESCE(launcher, 'unknown')) = LOWER(?2)",
+                params![to, from.trim()],
+            )
+            .map_err(|err| err.to_string())
+    }
+
+    /// Averages stored runs per launcher ignoring case, slowest first, skipping `ignored` launchers.
+    pub fn averages_by_launcher(&self, ignored: &[String]) -> Vec<LauncherAverage> {
+        let connection = match self.connection.lock() {
+            Ok(connection) => connection,
+            Err(_) => return Vec::new(),
+        };
+
+        let mut statement = match connection.prepare(
+            "SELECT MIN(COALESCE(launcher, 'unknown')), AVG(duration_ms), COUNT(*)
+             FROM startup_records
+             GROUP BY LOWER(COALESCE(launcher, 'unknown'))
+             ORDER BY AVG(duration_ms) DESC",
+        ) {
+            Ok(statement) => statement,
+            Err(err) => {
+                eprintln!("failed to read launcher averages: {err}");
+                return Vec::new();
+            }
+        };
+
+        let rows = match statement.query_map([], |row| {
+            Ok(LauncherAverage {
+                launcher: row.get(0)?,
+                average_ms: row.get::<_, f64>(1)?.max(0.0).round() as u64,
+                runs: row.get::<_, i64>(2)?.max(0) as u64,
+            })
+        }) {
+            Ok(rows) => rows,
+            Err(err) => {
+                eprintln!("failed to collect launcher averages: {err}");
+                return Vec::new();
+            }
+        };
+
+        rows.filter_map(Result::ok)
+            .filter(|average| {
+                !ignored
+                    .iter()
+                    .any(|launcher| launcher.eq_ignore_ascii_case(&average.launcher))
+            })
+            .collect()
+    }
+
+    /// Counts runs recorded between the start of the local day containing `now_ms` and `now_ms`.
+    pub fn records_today(&self, now_ms: u64, offset_minutes: i32) -> u64 {
+        let connection = match self.connection.lock() {
+            Ok(connection) => connection,
+            Err(_) => return 0,
+        };
+        let day_start = day_start_ms(now_ms, offset_minutes);
+        connection
+            .query_row(
+                "SELECT COUNT(*) FROM startup_records
+                 WHERE recorded_at_ms >= ?1 AND recorded_at_ms <= ?2",
                 params![
-                    recorded_at_ms_clamped as i64,
-                    duration_ms_clamped as i64,
-                    launcher
+                    day_start.min(i64::MAX as u64) as i64,
+                    now_ms.min(i64::MAX as u64) as i64
                 ],
+                |row| row.get::<_, i64>(0),
             )
+            .map(|count| count.max(0) as u64)
+            .unwrap_or_else(|err| {
+                eprintln!("failed to count today's startup records: {err}");
+                0
+            })
+    }
+
+    /// Returns true when a run was already recorded on the local day containing `now_ms`.
+    pub fn has_record_today(&self, now_ms: u64, offset_minutes: i32) -> bool {
+        self.records_today(now_ms, offset_minutes) > 0
+    }
+
+    /// Returns true when exactly one run has been recorded today, making the latest run the day's first.
+    pub fn is_first_record_today(&self, now_ms: u64, offset_minutes: i32) -> bool {
+        self.records_today(now_ms, offset_minutes) == 1
+    }
+
+    /// Replaces every stored startup record with `records`, keeping the newest `MAX_RECORDS`.
+    ///
+    /// The transaction commits only after `before_commit` succeeds, so callers can stage other
+    /// stores inside it and roll everything back together.
+    pub fn replace_records(
+        &self,
+        records: &[StartupRecord],
+        before_commit: impl FnOnce() -> Result<(), String>,
+    ) -> Result<(), String> {
+        let mut connection = self
+            .connection
+            .lock()
+            .map_err(|_| "startup metrics mutex poisoned".to_string())?;
+        let transaction = connection.transaction().map_err(|err| err.to_string())?;
+        transaction
+            .execute("DELETE FROM startup_records", [])
             .map_err(|err| err.to_string())?;
+        for record in records {
+            insert_record(&transaction, record).map_err(|err| err.to_string())?;
+        }
+        trim_records(&transaction).map_err(|err| err.to_string())?;
+        before_commit()?;
+        transaction.commit().map_err(|err| err.to_string())
+    }
 
-        connection
+    /// Deletes the record written at `recorded_at_ms`, keeping it so the delete can be undone.
+    ///
+    /// Returns false when no such record exists.
+    pub fn delete_record(&self, recorded_at_ms: u64, now_ms: u64) -> Result<bool, String> {
+        let connection = self
+            .connection
+            .lock()
+            .map_err(|_| "startup metrics mutex poisoned".to_string())?;
+        let recorded_at_ms = recorded_at_ms.min(i64::MAX as u64) as i64;
+        let record = match connection.query_row(
+            &format!(
+                "SELECT {RECORD_COLUMNS} FROM startup_records WHERE recorded_at_ms = ?1 LIMIT 1"
+            ),
+            params![recorded_at_ms],
+            record_from_row,
+        ) {
+            Ok(record) => record,
+            Err(rusqlite::Error::QueryReturnedNoRows) => return Ok(false),
+            Err(err) => return Err(err.to_string()),
+        };
+        let deleted = connection
             .execute(
-                "DELETE FROM startup_records
-                 WHERE id NOT IN (
-                     SELECT id FROM startup_records
-                     ORDER BY recorded_at_ms DESC
-                     LIMIT ?1
+                "DELETE FROM startup_records WHERE id = (
+                     SELECT id FROM startup_records WHERE recorded_at_ms = ?1 LIMIT 1
                  )",
-                params![MAX_RECORDS as i64],
+                params![recorded_at_ms],
             )
             .map_err(|err| err.to_string())?;
+        maintenance::record_deletes(&connection, deleted).map_err(|err| err.to_string())?;
+
+        let mut last_deleted = self
+            .last_deleted
+            .lock()
+            .map_err(|_| "startup metrics mutex poisoned".to_string())?;
+        *last_deleted = Some((record, now_ms));
+        Ok(true)
+    }
+
+    /// Re-inserts the most recently deleted record if it was deleted within `UNDO_WINDOW_MS`.
+    ///
+    /// The buffer is emptied either way, so a second undo does nothing.
+    pub fn undo_delete(&self, now_ms: u64) -> Result<Option<StartupRecord>, String> {
+        let deleted = self
+            .last_deleted
+            .lock()
+            .map_err(|_| "startup metrics mutex poisoned".to_string())?
+            .take();
+        let Some((record, deleted_at_ms)) = deleted else {
+            return Ok(None);
+        };
+        if now_ms.saturating_sub(deleted_at_ms) > UNDO_WINDOW_MS {
+            return Ok(None);
+        }
 
+        let connection = self
+            .connection
+            .lock()
+            .map_err(|_| "startup metrics mutex poisoned".to_string())?;
+        insert_record(&connection, &record).map_err(|err| err.to_string())?;
         Ok(Some(record))
     }
 
@@ -157,11 +716,9 @@ impl StartupMetrics {
             Err(_) => return Vec::new(),
         };
 
-        let mut statement = match connection.prepare(
-            "SELECT recorded_at_ms, duration_ms, launcher
-             FROM startup_records
-             ORDER BY recorded_at_ms DESC",
-        ) {
+        let mut statement = match connection.prepare(&format!(
+            "SELECT {RECORD_COLUMNS} FROM startup_records ORDER BY recorded_at_ms DESC"
+        )) {
             Ok(statement) => statement,
             Err(err) => {
                 eprintln!("failed to read startup metrics: {err}");
@@ -169,15 +726,7 @@ impl StartupMetrics {
             }
         };
 
-        let rows = match statement.query_map([], |row| {
-            Ok(StartupRecord {
-                recorded_at_ms: row.get::<_, i64>(0)?.max(0) as u64,
-                duration_ms: row.get::<_, i64>(1)?.max(0) as u64,
-                launcher: row
-                    .get::<_, Option<String>>(2)?
-                    .unwrap_or_else(|| "unknown".to_string()),
-            })
-        }) {
+        let rows = match statement.query_map([], record_from_row) {
             Ok(rows) => rows,
             Err(err) => {
                 eprintln!("failed to collect startup metrics: {err}");
@@ -195,12 +744,209 @@ pub fn fetch_startup_records(state: tauri::State<'_, StartupMetrics>) -> Vec<Sta
     state.records()
 }
 
+#[tauri::command]
+/// Tauri command deleting the startup record written at `recorded_at_ms`; the delete can be undone briefly.
+pub fn delete_startup_record(
+    state: tauri::State<'_, StartupMetrics>,
+    recorded_at_ms: u64,
+) -> Result<bool, String> {
+    state.delete_record(recorded_at_ms, system_time_to_ms(SystemTime::now()))
+}
+
+#[tauri::command]
+/// Tauri command restoring the most recently deleted startup record, returning whether one was restored.
+pub fn undo_delete_startup_record(state: tauri::State<'_, StartupMetrics>) -> Result<bool, String> {
+    state
+        .undo_delete(system_time_to_ms(SystemTime::now()))
+        .map(|restored| restored.is_some())
+}
+
+#[tauri::command]
+/// Tauri command renaming a launcher across all stored startup records.
+pub fn relabel_launcher(
+    state: tauri::State<'_, StartupMetrics>,
+    from: String,
+    to: String,
+) -> Result<usize, String> {
+    state.relabel_launcher(&from, &to)
+}
+
+#[tauri::command]
+/// Tauri command returning per-launcher startup averages without the ignored launchers.
+pub fn fetch_launcher_averages(
+    state: tauri::State<'_, StartupMetrics>,
+    preferences: tauri::State<'_, PreferencesStore>,
+) -> Vec<LauncherAverage> {
+    state.averages_by_launcher(&preferences.get().ignored_launchers)
+}
+
+#[tauri::command]
+/// Tauri command returning the startup chart points and speed buckets computed on the backend.
+pub fn fetch_chart_data(
+    state: tauri::State<'_, StartupMetrics>,
+    preferences: tauri::State<'_, PreferencesStore>,
+) -> ChartData {
+    chart_data(&state.records(), &preferences.get(), local_offset_minutes())
+}
+
+#[tauri::command]
+/// Tauri command returning the milliseconds every recorded start saved compared with the slowest one.
+pub fn fetch_time_saved(state: tauri::State<'_, StartupMetrics>) -> u64 {
+    let samples: Vec<ChartSample> = state.records().iter().map(ChartSample::from).collect();
+    time_saved_vs_worst(&samples)
+}
+
+#[tauri::command]
+/// Tauri command projecting the next startup duration from the recent trend, or `None` with too few runs.
+pub fn forecast_next_startup(state: tauri::State<'_, StartupMetrics>) -> Option<u64> {
+    let samples: Vec<ChartSample> = state.records().iter().map(ChartSample::from).collect();
+    forecast_next(&samples, FORECAST_WINDOW)
+}
+
+#[tauri::command]
+/// Tauri command returning each launcher's share of all starts, without the ignored launchers.
+pub fn fetch_launcher_distribution(
+    state: tauri::State<'_, StartupMetrics>,
+    preferences: tauri::State<'_, PreferencesStore>,
+) -> Vec<LauncherShare> {
+    launcher_distribution(&state.averages_by_launcher(&preferences.get().ignored_launchers))
+}
+
+#[tauri::command]
+/// Tauri command returning why the latest startup could not be recorded, or `None` while recording works.
+pub fn last_record_failure(failure: tauri::State<'_, RecordFailure>) -> Option<String> {
+    failure.get()
+}
+
+#[tauri::command]
+/// Tauri command ranking launchers by how much slower than average their starts are.
+pub fn launcher_slowdown_ranking(
+    state: tauri::State<'_, StartupMetrics>,
+    preferences: tauri::State<'_, PreferencesStore>,
+) -> Vec<LauncherSlowdown> {
+    let preferences = preferences.get();
+    rank_launcher_slowdowns(
+        &state.averages_by_launcher(&preferences.ignored_launchers),
+        u64::from(preferences.launcher_ranking_min_runs),
+    )
+}
+
+#[tauri::command]
+/// Tauri command returning the shareable one-line startup summary.
+pub fn startup_headline(state: tauri::State<'_, StartupMetrics>) -> String {
+    startup_headline_text(&state.records())
+}
+
+/// Allows one more startup measurement this run, so it can be re-recorded without relaunching.
+#[cfg(debug_assertions)]
+#[tauri::command]
+pub fn reset_startup_guard(
+    state: tauri::State<'_, StartupMetrics>,
+    clock: tauri::State<'_, StartupClock>,
+) {
+    state.reset_startup_guard();
+    clock.reset();
+}
+
+#[tauri::command]
+/// Tauri command reporting whether a startup was recorded earlier on the current local day.
+pub fn has_record_today(
+    state: tauri::State<'_, StartupMetrics>,
+    preferences: tauri::State<'_, PreferencesStore>,
+) -> bool {
+    let offset = local_day_offset_minutes(preferences.get().day_rollover_hour);
+    state.has_record_today(system_time_to_ms(SystemTime::now()), offset)
+}
+
+#[tauri::command]
+/// Tauri command reporting whether the latest startup is the first one of the local day.
+pub fn is_first_launch_today(
+    state: tauri::State<'_, StartupMetrics>,
+    preferences: tauri::State<'_, PreferencesStore>,
+) -> bool {
+    let offset = local_day_offset_minutes(preferences.get().day_rollover_hour);
+    state.is_first_record_today(system_time_to_ms(SystemTime::now()), offset)
+}
+
+#[tauri::command]
+/// Tauri command adding a launcher to, or removing it from, the ignored set.
+pub fn set_launcher_ignored(
+    preferences: tauri::State<'_, PreferencesStore>,
+    launcher: String,
+    ignored: bool,
+) -> Result<Preferences, String> {
+    let launcher = launcher.trim();
+    if launcher.is_empty() {
+        return Err("launcher name must not be empty".to_string());
+    }
+    let mut launchers = preferences.get().ignored_launchers;
+    launchers.retain(|existing| existing != launcher);
+    if ignored {
+        launchers.push(launcher.to_string());
+    }
+    let value = serde_json::to_value(launchers).map_err(|err| err.to_string())?;
+    preferences.set("ignored_launchers", value)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use rusqlite::{params, Connection};
     use std::time::Duration;
 
+    #[test]
+    fn startup_clock_measures_once_from_launch() {
+        let started = Instant::now();
+        let clock = StartupClock::new(started);
+        assert_eq!(
+            clock.finish(started + Duration::from_millis(850)),
+            Some(Duration::from_millis(850))
+        );
+        assert_eq!(clock.finish(started + Duration::from_millis(900)), None);
+    }
+
+    #[test]
+    fn since_boot_measures_autostart_launches_from_boot() {
+        let elapsed = Duration::from_millis(800);
+        let boot_ms = 1_000_000;
+        let ready_ms = boot_ms + 42_000;
+
+        assert_eq!(
+            startup_duration(
+                StartupMeasurement::Process,
+                elapsed,
+                true,
+                boot_ms,
+                ready_ms
+            ),
+            (elapsed, StartupMeasurement::Process)
+        );
+        assert_eq!(
+            startup_duration(
+                StartupMeasurement::SinceBoot,
+                elapsed,
+                true,
+                boot_ms,
+                ready_ms
+            ),
+            (Duration::from_millis(42_000), StartupMeasurement::SinceBoot)
+        );
+        assert_eq!(
+            startup_duration(
+                StartupMeasurement::SinceBoot,
+                elapsed,
+                false,
+                boot_ms,
+                ready_ms
+            ),
+            (elapsed, StartupMeasurement::Process)
+        );
+        assert_eq!(
+            startup_duration(StartupMeasurement::SinceBoot, elapsed, true, 0, ready_ms),
+            (elapsed, StartupMeasurement::Process)
+        );
+    }
+
     #[test]
     fn records_are_trimmed_to_maximum() {
         let dir = tempfile::tempdir().unwrap();
@@ -218,7 +964,13 @@ mod tests {
         }
 
         metrics
-            .record_startup(Duration::from_millis(10), "test".to_string())
+            .record_startup(
+                Duration::from_millis(10),
+                "test".to_string(),
+                EnvironmentSnapshot::default(),
+                StartupMeasurement::Process,
+                0,
+            )
             .unwrap();
 
         let records = metrics.records();
@@ -230,6 +982,74 @@ mod tests {
         assert_eq!(count as usize, MAX_RECORDS);
     }
 
+    #[test]
+    fn replacing_records_trims_and_waits_for_before_commit() {
+        let dir = tempfile::tempdir().unwrap();
+        let metrics = StartupMetrics::with_storage_path(dir.path().join("records.sqlite"));
+        let records: Vec<StartupRecord> = (0..MAX_RECORDS as u64 + 5)
+            .map(|recorded_at_ms| StartupRecord {
+                recorded_at_ms,
+                duration_ms: 10,
+                launcher: "Dock".to_string(),
+                battery_percent: None,
+                thermal_pressure: None,
+                cpu_time_ms: None,
+                build_environment: "release".to_string(),
+                measurement: StartupMeasurement::Process,
+            })
+            .collect();
+
+        assert!(metrics
+            .replace_records(&records, || Err("staged store failed".to_string()))
+            .is_err());
+        assert!(metrics.records().is_empty());
+
+        metrics.replace_records(&records, || Ok(())).unwrap();
+        let stored = metrics.records();
+        assert_eq!(stored.len(), MAX_RECORDS);
+        assert_eq!(stored[0].recorded_at_ms, MAX_RECORDS as u64 + 4);
+        assert_eq!(stored[MAX_RECORDS - 1].recorded_at_ms, 5);
+    }
+
+    #[test]
+    fn deleted_records_can_be_restored_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let metrics = StartupMetrics::with_storage_path(dir.path().join("records.sqlite"));
+        let record = metrics
+            .record_startup(
+                Duration::from_millis(420),
+                "test".to_string(),
+                EnvironmentSnapshot::default(),
+                StartupMeasurement::Process,
+                0,
+            )
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            metrics.delete_record(record.recorded_at_ms, 1_000),
+            Ok(true)
+        );
+        assert!(metrics.records().is_empty());
+
+        // The run was already recorded, yet undo still re-inserts the row.
+        assert_eq!(metrics.undo_delete(5_000), Ok(Some(record.clone())));
+        assert_eq!(metrics.records(), vec![record.clone()]);
+        assert_eq!(metrics.undo_delete(5_000), Ok(None));
+        assert_eq!(metrics.records().len(), 1);
+
+        assert_eq!(
+            metrics.delete_record(record.recorded_at_ms, 1_000),
+            Ok(true)
+        );
+        assert_eq!(metrics.undo_delete(1_000 + UNDO_WINDOW_MS + 1), Ok(None));
+        assert!(metrics.records().is_empty());
+        assert_eq!(
+            metrics.delete_record(record.recorded_at_ms, 1_000),
+            Ok(false)
+        );
+    }
+
     #[test]
     fn records_only_once_per_run() {
         let dir = tempfile::tempdir().unwrap();
@@ -237,12 +1057,485 @@ mod tests {
         let metrics = StartupMetrics::with_storage_path(storage_path);
 
         assert!(metrics
-            .record_startup(Duration::from_millis(5), "test".to_string())
+            .record_startup(
+                Duration::from_millis(5),
+                "test".to_string(),
+                EnvironmentSnapshot::default(),
+                StartupMeasurement::Process,
+                0,
+            )
             .unwrap()
             .is_some());
         assert!(metrics
-            .record_startup(Duration::from_millis(5), "test".to_string())
+            .record_startup(
+                Duration::from_millis(5),
+                "test".to_string(),
+                EnvironmentSnapshot::default(),
+                StartupMeasurement::Process,
+                0,
+            )
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn startups_below_the_floor_are_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        let skipped = StartupMetrics::with_storage_path(dir.path().join("skipped.sqlite"));
+        assert!(skipped
+            .record_startup(
+                Duration::from_millis(30),
+                "test".to_string(),
+                EnvironmentSnapshot::default(),
+                StartupMeasurement::Process,
+                50,
+            )
             .unwrap()
             .is_none());
+        assert!(skipped.records().is_empty());
+
+        let stored = StartupMetrics::with_storage_path(dir.path().join("stored.sqlite"));
+        assert!(stored
+            .record_startup(
+                Duration::from_millis(80),
+                "test".to_string(),
+                EnvironmentSnapshot::default(),
+                StartupMeasurement::Process,
+                50,
+            )
+            .unwrap()
+            .is_some());
+        assert_eq!(stored.records()[0].duration_ms, 80);
+    }
+
+    #[test]
+    fn today_checks_only_count_runs_since_the_local_day_start() {
+        const DAY_MS: u64 = 24 * 60 * 60 * 1_000;
+        let dir = tempfile::tempdir().unwrap();
+        let storage_path = dir.path().join("records.sqlite");
+        let metrics = StartupMetrics::with_storage_path(storage_path.clone());
+
+        let seed_connection = Connection::open(&storage_path).unwrap();
+        for recorded_at_ms in [DAY_MS + 60_000, 3 * DAY_MS + 60_000, 3 * DAY_MS + 120_000] {
+            seed_connection
+                .execute(
+                    "INSERT INTO startup_records (recorded_at_ms, duration_ms, launcher) VALUES (?1, ?2, ?3)",
+                    params![recorded_at_ms as i64, 10i64, "seed"],
+                )
+                .unwrap();
+        }
+
+        assert!(metrics.has_record_today(DAY_MS + 3_600_000, 0));
+        assert!(metrics.is_first_record_today(DAY_MS + 3_600_000, 0));
+        assert!(!metrics.has_record_today(2 * DAY_MS + 3_600_000, 0));
+        assert!(metrics.has_record_today(3 * DAY_MS + 3_600_000, 0));
+        assert!(!metrics.is_first_record_today(3 * DAY_MS + 3_600_000, 0));
+
+        // Two hours ahead of UTC, 23:00 UTC is already the next local day.
+        assert!(!metrics.has_record_today(3 * DAY_MS + 23 * 3_600_000, 120));
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    fn resetting_the_guard_allows_another_record() {
+        let dir = tempfile::tempdir().unwrap();
+        let metrics = StartupMetrics::with_storage_path(dir.path().join("records.sqlite"));
+        let record = || {
+            metrics
+                .record_startup(
+                    Duration::from_millis(5),
+                    "test".to_string(),
+                    EnvironmentSnapshot::default(),
+                    StartupMeasurement::Process,
+                    0,
+                )
+                .unwrap()
+        };
+
+        assert!(record().is_some());
+        assert!(record().is_none());
+        metrics.reset_startup_guard();
+        assert!(record().is_some());
+        assert!(record().is_none());
+    }
+
+    #[test]
+    fn relabel_launcher_updates_matching_rows_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage_path = dir.path().join("records.sqlite");
+        let metrics = StartupMetrics::with_storage_path(storage_path.clone());
+
+        let seed_connection = Connection::open(&storage_path).unwrap();
+        for (index, launcher) in ["unknown", "Unknown", "Finder", "unknown"]
+            .iter()
+            .enumerate()
+        {
+            seed_connection
+                .execute(
+                    "INSERT INTO startup_records (recorded_at_ms, duration_ms, launcher) VALUES (?1, ?2, ?3)",
+                    params![index as i64, 10i64, launcher],
+                )
+                .unwrap();
+        }
+
+        assert_eq!(
+            metrics
+                .relabel_launcher("unknown", " Dock   Menu ")
+                .unwrap(),
+            3
+        );
+
+        let launchers: Vec<_> = metrics
+            .records()
+            .into_iter()
+            .map(|record| record.launcher)
+            .collect();
+        assert_eq!(
+            launchers,
+            vec!["Dock Menu", "Finder", "Dock Menu", "Dock Menu"]
+        );
+    }
+
+    #[test]
+    fn launchers_differing_only_in_case_share_an_average() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage_path = dir.path().join("records.sqlite");
+        let metrics = StartupMetrics::with_storage_path(storage_path.clone());
+
+        let seed_connection = Connection::open(&storage_path).unwrap();
+        for (index, launcher) in ["Dock", "dock"].iter().enumerate() {
+            seed_connection
+                .execute(
+                    "INSERT INTO startup_records (recorded_at_ms, duration_ms, launcher) VALUES (?1, ?2, ?3)",
+                    params![index as i64, 100i64 + index as i64 * 100, launcher],
+                )
+                .unwrap();
+        }
+
+        assert_eq!(
+            metrics.averages_by_launcher(&[]),
+            vec![LauncherAverage {
+                launcher: "Dock".to_string(),
+                average_ms: 150,
+                runs: 2,
+            }]
+        );
+        assert!(metrics
+            .averages_by_launcher(&["DOCK".to_string()])
+            .is_empty());
+    }
+
+    #[test]
+    fn slowest_launcher_ranks_first_and_rare_launchers_are_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage_path = dir.path().join("records.sqlite");
+        let metrics = StartupMetrics::with_storage_path(storage_path.clone());
+
+        let seed_connection = Connection::open(&storage_path).unwrap();
+        let seeds = [
+            ("Finder", 500),
+            ("Finder", 600),
+            ("Finder", 400),
+            ("Terminal", 2_000),
+            ("Terminal", 2_400),
+            ("Terminal", 1_600),
+            ("Dock", 700),
+            ("Dock", 500),
+            ("Dock", 600),
+            ("Spotlight", 9_000),
+        ];
+        for (index, (launcher, duration_ms)) in seeds.iter().enumerate() {
+            seed_connection
+                .execute(
+                    "INSERT INTO startup_records (recorded_at_ms, duration_ms, launcher) VALUES (?1, ?2, ?3)",
+                    params![index as i64, *duration_ms as i64, launcher],
+                )
+                .unwrap();
+        }
+
+        // Overall average: 18 300 ms over 10 runs.
+        let ranking = rank_launcher_slowdowns(&metrics.averages_by_launcher(&[]), 3);
+        assert_eq!(
+            ranking,
+            vec![
+                LauncherSlowdown {
+                    launcher: "Terminal".to_string(),
+                    avg_ms: 2_000,
+                    count: 3,
+                    delta_vs_overall_ms: 170,
+                },
+                LauncherSlowdown {
+                    launcher: "Dock".to_string(),
+                    avg_ms: 600,
+                    count: 3,
+                    delta_vs_overall_ms: -1_230,
+                },
+                LauncherSlowdown {
+                    launcher: "Finder".to_string(),
+                    avg_ms: 500,
+                    count: 3,
+                    delta_vs_overall_ms: -1_330,
+                },
+            ]
+        );
+        assert!(rank_launcher_slowdowns(&[], 3).is_empty());
+    }
+
+    #[test]
+    fn averages_by_launcher_skip_ignored_launchers_but_records_keep_them() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage_path = dir.path().join("records.sqlite");
+        let metrics = StartupMetrics::with_storage_path(storage_path.clone());
+
+        let seed_connection = Connection::open(&storage_path).unwrap();
+        for (index, (launcher, duration_ms)) in [
+            ("Finder", 400),
+            ("bash", 90),
+            ("Finder", 600),
+            ("Dock", 800),
+        ]
+        .iter()
+        .enumerate()
+        {
+            seed_connection
+                .execute(
+                    "INSERT INTO startup_records (recorded_at_ms, duration_ms, launcher) VALUES (?1, ?2, ?3)",
+                    params![index as i64, *duration_ms as i64, launcher],
+                )
+                .unwrap();
+        }
+
+        let averages = metrics.averages_by_launcher(&["bash".to_string()]);
+        assert_eq!(
+            averages,
+            vec![
+                LauncherAverage {
+                    launcher: "Dock".to_string(),
+                    average_ms: 800,
+                    runs: 1,
+                },
+                LauncherAverage {
+                    launcher: "Finder".to_string(),
+                    average_ms: 500,
+                    runs: 2,
+                },
+            ]
+        );
+
+        let launchers: Vec<_> = metrics
+            .records()
+            .into_iter()
+            .map(|record| record.launcher)
+            .collect();
+        assert_eq!(launchers, vec!["Dock", "Finder", "bash", "Finder"]);
+    }
+
+    #[test]
+    fn record_failure_is_kept_until_a_record_is_written() {
+        let dir = tempfile::tempdir().unwrap();
+        let metrics = StartupMetrics::with_storage_path(dir.path().join("records.sqlite"));
+        let failure = RecordFailure::default();
+
+        failure.track(&Err("database is locked".to_string()));
+        assert_eq!(failure.get().as_deref(), Some("database is locked"));
+
+        // A skipped run says nothing about whether writing works again.
+        failure.track(&Ok(None));
+        assert_eq!(failure.get().as_deref(), Some("database is locked"));
+
+        failure.track(&metrics.record_startup(
+            Duration::from_millis(400),
+            "Dock".to_string(),
+            EnvironmentSnapshot::default(),
+            StartupMeasurement::Process,
+            0,
+        ));
+        assert_eq!(failure.get(), None);
+    }
+
+    #[test]
+    fn launcher_distribution_turns_runs_into_fractions() {
+        let average = |launcher: &str, runs| LauncherAverage {
+            launcher: launcher.to_string(),
+            average_ms: 500,
+            runs,
+        };
+        let shares = launcher_distribution(&[
+            average("Dock", 1),
+            average("Finder", 2),
+            average("Terminal", 1),
+        ]);
+
+        let summary: Vec<_> = shares
+            .iter()
+            .map(|share| (share.launcher.as_str(), share.count, share.fraction))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![("Finder", 2, 0.5), ("Dock", 1, 0.25), ("Terminal", 1, 0.25)]
+        );
+        let total: f64 = shares.iter().map(|share| share.fraction).sum();
+        assert!((total - 1.0).abs() < f64::EPSILON);
+        assert!(launcher_distribution(&[]).is_empty());
+    }
+
+    #[test]
+    fn launcher_distribution_groups_case_and_skips_ignored_launchers() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage_path = dir.path().join("records.sqlite");
+        let metrics = StartupMetrics::with_storage_path(storage_path.clone());
+
+        let seed_connection = Connection::open(&storage_path).unwrap();
+        for (index, launcher) in [Some("Finder"), Some("finder"), Some("bash"), None]
+            .iter()
+            .enumerate()
+        {
+            seed_connection
+                .execute(
+                    "INSERT INTO startup_records (recorded_at_ms, duration_ms, launcher) VALUES (?1, 500, ?2)",
+                    params![index as i64, launcher],
+                )
+                .unwrap();
+        }
+
+        let shares = launcher_distribution(&metrics.averages_by_launcher(&["bash".to_string()]));
+        let counts: Vec<_> = shares
+            .iter()
+            .map(|share| (share.launcher.as_str(), share.count))
+            .collect();
+        assert_eq!(counts, vec![("Finder", 2), ("unknown", 1)]);
+    }
+
+    #[test]
+    fn relabel_launcher_rejects_empty_target() {
+        let dir = tempfile::tempdir().unwrap();
+        let metrics = StartupMetrics::with_storage_path(dir.path().join("records.sqlite"));
+
+        assert!(metrics.relabel_launcher("unknown", "   ").is_err());
+    }
+
+    #[test]
+    fn environment_columns_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let metrics = StartupMetrics::with_storage_path(dir.path().join("records.sqlite"));
+
+        metrics
+            .record_startup(
+                Duration::from_millis(2_400),
+                "Dock".to_string(),
+                EnvironmentSnapshot {
+                    battery_percent: Some(14),
+                    thermal_pressure: Some(true),
+                    cpu_time_ms: Some(1_800),
+                },
+                StartupMeasurement::SinceBoot,
+                0,
+            )
+            .unwrap();
+
+        let records = metrics.records();
+        assert_eq!(records[0].battery_percent, Some(14));
+        assert_eq!(records[0].thermal_pressure, Some(true));
+        assert_eq!(records[0].cpu_time_ms, Some(1_800));
+        assert_eq!(
+            records[0].build_environment,
+            classify_environment(cfg!(debug_assertions), "Dock")
+        );
+        assert_eq!(records[0].measurement, StartupMeasurement::SinceBoot);
+    }
+
+    #[test]
+    fn legacy_rows_read_missing_environment_as_none() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage_path = dir.path().join("records.sqlite");
+
+        let legacy = Connection::open(&storage_path).unwrap();
+        legacy
+            .execute_batch(
+                "CREATE TABLE startup_records (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    recorded_at_ms INTEGER NOT NULL,
+                    duration_ms INTEGER NOT NULL,
+                    launcher TEXT
+                );
+                INSERT INTO startup_records (recorded_at_ms, duration_ms, launcher)
+                VALUES (1, 800, 'Finder');",
+            )
+            .unwrap();
+        drop(legacy);
+
+        let records = StartupMetrics::with_storage_path(storage_path).records();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].battery_percent, None);
+        assert_eq!(records[0].thermal_pressure, None);
+        assert_eq!(records[0].cpu_time_ms, None);
+        assert_eq!(records[0].build_environment, UNKNOWN_ENVIRONMENT);
+        assert_eq!(records[0].measurement, StartupMeasurement::Process);
+    }
+
+    #[test]
+    fn chart_data_aggregates_records_with_local_labels() {
+        let records: Vec<StartupRecord> = [(3_600_000, 400), (0, 2_000)]
+            .into_iter()
+            .map(|(recorded_at_ms, duration_ms)| StartupRecord {
+                recorded_at_ms,
+                duration_ms,
+                launcher: "Dock".to_string(),
+                battery_percent: None,
+                thermal_pressure: None,
+                cpu_time_ms: None,
+                build_environment: "release".to_string(),
+                measurement: StartupMeasurement::Process,
+            })
+            .collect();
+        let preferences = Preferences {
+            recent_chart_bars: 3,
+            distribution_chart_bars: 2,
+            ..Preferences::default()
+        };
+
+        let data = chart_data(&records, &preferences, 120);
+        let labels: Vec<_> = data
+            .recent
+            .iter()
+            .map(|point| point.label.as_str())
+            .collect();
+        assert_eq!(labels, vec!["-", "02:00:00", "03:00:00"]);
+        assert_eq!(
+            data.distribution
+                .iter()
+                .map(|point| point.value)
+                .collect::<Vec<_>>(),
+            vec![1, 1]
+        );
+        assert_eq!(data.categories.fast.runs, 1);
+        assert_eq!(data.categories.slow.total_ms, 2_000);
+    }
+
+    #[test]
+    fn startup_headline_summarises_average_run_count_and_best() {
+        let records: Vec<StartupRecord> = [420, 310, 530]
+            .into_iter()
+            .enumerate()
+            .map(|(index, duration_ms)| StartupRecord {
+                recorded_at_ms: index as u64,
+                duration_ms,
+                launcher: "Dock".to_string(),
+                battery_percent: None,
+                thermal_pressure: None,
+                cpu_time_ms: None,
+                build_environment: "release".to_string(),
+                measurement: StartupMeasurement::Process,
+            })
+            .collect();
+        assert_eq!(
+            startup_headline_text(&records),
+            "Time Wise startup: 420 ms avg over 3 runs (best 310 ms)"
+        );
+        assert_eq!(
+            startup_headline_text(&records[..1]),
+            "Time Wise startup: 420 ms avg over 1 run (best 420 ms)"
+        );
+        assert_eq!(startup_headline_text(&[]), "No startup data yet");
     }
 }
@@ -0,0 +1,193 @@
+//! A 0–100 steady-use score for the day, derived from how long the most recently opened app stays
+//! the newest one and how often another app is opened.
+//!
+//! The tracker only sees running processes, not the focused window, so opening an app is the
+//! closest signal it has to switching tasks.
+
+use std::time::SystemTime;
+
+use crate::app_usage::{system_time_to_ms, AppUsageRecorder};
+use crate::calendar::{day_offset_minutes, day_start_ms};
+use crate::preferences::PreferencesStore;
+use crate::usage_history::{UsageHistory, UsageSession};
+
+/// Shortest run that counts as steady use.
+const STEADY_RUN_MS: u64 = 25 * 60 * 1_000;
+/// Points taken off per newly opened app per hour of tracked time.
+const SWITCH_PENALTY_PER_HOUR: u64 = 5;
+/// Largest penalty opening apps can cost, so a steady day is never wiped out entirely.
+const MAX_SWITCH_PENALTY: u64 = 50;
+
+const HOUR_MS: u64 = 60 * 60 * 1_000;
+
+/// Stretch of time during which one app stayed the most recently opened running app.
+struct NewestAppRun<'a> {
+    name: &'a str,
+    start_ms: u64,
+    end_ms: u64,
+}
+
+/// Splits `[day_start_ms, now_ms)` into runs of the most recently started open app.
+///
+/// Apps left running behind the newest one do not interrupt it, so overlapping sessions only switch when a newer one starts.
+fn newest_app_runs(
+    sessions: &[UsageSession],
+    day_start_ms: u64,
+    now_ms: u64,
+) -> Vec<NewestAppRun<'_>> {
+    let clipped: Vec<(&str, u64, u64)> = sessions
+        .iter()
+        .filter(|session| session.overlap_ms(day_start_ms, now_ms) > 0)
+        .map(|session| {
+            (
+                session.name.as_str(),
+                session.start_ms.max(day_start_ms),
+                session.end_ms.min(now_ms),
+            )
+        })
+        .collect();
+    let mut boundaries: Vec<u64> = clipped
+        .iter()
+        .flat_map(|(_, start_ms, end_ms)| [*start_ms, *end_ms])
+        .collect();
+    boundaries.sort_unstable();
+    boundaries.dedup();
+
+    let mut runs: Vec<NewestAppRun<'_>> = Vec::new();
+    for window in boundaries.windows(2) {
+        let (from_ms, to_ms) = (window[0], window[1]);
+        let active = clipped
+            .iter()
+            .filter(|(_, start_ms, end_ms)| *start_ms <= from_ms && *end_ms >= to_ms)
+            .fold(
+                None,
+                |active: Option<&(&str, u64, u64)>, session| match active {
+                    Some(current) if current.1 >= session.1 => Some(current),
+                    _ => Some(session),
+                },
+            );
+        let Some((name, _, _)) = active else {
+            continue;
+        };
+        match runs.last_mut() {
+            Some(run) if run.name == *name && run.end_ms == from_ms => run.end_ms = to_ms,
+            _ => runs.push(NewestAppRun {
+                name,
+                start_ms: from_ms,
+                end_ms: to_ms,
+            }),
+        }
+    }
+    runs
+}
+
+/// Scores `[day_start_ms, now_ms)`: the share of time spent in long newest-app runs, minus a penalty for opening apps often.
+pub fn steady_use_score(sessions: &[UsageSession], day_start_ms: u64, now_ms: u64) -> u8 {
+    let runs = newest_app_runs(sessions, day_start_ms, now_ms);
+    let total_ms: u64 = runs.iter().map(|run| run.end_ms - run.start_ms).sum();
+    if total_ms == 0 {
+        return 0;
+    }
+
+    let steady_ms: u64 = runs
+        .iter()
+        .map(|run| run.end_ms - run.start_ms)
+        .filter(|duration| *duration >= STEADY_RUN_MS)
+        .sum();
+    let base = steady_ms.saturating_mul(100) / total_ms;
+
+    let switches = runs
+        .windows(2)
+        .filter(|pair| pair[0].name != pair[1].name)
+        .count() as u64;
+    let penalty = (switches * SWITCH_PENALTY_PER_HOUR * HOUR_MS / total_ms).min(MAX_SWITCH_PENALTY);
+
+    base.saturating_sub(penalty).min(100) as u8
+}
+
+#[tauri::command]
+/// Tauri command returning today's steady-use score from stored and open sessions.
+///
+/// `tz_offset` is the caller's UTC offset in minutes; the day still starts at the rollover hour.
+pub fn fetch_focus_score(
+    history: tauri::State<'_, UsageHistory>,
+    recorder: tauri::State<'_, AppUsageRecorder>,
+    preferences: tauri::State<'_, PreferencesStore>,
+    tz_offset: i32,
+) -> u8 {
+    let now_ms = system_time_to_ms(SystemTime::now());
+    let today = day_start_ms(
+        now_ms,
+        day_offset_minutes(tz_offset, preferences.get().day_rollover_hour),
+    );
+    let mut sessions = history.sessions_between(today, now_ms);
+    sessions.extend(recorder.open_sessions());
+    steady_use_score(&sessions, today, now_ms)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MINUTE_MS: u64 = 60 * 1_000;
+
+    fn sessions(durations_minutes: &[u64]) -> Vec<UsageSession> {
+        let mut start_ms = 0;
+        durations_minutes
+            .iter()
+            .enumerate()
+            .map(|(index, minutes)| {
+                let session = UsageSession {
+                    name: format!("App {index}"),
+                    executable: None,
+                    start_ms,
+                    end_ms: start_ms + minutes * MINUTE_MS,
+                };
+                start_ms = session.end_ms;
+                session
+            })
+            .collect()
+    }
+
+    #[test]
+    fn a_few_long_sessions_beat_many_short_switches() {
+        let focused = sessions(&[90, 60, 45]);
+        let scattered = sessions(&[5; 39]);
+        let end_ms = 8 * HOUR_MS;
+
+        let focused_score = steady_use_score(&focused, 0, end_ms);
+        let scattered_score = steady_use_score(&scattered, 0, end_ms);
+        assert!(focused_score > scattered_score);
+        assert_eq!(focused_score, 97);
+        assert_eq!(scattered_score, 0);
+    }
+
+    #[test]
+    fn overlapping_long_sessions_are_not_switches() {
+        let session = |name: &str| UsageSession {
+            name: name.to_string(),
+            executable: None,
+            start_ms: 0,
+            end_ms: 2 * HOUR_MS,
+        };
+
+        assert_eq!(
+            steady_use_score(&[session("Editor"), session("Music")], 0, 2 * HOUR_MS),
+            100
+        );
+    }
+
+    #[test]
+    fn returning_to_the_same_app_is_not_a_switch() {
+        let mut same_app = sessions(&[30, 30]);
+        same_app[1].name = same_app[0].name.clone();
+
+        assert_eq!(steady_use_score(&same_app, 0, HOUR_MS), 100);
+    }
+
+    #[test]
+    fn empty_day_scores_zero() {
+        assert_eq!(steady_use_score(&[], 0, HOUR_MS), 0);
+        assert_eq!(steady_use_score(&sessions(&[30]), HOUR_MS, 2 * HOUR_MS), 0);
+    }
+}
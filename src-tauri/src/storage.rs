@@ -0,0 +1,111 @@
+//! Checks that a directory can hold the app's databases before it is used for storage.
+
+use std::fs;
+use std::path::Path;
+
+/// Name of the throwaway file written to prove a directory is writable.
+const PROBE_FILE_NAME: &str = ".time-wise-write-check";
+
+/// Creates `path` when missing and confirms a file can be written inside it.
+pub fn ensure_writable_dir(path: &Path) -> Result<(), String> {
+    if path.as_os_str().is_empty() {
+        return Err("data folder must not be empty".to_string());
+    }
+    if path.exists() && !path.is_dir() {
+        return Err(format!("{} is not a folder", path.display()));
+    }
+    fs::create_dir_all(path).map_err(|err| format!("cannot create {}: {err}", path.display()))?;
+    probe_write(path)
+}
+
+/// Confirms `path` could hold the databases without creating it: `path`, or its nearest existing
+/// ancestor when it is missing, must be a writable folder.
+pub fn check_writable_dir(path: &Path) -> Result<(), String> {
+    if path.as_os_str().is_empty() {
+        return Err("data folder must not be empty".to_string());
+    }
+    let existing = path
+        .ancestors()
+        .find(|ancestor| !ancestor.as_os_str().is_empty() && ancestor.exists())
+        .ok_or_else(|| format!("no folder of {} exists", path.display()))?;
+    if !existing.is_dir() {
+        return Err(format!("{} is not a folder", existing.display()));
+    }
+    probe_write(existing)
+}
+
+/// Writes and removes a throwaway file in `dir`.
+fn probe_write(dir: &Path) -> Result<(), String> {
+    let probe = dir.join(PROBE_FILE_NAME);
+    fs::write(&probe, b"").map_err(|err| format!("cannot write to {}: {err}", dir.display()))?;
+    if let Err(err) = fs::remove_file(&probe) {
+        eprintln!("failed to remove write check file: {err}");
+    }
+    Ok(())
+}
+
+#[tauri::command]
+/// Tauri command checking that `path` is, or could be created as, a writable folder; creates nothing.
+pub fn validate_data_dir(path: String) -> Result<(), String> {
+    check_writable_dir(Path::new(path.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writable_dir_passes_and_leaves_no_probe_behind() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(ensure_writable_dir(dir.path()), Ok(()));
+        assert!(!dir.path().join(PROBE_FILE_NAME).exists());
+    }
+
+    #[test]
+    fn missing_dir_is_created() {
+        let dir = tempfile::tempdir().unwrap();
+        let nested = dir.path().join("data").join("time-wise");
+        assert_eq!(ensure_writable_dir(&nested), Ok(()));
+        assert!(nested.is_dir());
+    }
+
+    #[test]
+    fn checking_a_missing_dir_creates_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let nested = dir.path().join("data").join("time-wise");
+        assert_eq!(check_writable_dir(&nested), Ok(()));
+        assert!(!dir.path().join("data").exists());
+        assert!(!dir.path().join(PROBE_FILE_NAME).exists());
+    }
+
+    #[test]
+    fn paths_that_cannot_hold_files_are_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("file");
+        fs::write(&file, b"").unwrap();
+        assert!(ensure_writable_dir(&file).is_err());
+        assert!(ensure_writable_dir(&file.join("data")).is_err());
+        assert!(ensure_writable_dir(Path::new("")).is_err());
+        assert!(check_writable_dir(&file).is_err());
+        assert!(check_writable_dir(&file.join("data")).is_err());
+        assert!(check_writable_dir(Path::new("")).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn read_only_dir_is_rejected() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let read_only = dir.path().join("read-only");
+        fs::create_dir(&read_only).unwrap();
+        fs::set_permissions(&read_only, fs::Permissions::from_mode(0o555)).unwrap();
+
+        // Root ignores permission bits, so only assert where they are enforced.
+        if fs::write(read_only.join("probe"), b"").is_err() {
+            assert!(ensure_writable_dir(&read_only).is_err());
+            assert!(check_writable_dir(&read_only.join("data")).is_err());
+        }
+        fs::set_permissions(&read_only, fs::Permissions::from_mode(0o755)).unwrap();
+    }
+}
@@ -0,0 +1,104 @@
+//! Pausing and resuming usage tracking, with optional audible confirmation.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use tauri::{AppHandle, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::app_usage::AppUsageRecorder;
+use crate::error_log::log_error;
+use crate::preferences::PreferencesStore;
+
+/// Whether the poll loop is currently recording running apps.
+#[derive(Default)]
+pub struct TrackingState {
+    paused: AtomicBool,
+}
+
+impl TrackingState {
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+
+    /// Stores the new state and reports whether it differs from the previous one.
+    fn set_paused(&self, paused: bool) -> bool {
+        self.paused.swap(paused, Ordering::SeqCst) != paused
+    }
+}
+
+/// Sound feedback only plays when the user opted in and tracking actually changed state.
+pub fn should_play_sound(enabled: bool, changed: bool) -> bool {
+    enabled && changed
+}
+
+/// Tray label offering the opposite of the current tracking state.
+pub fn tracking_menu_label(paused: bool) -> &'static str {
+    if paused {
+        "Resume Tracking"
+    } else {
+        "Pause Tracking"
+    }
+}
+
+/// Flips tracking between paused and running and returns the new paused state.
+pub fn toggle_tracking(app: &AppHandle) -> bool {
+    let paused = !app.state::<TrackingState>().is_paused();
+    set_tracking_paused(app, paused);
+    paused
+}
+
+fn set_tracking_paused(app: &AppHandle, paused: bool) {
+    let changed = app.state::<TrackingState>().set_paused(paused);
+    if changed && paused {
+        if let Err(err) = app.state::<AppUsageRecorder>().close_open_sessions() {
+            log_error(
+                app,
+                &format!("failed to close usage sessions on pause: {err}"),
+            );
+        }
+    }
+
+    let enabled = app.state::<PreferencesStore>().get().sound_feedback;
+    if !should_play_sound(enabled, changed) {
+        return;
+    }
+
+    let body = if paused {
+        "Tracking paused"
+    } else {
+        "Tracking resumed"
+    };
+    if let Err(err) = app
+        .notification()
+        .builder()
+        .title("Time Wise")
+        .body(body)
+        .sound("default")
+        .show()
+    {
+        eprintln!("failed to play tracking sound: {err}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sound_plays_only_when_enabled_and_state_changed() {
+        assert!(should_play_sound(true, true));
+        assert!(!should_play_sound(true, false));
+        assert!(!should_play_sound(false, true));
+        assert!(!should_play_sound(false, false));
+    }
+
+    #[test]
+    fn set_paused_reports_state_changes() {
+        let state = TrackingState::default();
+        assert!(!state.set_paused(false));
+        assert!(state.set_paused(true));
+        assert!(state.is_paused());
+        assert!(!state.set_paused(true));
+        assert!(state.set_paused(false));
+    }
+}
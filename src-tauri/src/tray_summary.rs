@@ -0,0 +1,147 @@
+//! Keeps the tray tooltip and its top-apps menu entries in sync with today's usage.
+
+use std::time::SystemTime;
+
+use tauri::{menu::MenuItem, AppHandle, Manager, Wry};
+use time_wise_shared::usage_rounding::{round_ms, UsageRounding};
+
+use crate::app_usage::{system_time_to_ms, AppUsageRecorder};
+use crate::glance::{today_glance, TodayGlance};
+use crate::preferences::PreferencesStore;
+use crate::startup_metrics::StartupMetrics;
+use crate::usage_history::UsageHistory;
+
+/// Id of the tray icon, used to update its tooltip after it is built.
+pub const TRAY_ICON_ID: &str = "main";
+
+/// How many apps the tray menu lists under "Top apps today".
+pub const TRAY_TOP_APP_COUNT: usize = 3;
+
+/// Text shown in an empty top-app slot.
+pub const EMPTY_TOP_APP_LABEL: &str = "—";
+
+/// Menu entries rewritten with today's top apps on every poll.
+pub struct TrayTopApps {
+    pub items: Vec<MenuItem<Wry>>,
+}
+
+/// Formats tracked time as "1h 5m" or "12m".
+pub(crate) fn format_tray_duration(ms: u64) -> String {
+    let minutes = ms / 60_000;
+    match (minutes / 60, minutes % 60) {
+        (0, minutes) => format!("{minutes}m"),
+        (hours, 0) => format!("{hours}h"),
+        (hours, minutes) => format!("{hours}h {minutes}m"),
+    }
+}
+
+fn top_app_label(name: &str, ms: u64, rounding: UsageRounding) -> String {
+    format!("{name} · {}", format_tray_duration(round_ms(ms, rounding)))
+}
+
+/// One line with today's launch count and tracked time.
+fn glance_line(glance: &TodayGlance, rounding: UsageRounding) -> String {
+    let launches = if glance.launches_today == 1 {
+        "launch"
+    } else {
+        "launches"
+    };
+    format!(
+        "Today: {} {launches} · {}",
+        glance.launches_today,
+        format_tray_duration(round_ms(glance.usage_today_ms, rounding))
+    )
+}
+
+/// Tooltip with today's glance line and top apps under the app name, with durations rounded for display.
+pub fn tray_tooltip(
+    glance: &TodayGlance,
+    top_apps: &[(String, u64)],
+    rounding: UsageRounding,
+) -> String {
+    ["Time Wise".to_string(), glance_line(glance, rounding)]
+        .into_iter()
+        .chain(
+            top_apps
+                .iter()
+                .map(|(name, ms)| top_app_label(name, *ms, rounding)),
+        )
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Rewrites the tray tooltip and top-app menu entries from today's usage.
+pub fn refresh_tray_summary(app: &AppHandle) {
+    let now_ms = system_time_to_ms(SystemTime::now());
+    let preferences = app.state::<PreferencesStore>().get();
+    let rollover_hour = preferences.day_rollover_hour;
+    let rounding = preferences.usage_rounding;
+    let history = app.state::<UsageHistory>();
+    let recorder = app.state::<AppUsageRecorder>();
+    let top_apps = recorder.top_apps_today(&history, TRAY_TOP_APP_COUNT, now_ms, rollover_hour);
+    let glance = today_glance(
+        &app.state::<StartupMetrics>(),
+        &history,
+        &recorder,
+        now_ms,
+        rollover_hour,
+    );
+
+    if let Some(tray) = app.tray_by_id(TRAY_ICON_ID) {
+        if let Err(err) = tray.set_tooltip(Some(tray_tooltip(&glance, &top_apps, rounding))) {
+            eprintln!("failed to update tray tooltip: {err}");
+        }
+    }
+
+    let Some(top_items) = app.try_state::<TrayTopApps>() else {
+        return;
+    };
+    for (index, item) in top_items.items.iter().enumerate() {
+        let label = top_apps
+            .get(index)
+            .map(|(name, ms)| top_app_label(name, *ms, rounding))
+            .unwrap_or_else(|| EMPTY_TOP_APP_LABEL.to_string());
+        let _ = item.set_text(label);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tooltip_lists_top_apps_with_durations() {
+        let top_apps = vec![
+            ("Code".to_string(), 65 * 60_000),
+            ("Mail".to_string(), 12 * 60_000 + 30_000),
+            ("Music".to_string(), 2 * 60 * 60_000),
+        ];
+        let glance = TodayGlance {
+            launches_today: 2,
+            usage_today_ms: 3 * 60 * 60_000 + 17 * 60_000,
+            ..TodayGlance::default()
+        };
+        assert_eq!(
+            tray_tooltip(&glance, &top_apps, UsageRounding::None),
+            "Time Wise\nToday: 2 launches · 3h 17m\nCode · 1h 5m\nMail · 12m\nMusic · 2h"
+        );
+        assert_eq!(
+            tray_tooltip(&TodayGlance::default(), &[], UsageRounding::None),
+            "Time Wise\nToday: 0 launches · 0m"
+        );
+    }
+
+    #[test]
+    fn tooltip_rounds_durations_like_the_dashboard() {
+        let top_apps = vec![("Mail".to_string(), 12 * 60_000 + 30_000)];
+        let glance = TodayGlance {
+            launches_today: 1,
+            usage_today_ms: 59 * 60_000 + 45_000,
+            ..TodayGlance::default()
+        };
+        assert_eq!(
+            tray_tooltip(&glance, &top_apps, UsageRounding::Minute),
+            "Time Wise\nToday: 1 launch · 1h\nMail · 13m"
+        );
+    }
+}
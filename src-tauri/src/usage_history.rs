@@ -0,0 +1,987 @@
+//! Persists completed app usage sessions in SQLite so usage can be queried across runs and ranges.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+
+use crate::app_usage::{system_time_to_ms, AppUsageRecord, AppUsageRecorder};
+use crate::calendar::{
+    day_start_ms, days_ago_start_ms, hour_of_day, local_day_offset_minutes, local_offset_minutes,
+    weekday_index,
+};
+use crate::maintenance;
+use crate::preferences::PreferencesStore;
+
+/// Days covered by the per-app daily usage series.
+const DAILY_USAGE_DAYS: u32 = 14;
+
+/// A contiguous period during which an application was observed running.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageSession {
+    pub name: String,
+    pub executable: Option<String>,
+    pub start_ms: u64,
+    pub end_ms: u64,
+}
+
+impl UsageSession {
+    /// Milliseconds of this session that fall inside `[start_ms, end_ms)`.
+    pub fn overlap_ms(&self, start_ms: u64, end_ms: u64) -> u64 {
+        let clipped_start = self.start_ms.max(start_ms);
+        let clipped_end = self.end_ms.min(end_ms);
+        clipped_end.saturating_sub(clipped_start)
+    }
+}
+
+/// Everything known about one app's usage across stored and open sessions.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AppUsageDetail {
+    pub name: String,
+    pub total_ms: u64,
+    pub launch_count: u64,
+    pub longest_session_ms: u64,
+    pub average_session_ms: u64,
+    pub first_seen_ms: u64,
+    pub last_seen_ms: u64,
+}
+
+/// SQLite-backed store of completed usage sessions.
+pub struct UsageHistory {
+    connection: Mutex<Connection>,
+}
+
+impl UsageHistory {
+    /// Opens or creates the SQLite database at the provided path and runs migrations.
+    pub fn with_storage_path(storage_path: PathBuf) -> Self {
+        if let Some(parent) = storage_path.parent() {
+            if let Err(err) = std::fs::create_dir_all(parent) {
+                eprintln!("failed to create usage history directory: {err}");
+            }
+        }
+
+        let connection = match Connection::open(&storage_path).and_then(|connection| {
+            Self::migrate(&connection)?;
+            Ok(connection)
+        }) {
+            Ok(connection) => connection,
+            Err(err) => {
+                eprintln!("failed to open usage history database: {err}");
+                return Self::in_memory();
+            }
+        };
+
+        Self::with_connection(connection)
+    }
+
+    /// Opens a throwaway in-memory database, leaving any stored data untouched.
+    pub fn in_memory() -> Self {
+        let connection =
+            Connection::open_in_memory().expect("failed to open in-memory sqlite connection");
+        if let Err(err) = Self::migrate(&connection) {
+            eprintln!("failed to initialize in-memory database: {err}");
+        }
+        Self::with_connection(connection)
+    }
+
+    fn with_connection(connection: Connection) -> Self {
+        Self {
+            connection: Mutex::new(connection),
+        }
+    }
+
+    /// Ensures the backing tables and indexes exist.
+    fn migrate(connection: &Connection) -> rusqlite::Result<()> {
+        connection.execute_batch(
+            "CREATE TABLE IF NOT EXISTS usage_sessions (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL,
+                executable TEXT,
+                start_ms INTEGER NOT NULL,
+                end_ms INTEGER NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_usage_sessions_start
+                ON usage_sessions(start_ms);
+            ",
+        )?;
+        maintenance::migrate(connection)
+    }
+
+    /// Vacuums the database when its maintenance metadata says it is due.
+    pub fn run_maintenance(&self, now_ms: u64) -> Result<Option<u64>, String> {
+        let connection = self
+            .connection
+            .lock()
+            .map_err(|_| "usage history mutex poisoned".to_string())?;
+        maintenance::run_if_due(&connection, now_ms).map_err(|err| err.to_string())
+    }
+
+    /// Vacuums the database now, returning the bytes reclaimed.
+    pub fn vacuum(&self, now_ms: u64) -> Result<u64, String> {
+        let connection = self
+            .connection
+            .lock()
+            .map_err(|_| "usage history mutex poisoned".to_string())?;
+        maintenance::vacuum(&connection, now_ms).map_err(|err| err.to_string())
+    }
+
+    /// Appends completed sessions in a single transaction.
+    pub fn record_sessions(&self, sessions: &[UsageSession]) -> Result<(), String> {
+        if sessions.is_empty() {
+            return Ok(());
+        }
+        self.write_sessions(sessions, false, || Ok(()))
+    }
+
+    /// Replaces every stored session with `sessions` in a single transaction.
+    ///
+    /// The transaction commits only after `before_commit` succeeds, so callers can stage other
+    /// stores inside it and roll everything back together.
+    pub fn replace_sessions(
+        &self,
+        sessions: &[UsageSession],
+        before_commit: impl FnOnce() -> Result<(), String>,
+    ) -> Result<(), String> {
+        self.write_sessions(sessions, true, before_commit)
+    }
+
+    fn write_sessions(
+        &self,
+        sessions: &[UsageSession],
+        replace: bool,
+        before_commit: impl FnOnce() -> Result<(), String>,
+    ) -> Result<(), String> {
+        let mut connection = self
+            .connection
+            .lock()
+            .map_err(|_| "usage history mutex poisoned".to_string())?;
+        let transaction = connection.transaction().map_err(|err| err.to_string())?;
+        if replace {
+            transaction
+                .execute("DELETE FROM usage_sessions", [])
+                .map_err(|err| err.to_string())?;
+        }
+        for session in sessions {
+            transaction
+                .execute(
+                    "INSERT INTO usage_sessions (name, executable, start_ms, end_ms)
+                     VALUES (?1, ?2, ?3, ?4)",
+                    params![
+                        session.name,
+                        session.executable,
+                        session.start_ms.min(i64::MAX as u64) as i64,
+                        session.end_ms.min(i64::MAX as u64) as i64
+                    ],
+                )
+                .map_err(|err| err.to_string())?;
+        }
+        before_commit()?;
+        transaction.commit().map_err(|err| err.to_string())
+    }
+
+    /// Returns stored sessions overlapping `[start_ms, end_ms)`, oldest first.
+    pub fn sessions_between(&self, start_ms: u64, end_ms: u64) -> Vec<UsageSession> {
+        let connection = match self.connection.lock() {
+            Ok(connection) => connection,
+            Err(_) => return Vec::new(),
+        };
+
+        let mut statement = match connection.prepare(
+            "SELECT name, executable, start_ms, end_ms
+             FROM usage_sessions
+             WHERE end_ms > ?1 AND start_ms < ?2
+             ORDER BY start_ms ASC",
+        ) {
+            Ok(statement) => statement,
+            Err(err) => {
+                eprintln!("failed to read usage history: {err}");
+                return Vec::new();
+            }
+        };
+
+        let rows = match statement.query_map(
+            params![
+                start_ms.min(i64::MAX as u64) as i64,
+                end_ms.min(i64::MAX as u64) as i64
+            ],
+            |row| {
+                Ok(UsageSession {
+                    name: row.get(0)?,
+                    executable: row.get(1)?,
+                    start_ms: row.get::<_, i64>(2)?.max(0) as u64,
+                    end_ms: row.get::<_, i64>(3)?.max(0) as u64,
+                })
+            },
+        ) {
+            Ok(rows) => rows,
+            Err(err) => {
+                eprintln!("failed to collect usage history: {err}");
+                return Vec::new();
+            }
+        };
+
+        rows.filter_map(Result::ok).collect()
+    }
+
+    /// Returns every stored session of `app`, oldest first.
+    fn sessions_for_app(&self, app: &str) -> Vec<UsageSession> {
+        let connection = match self.connection.lock() {
+            Ok(connection) => connection,
+            Err(_) => return Vec::new(),
+        };
+
+        let mut statement = match connection.prepare(
+            "SELECT name, executable, start_ms, end_ms
+             FROM usage_sessions
+             WHERE name = ?1
+             ORDER BY start_ms ASC",
+        ) {
+            Ok(statement) => statement,
+            Err(err) => {
+                eprintln!("failed to read usage history: {err}");
+                return Vec::new();
+            }
+        };
+
+        let rows = match statement.query_map(params![app], |row| {
+            Ok(UsageSession {
+                name: row.get(0)?,
+                executable: row.get(1)?,
+                start_ms: row.get::<_, i64>(2)?.max(0) as u64,
+                end_ms: row.get::<_, i64>(3)?.max(0) as u64,
+            })
+        }) {
+            Ok(rows) => rows,
+            Err(err) => {
+                eprintln!("failed to collect usage history: {err}");
+                return Vec::new();
+            }
+        };
+
+        rows.filter_map(Result::ok).collect()
+    }
+
+    /// Total stored usage of `app` per local weekday, Monday first.
+    pub fn usage_by_weekday(&self, app: &str, tz_offset: i32) -> [u64; 7] {
+        weekday_totals(&self.sessions_for_app(app), tz_offset)
+    }
+
+    /// Stored usage of `app` per local day for the `days` days ending today, oldest first.
+    pub fn usage_by_day(
+        &self,
+        app: &str,
+        days: u32,
+        tz_offset: i32,
+        now_ms: u64,
+    ) -> Vec<(u64, u64)> {
+        let first_day = days_ago_start_ms(now_ms, tz_offset, days.saturating_sub(1));
+        daily_totals(&self.sessions_for_app(app), tz_offset, first_day, days)
+    }
+
+    /// Returns when each recorded app's most recent session ended.
+    pub fn last_seen_by_app(&self) -> Vec<(String, u64)> {
+        let connection = match self.connection.lock() {
+            Ok(connection) => connection,
+            Err(_) => return Vec::new(),
+        };
+
+        let mut statement = match connection
+            .prepare("SELECT name, MAX(end_ms) FROM usage_sessions GROUP BY name")
+        {
+            Ok(statement) => statement,
+            Err(err) => {
+                eprintln!("failed to read last seen apps: {err}");
+                return Vec::new();
+            }
+        };
+
+        let rows = match statement.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, i64>(1)?.max(0) as u64,
+            ))
+        }) {
+            Ok(rows) => rows,
+            Err(err) => {
+                eprintln!("failed to collect last seen apps: {err}");
+                return Vec::new();
+            }
+        };
+
+        rows.filter_map(Result::ok).collect()
+    }
+
+    /// Returns apps whose most recent session ended before the local day `days` days ago,
+    /// paired with that last-seen timestamp, oldest first.
+    pub fn stale_apps(&self, days: u32, tz_offset: i32, now_ms: u64) -> Vec<(String, u64)> {
+        let cutoff = days_ago_start_ms(now_ms, tz_offset, days);
+        let connection = match self.connection.lock() {
+            Ok(connection) => connection,
+            Err(_) => return Vec::new(),
+        };
+
+        let mut statement = match connection.prepare(
+            "SELECT name, MAX(end_ms) AS last_seen
+             FROM usage_sessions
+             GROUP BY name
+             HAVING last_seen < ?1
+             ORDER BY last_seen ASC, name ASC",
+        ) {
+            Ok(statement) => statement,
+            Err(err) => {
+                eprintln!("failed to read stale apps: {err}");
+                return Vec::new();
+            }
+        };
+
+        let rows = match statement.query_map(params![cutoff.min(i64::MAX as u64) as i64], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, i64>(1)?.max(0) as u64,
+            ))
+        }) {
+            Ok(rows) => rows,
+            Err(err) => {
+                eprintln!("failed to collect stale apps: {err}");
+                return Vec::new();
+            }
+        };
+
+        rows.filter_map(Result::ok).collect()
+    }
+}
+
+/// Sums session time per local weekday, Monday first, splitting sessions that cross midnight.
+pub fn weekday_totals(sessions: &[UsageSession], tz_offset: i32) -> [u64; 7] {
+    const DAY_MS: u64 = 24 * 60 * 60 * 1_000;
+    let mut totals = [0; 7];
+    for session in sessions {
+        let mut cursor = session.start_ms;
+        while cursor < session.end_ms {
+            let next_day = day_start_ms(cursor, tz_offset) + DAY_MS;
+            let slice_end = session.end_ms.min(next_day);
+            totals[weekday_index(cursor, tz_offset)] += slice_end - cursor;
+            cursor = slice_end;
+        }
+    }
+    totals
+}
+
+/// Local hour (0–23) with the most session time inside `[start_ms, end_ms)`, with that time, or `None` without usage.
+///
+/// Sessions crossing an hour split their time between the hours; ties go to the earliest hour.
+pub fn busiest_hour(
+    sessions: &[UsageSession],
+    start_ms: u64,
+    end_ms: u64,
+    tz_offset: i32,
+) -> Option<(u8, u64)> {
+    const HOUR_MS: u64 = 60 * 60 * 1_000;
+    let offset_ms = i64::from(tz_offset) * 60_000;
+    let mut totals = [0u64; 24];
+    for session in sessions {
+        let mut cursor = session.start_ms.max(start_ms);
+        let session_end = session.end_ms.min(end_ms);
+        while cursor < session_end {
+            let into_hour = (cursor as i64 + offset_ms).rem_euclid(HOUR_MS as i64) as u64;
+            let slice_end = session_end.min(cursor - into_hour + HOUR_MS);
+            totals[hour_of_day(cursor, tz_offset)] += slice_end - cursor;
+            cursor = slice_end;
+        }
+    }
+    let (hour, total_ms) = totals
+        .into_iter()
+        .enumerate()
+        .fold((0, 0), |best, (hour, total_ms)| {
+            if total_ms > best.1 {
+                (hour, total_ms)
+            } else {
+                best
+            }
+        });
+    (total_ms > 0).then_some((hour as u8, total_ms))
+}
+
+/// Aggregates the sessions of `name`, or `None` when it has none.
+pub fn app_usage_detail(name: &str, sessions: &[UsageSession]) -> Option<AppUsageDetail> {
+    let durations: Vec<u64> = sessions
+        .iter()
+        .filter(|session| session.name == name)
+        .map(|session| session.end_ms.saturating_sub(session.start_ms))
+        .collect();
+    if durations.is_empty() {
+        return None;
+    }
+    let own = || sessions.iter().filter(|session| session.name == name);
+    let total_ms: u64 = durations.iter().sum();
+    let launch_count = durations.len() as u64;
+    Some(AppUsageDetail {
+        name: name.to_string(),
+        total_ms,
+        launch_count,
+        longest_session_ms: durations.iter().copied().max().unwrap_or(0),
+        average_session_ms: total_ms / launch_count,
+        first_seen_ms: own().map(|session| session.start_ms).min().unwrap_or(0),
+        last_seen_ms: own().map(|session| session.end_ms).max().unwrap_or(0),
+    })
+}
+
+/// Counts consecutive local days with any usage, starting today and stopping at the first empty day.
+pub fn streak_length(sessions: &[UsageSession], tz_offset: i32, now_ms: u64) -> usize {
+    const DAY_MS: u64 = 24 * 60 * 60 * 1_000;
+    let mut days = 0;
+    loop {
+        let day_start = days_ago_start_ms(now_ms, tz_offset, days as u32);
+        let used = sessions
+            .iter()
+            .any(|session| session.overlap_ms(day_start, day_start + DAY_MS) > 0);
+        if !used {
+            return days;
+        }
+        days += 1;
+        if day_start == 0 {
+            return days;
+        }
+    }
+}
+
+/// Sums session time per local day for `days` days from `first_day_ms`, pairing each day start with its total.
+pub fn daily_totals(
+    sessions: &[UsageSession],
+    tz_offset: i32,
+    first_day_ms: u64,
+    days: u32,
+) -> Vec<(u64, u64)> {
+    const DAY_MS: u64 = 24 * 60 * 60 * 1_000;
+    let first_day_ms = day_start_ms(first_day_ms, tz_offset);
+    (0..u64::from(days))
+        .map(|index| {
+            let day_ms = first_day_ms + index * DAY_MS;
+            let total = sessions
+                .iter()
+                .map(|session| session.overlap_ms(day_ms, day_ms + DAY_MS))
+                .sum();
+            (day_ms, total)
+        })
+        .collect()
+}
+
+/// Stored sessions overlapping `[start_ms, end_ms)` together with the open and not yet persisted
+/// ones `recorder` still holds.
+pub fn sessions_including_open(
+    history: &UsageHistory,
+    recorder: &AppUsageRecorder,
+    start_ms: u64,
+    end_ms: u64,
+) -> Vec<UsageSession> {
+    let mut sessions = history.sessions_between(start_ms, end_ms);
+    sessions.extend(recorder.unsaved_sessions(end_ms));
+    sessions
+}
+
+/// Returns the `n` apps with the most time within `[start_ms, end_ms)`, longest first and ties by name.
+pub fn top_apps(
+    sessions: &[UsageSession],
+    n: usize,
+    start_ms: u64,
+    end_ms: u64,
+) -> Vec<(String, u64)> {
+    let mut totals: HashMap<&str, u64> = HashMap::new();
+    for session in sessions {
+        let overlap = session.overlap_ms(start_ms, end_ms);
+        if overlap > 0 {
+            *totals.entry(&session.name).or_default() += overlap;
+        }
+    }
+
+    let mut top: Vec<_> = totals
+        .into_iter()
+        .map(|(name, ms)| (name.to_string(), ms))
+        .collect();
+    top.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    top.truncate(n);
+    top
+}
+
+/// Sums each application's time overlapping `[start_ms, end_ms)`, clipping sessions at the edges.
+pub fn usage_in_range(
+    sessions: &[UsageSession],
+    start_ms: u64,
+    end_ms: u64,
+) -> Vec<AppUsageRecord> {
+    let mut by_app: HashMap<(String, Option<String>), AppUsageRecord> = HashMap::new();
+
+    for session in sessions {
+        let overlap = session.overlap_ms(start_ms, end_ms);
+        if overlap == 0 {
+            continue;
+        }
+
+        let record = by_app
+            .entry((session.name.clone(), session.executable.clone()))
+            .or_insert_with(|| AppUsageRecord {
+                name: session.name.clone(),
+                executable: session.executable.clone(),
+                total_active_ms: 0,
+                last_seen_at_ms: session.end_ms,
+                first_seen_at_ms: session.start_ms,
+                active: false,
+                window_title: None,
+            });
+        record.total_active_ms += overlap;
+        record.first_seen_at_ms = record.first_seen_at_ms.min(session.start_ms);
+        record.last_seen_at_ms = record.last_seen_at_ms.max(session.end_ms);
+    }
+
+    let mut records: Vec<_> = by_app.into_values().collect();
+    records.sort_by(|a, b| {
+        b.total_active_ms
+            .cmp(&a.total_active_ms)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    records
+}
+
+#[tauri::command]
+/// Tauri command returning per-app usage overlapping the requested range.
+pub fn fetch_usage_in_range(
+    history: tauri::State<'_, UsageHistory>,
+    recorder: tauri::State<'_, AppUsageRecorder>,
+    start_ms: u64,
+    end_ms: u64,
+) -> Vec<AppUsageRecord> {
+    let open_sessions = recorder.open_sessions();
+    let mut sessions = history.sessions_between(start_ms, end_ms);
+    sessions.extend(open_sessions.iter().cloned());
+
+    let mut records = usage_in_range(&sessions, start_ms, end_ms);
+    for record in &mut records {
+        record.active = open_sessions.iter().any(|session| {
+            session.name == record.name
+                && session.executable == record.executable
+                && session.overlap_ms(start_ms, end_ms) > 0
+        });
+    }
+    records
+}
+
+#[tauri::command]
+/// Tauri command returning the local hour with the most usage in the range, with its total.
+pub fn fetch_busiest_hour(
+    history: tauri::State<'_, UsageHistory>,
+    recorder: tauri::State<'_, AppUsageRecorder>,
+    start_ms: u64,
+    end_ms: u64,
+) -> Option<(u8, u64)> {
+    let mut sessions = history.sessions_between(start_ms, end_ms);
+    sessions.extend(recorder.open_sessions());
+    busiest_hour(&sessions, start_ms, end_ms, local_offset_minutes())
+}
+
+#[tauri::command]
+/// Tauri command listing apps not used in the last `days` days, skipping apps running now.
+pub fn fetch_stale_apps(
+    history: tauri::State<'_, UsageHistory>,
+    recorder: tauri::State<'_, AppUsageRecorder>,
+    preferences: tauri::State<'_, PreferencesStore>,
+    days: u32,
+) -> Vec<(String, u64)> {
+    let now_ms = system_time_to_ms(std::time::SystemTime::now());
+    let open_sessions = recorder.open_sessions();
+    let day_offset = local_day_offset_minutes(preferences.get().day_rollover_hour);
+    history
+        .stale_apps(days, day_offset, now_ms)
+        .into_iter()
+        .filter(|(name, _)| !open_sessions.iter().any(|session| &session.name == name))
+        .collect()
+}
+
+#[tauri::command]
+/// Tauri command returning an app's stored usage per local weekday, Monday first.
+pub fn fetch_usage_by_weekday(
+    history: tauri::State<'_, UsageHistory>,
+    preferences: tauri::State<'_, PreferencesStore>,
+    app: String,
+) -> [u64; 7] {
+    let day_offset = local_day_offset_minutes(preferences.get().day_rollover_hour);
+    history.usage_by_weekday(&app, day_offset)
+}
+
+#[tauri::command]
+/// Tauri command summarizing one app's stored and open sessions, or `None` for an unknown app.
+pub fn fetch_app_usage_detail(
+    history: tauri::State<'_, UsageHistory>,
+    recorder: tauri::State<'_, AppUsageRecorder>,
+    name: String,
+) -> Option<AppUsageDetail> {
+    let mut sessions = history.sessions_for_app(&name);
+    sessions.extend(recorder.open_sessions());
+    app_usage_detail(&name, &sessions)
+}
+
+#[tauri::command]
+/// Tauri command returning the current usage streak of every pinned app that has one.
+pub fn fetch_usage_streaks(
+    history: tauri::State<'_, UsageHistory>,
+    recorder: tauri::State<'_, AppUsageRecorder>,
+    preferences: tauri::State<'_, PreferencesStore>,
+) -> Vec<(String, usize)> {
+    let preferences = preferences.get();
+    let day_offset = local_day_offset_minutes(preferences.day_rollover_hour);
+    let now_ms = system_time_to_ms(std::time::SystemTime::now());
+    let open_sessions = recorder.open_sessions();
+    preferences
+        .pinned_apps
+        .into_iter()
+        .filter_map(|app| {
+            let mut sessions = history.sessions_for_app(&app);
+            sessions.extend(
+                open_sessions
+                    .iter()
+                    .filter(|session| session.name == app)
+                    .cloned(),
+            );
+            let streak = streak_length(&sessions, day_offset, now_ms);
+            (streak > 0).then_some((app, streak))
+        })
+        .collect()
+}
+
+#[tauri::command]
+/// Tauri command returning an app's stored usage per local day over the last `DAILY_USAGE_DAYS` days.
+pub fn fetch_daily_usage(
+    history: tauri::State<'_, UsageHistory>,
+    preferences: tauri::State<'_, PreferencesStore>,
+    app: String,
+) -> Vec<(u64, u64)> {
+    let day_offset = local_day_offset_minutes(preferences.get().day_rollover_hour);
+    history.usage_by_day(
+        &app,
+        DAILY_USAGE_DAYS,
+        day_offset,
+        system_time_to_ms(std::time::SystemTime::now()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(name: &str, start_ms: u64, end_ms: u64) -> UsageSession {
+        UsageSession {
+            name: name.to_string(),
+            executable: None,
+            start_ms,
+            end_ms,
+        }
+    }
+
+    #[test]
+    fn top_apps_sum_sessions_within_the_range() {
+        let sessions = vec![
+            session("Focus", 0, 120_000),
+            session("Mail", 60_000, 180_000),
+            session("Focus", 150_000, 160_000),
+        ];
+
+        assert_eq!(
+            top_apps(&sessions, 5, 30_000, 180_000),
+            vec![
+                ("Mail".to_string(), 120_000),
+                ("Focus".to_string(), 100_000)
+            ]
+        );
+        assert_eq!(
+            top_apps(&sessions, 1, 30_000, 180_000),
+            vec![("Mail".to_string(), 120_000)]
+        );
+        assert!(top_apps(&sessions, 5, 200_000, 300_000).is_empty());
+    }
+
+    #[test]
+    fn usage_in_range_counts_sessions_fully_inside() {
+        let records = usage_in_range(&[session("Focus", 1_000, 2_000)], 0, 10_000);
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].total_active_ms, 1_000);
+    }
+
+    #[test]
+    fn usage_in_range_ignores_sessions_fully_outside() {
+        let sessions = vec![session("Before", 0, 1_000), session("After", 5_000, 6_000)];
+        assert!(usage_in_range(&sessions, 1_000, 5_000).is_empty());
+    }
+
+    #[test]
+    fn usage_in_range_clips_sessions_straddling_start() {
+        let records = usage_in_range(&[session("Focus", 500, 1_500)], 1_000, 5_000);
+        assert_eq!(records[0].total_active_ms, 500);
+    }
+
+    #[test]
+    fn usage_in_range_clips_sessions_straddling_end() {
+        let records = usage_in_range(&[session("Focus", 4_000, 7_000)], 1_000, 5_000);
+        assert_eq!(records[0].total_active_ms, 1_000);
+    }
+
+    #[test]
+    fn usage_in_range_sums_sessions_per_app() {
+        let sessions = vec![
+            session("Focus", 0, 2_000),
+            session("Mail", 1_000, 1_500),
+            session("Focus", 3_000, 4_000),
+        ];
+        let records = usage_in_range(&sessions, 1_000, 10_000);
+        assert_eq!(records[0].name, "Focus");
+        assert_eq!(records[0].total_active_ms, 2_000);
+        assert_eq!(records[1].name, "Mail");
+        assert_eq!(records[1].total_active_ms, 500);
+    }
+
+    #[test]
+    fn busiest_hour_finds_the_hour_with_most_usage() {
+        const MINUTE_MS: u64 = 60_000;
+        const HOUR_MS: u64 = 60 * MINUTE_MS;
+        let day = 3 * 24 * HOUR_MS;
+        let sessions = vec![
+            session(
+                "Code",
+                day + 10 * HOUR_MS + 5 * MINUTE_MS,
+                day + 10 * HOUR_MS + 50 * MINUTE_MS,
+            ),
+            session(
+                "Mail",
+                day + 10 * HOUR_MS + 10 * MINUTE_MS,
+                day + 10 * HOUR_MS + 20 * MINUTE_MS,
+            ),
+            session(
+                "Code",
+                day + 14 * HOUR_MS,
+                day + 14 * HOUR_MS + 10 * MINUTE_MS,
+            ),
+        ];
+        assert_eq!(
+            busiest_hour(&sessions, 0, u64::MAX, 0),
+            Some((10, 55 * MINUTE_MS))
+        );
+        assert_eq!(
+            busiest_hour(&sessions, 0, u64::MAX, 9 * 60),
+            Some((19, 55 * MINUTE_MS))
+        );
+        assert_eq!(busiest_hour(&[], 0, u64::MAX, 0), None);
+    }
+
+    #[test]
+    fn busiest_hour_splits_sessions_across_midnight_and_prefers_earlier_ties() {
+        const MINUTE_MS: u64 = 60_000;
+        const HOUR_MS: u64 = 60 * MINUTE_MS;
+        let day = 3 * 24 * HOUR_MS;
+        let overnight = [session(
+            "Code",
+            day + 23 * HOUR_MS + 30 * MINUTE_MS,
+            day + 24 * HOUR_MS + 45 * MINUTE_MS,
+        )];
+        assert_eq!(
+            busiest_hour(&overnight, 0, u64::MAX, 0),
+            Some((0, 45 * MINUTE_MS))
+        );
+
+        let tied = [
+            session(
+                "Mail",
+                day + 15 * HOUR_MS,
+                day + 15 * HOUR_MS + 30 * MINUTE_MS,
+            ),
+            session(
+                "Code",
+                day + 9 * HOUR_MS,
+                day + 9 * HOUR_MS + 30 * MINUTE_MS,
+            ),
+        ];
+        assert_eq!(
+            busiest_hour(&tied, 0, u64::MAX, 0),
+            Some((9, 30 * MINUTE_MS))
+        );
+    }
+
+    #[test]
+    fn sessions_round_trip_through_storage() {
+        let dir = tempfile::tempdir().unwrap();
+        let history = UsageHistory::with_storage_path(dir.path().join("usage.sqlite"));
+
+        history
+            .record_sessions(&[
+                session("Focus", 1_000, 2_000),
+                session("Mail", 8_000, 9_000),
+            ])
+            .unwrap();
+
+        let sessions = history.sessions_between(1_500, 5_000);
+        assert_eq!(sessions, vec![session("Focus", 1_000, 2_000)]);
+    }
+
+    #[test]
+    fn usage_by_weekday_attributes_sessions_to_local_weekdays() {
+        const DAY_MS: u64 = 24 * 60 * 60_000;
+        const HOUR_MS: u64 = 60 * 60_000;
+        // 1970-01-05 was a Monday and 1970-01-10 a Saturday.
+        let monday = 4 * DAY_MS;
+        let saturday = 9 * DAY_MS;
+        let dir = tempfile::tempdir().unwrap();
+        let history = UsageHistory::with_storage_path(dir.path().join("usage.sqlite"));
+
+        history
+            .record_sessions(&[
+                session("Game", monday + 10 * HOUR_MS, monday + 11 * HOUR_MS),
+                session("Game", saturday + 9 * HOUR_MS, saturday + 12 * HOUR_MS),
+                // Saturday 23:00 to Sunday 01:00 splits across both days.
+                session("Game", saturday + 23 * HOUR_MS, saturday + 25 * HOUR_MS),
+                session("Mail", monday + 9 * HOUR_MS, monday + 17 * HOUR_MS),
+            ])
+            .unwrap();
+
+        assert_eq!(
+            history.usage_by_weekday("Game", 0),
+            [HOUR_MS, 0, 0, 0, 0, 4 * HOUR_MS, HOUR_MS]
+        );
+        // At UTC-11 the Monday 10:00 UTC session falls on Sunday night.
+        assert_eq!(history.usage_by_weekday("Game", -11 * 60)[6], HOUR_MS);
+    }
+
+    #[test]
+    fn usage_by_day_fills_every_day_and_splits_at_midnight() {
+        const DAY_MS: u64 = 24 * 60 * 60_000;
+        const HOUR_MS: u64 = 60 * 60_000;
+        let dir = tempfile::tempdir().unwrap();
+        let history = UsageHistory::with_storage_path(dir.path().join("usage.sqlite"));
+        let now = 20 * DAY_MS + 12 * HOUR_MS;
+
+        history
+            .record_sessions(&[
+                session("Game", 17 * DAY_MS + 23 * HOUR_MS, 18 * DAY_MS + HOUR_MS),
+                session(
+                    "Game",
+                    20 * DAY_MS + 9 * HOUR_MS,
+                    20 * DAY_MS + 10 * HOUR_MS,
+                ),
+                // Before the window, so it is left out.
+                session("Game", 10 * DAY_MS, 10 * DAY_MS + HOUR_MS),
+                session("Mail", 19 * DAY_MS, 19 * DAY_MS + 5 * HOUR_MS),
+            ])
+            .unwrap();
+
+        assert_eq!(
+            history.usage_by_day("Game", 4, 0, now),
+            vec![
+                (17 * DAY_MS, HOUR_MS),
+                (18 * DAY_MS, HOUR_MS),
+                (19 * DAY_MS, 0),
+                (20 * DAY_MS, HOUR_MS),
+            ]
+        );
+    }
+
+    #[test]
+    fn app_usage_detail_combines_stored_and_open_sessions() {
+        let dir = tempfile::tempdir().unwrap();
+        let history = UsageHistory::with_storage_path(dir.path().join("usage.sqlite"));
+        history
+            .record_sessions(&[
+                session("Focus", 1_000, 4_000),
+                session("Focus", 10_000, 11_000),
+                session("Mail", 2_000, 50_000),
+            ])
+            .unwrap();
+
+        let mut sessions = history.sessions_for_app("Focus");
+        // An open session as reported by the recorder, alongside another app's.
+        sessions.push(session("Focus", 20_000, 28_000));
+        sessions.push(session("Chat", 20_000, 90_000));
+
+        assert_eq!(
+            app_usage_detail("Focus", &sessions),
+            Some(AppUsageDetail {
+                name: "Focus".to_string(),
+                total_ms: 12_000,
+                launch_count: 3,
+                longest_session_ms: 8_000,
+                average_session_ms: 4_000,
+                first_seen_ms: 1_000,
+                last_seen_ms: 28_000,
+            })
+        );
+        assert_eq!(app_usage_detail("Missing", &sessions), None);
+    }
+
+    #[test]
+    fn usage_streak_counts_back_from_today_until_a_gap() {
+        const DAY_MS: u64 = 24 * 60 * 60_000;
+        const HOUR_MS: u64 = 60 * 60_000;
+        let dir = tempfile::tempdir().unwrap();
+        let history = UsageHistory::with_storage_path(dir.path().join("usage.sqlite"));
+        let now = 30 * DAY_MS + 20 * HOUR_MS;
+        let on_day = |name: &str, day: u64| {
+            session(
+                name,
+                day * DAY_MS + 9 * HOUR_MS,
+                day * DAY_MS + 10 * HOUR_MS,
+            )
+        };
+
+        history
+            .record_sessions(&[
+                // Every day from 24 through today.
+                on_day("Journal", 24),
+                on_day("Journal", 25),
+                on_day("Journal", 26),
+                on_day("Journal", 27),
+                on_day("Journal", 28),
+                on_day("Journal", 29),
+                on_day("Journal", 30),
+                // Day 28 is missing, so only 29 and today count.
+                on_day("Piano", 26),
+                on_day("Piano", 27),
+                on_day("Piano", 29),
+                on_day("Piano", 30),
+                on_day("Run", 30),
+                on_day("Chess", 29),
+            ])
+            .unwrap();
+
+        let streak = |app: &str| streak_length(&history.sessions_for_app(app), 0, now);
+        assert_eq!(streak("Journal"), 7);
+        assert_eq!(streak("Piano"), 2);
+        assert_eq!(streak("Run"), 1);
+        assert_eq!(streak("Chess"), 0);
+        assert_eq!(streak("Missing"), 0);
+    }
+
+    #[test]
+    fn stale_apps_returns_only_apps_unused_since_cutoff() {
+        const DAY_MS: u64 = 24 * 60 * 60_000;
+        let dir = tempfile::tempdir().unwrap();
+        let history = UsageHistory::with_storage_path(dir.path().join("usage.sqlite"));
+        let now = 100 * DAY_MS + 12 * 60 * 60_000;
+
+        history
+            .record_sessions(&[
+                session("Archive", 10 * DAY_MS, 10 * DAY_MS + 5_000),
+                session("Legacy", 40 * DAY_MS, 40 * DAY_MS + 9_000),
+                session("Legacy", 60 * DAY_MS, 60 * DAY_MS + 7_000),
+                session("Focus", 50 * DAY_MS, 50 * DAY_MS + 1_000),
+                session("Focus", 99 * DAY_MS, 99 * DAY_MS + 1_000),
+            ])
+            .unwrap();
+
+        assert_eq!(
+            history.stale_apps(30, 0, now),
+            vec![
+                ("Archive".to_string(), 10 * DAY_MS + 5_000),
+                ("Legacy".to_string(), 60 * DAY_MS + 7_000),
+            ]
+        );
+    }
+}
@@ -0,0 +1,224 @@
+//! Sends a digest of the previous week once a new week begins.
+
+use std::time::{Duration, SystemTime};
+
+use tauri::{AppHandle, Manager};
+use tauri_plugin_notification::NotificationExt;
+use time_wise_shared::usage_rounding::{round_ms, UsageRounding};
+
+use crate::app_usage::system_time_to_ms;
+use crate::calendar::{local_day_offset_minutes, week_start_ms};
+use crate::error_log::log_error;
+use crate::preferences::PreferencesStore;
+use crate::startup_metrics::StartupMetrics;
+use crate::tray_summary::format_tray_duration;
+use crate::usage_history::{usage_in_range, UsageHistory, UsageSession};
+
+/// How often the background task checks whether a new week has begun.
+const WEEKLY_SUMMARY_CHECK_INTERVAL: Duration = Duration::from_secs(30 * 60);
+
+const WEEK_MS: u64 = 7 * 24 * 60 * 60 * 1_000;
+
+/// Returns true once `now_ms` falls in a later local week than the last summary.
+///
+/// Weeks start on `first_weekday` (Monday = 0); without a previous summary there is nothing to compare against.
+pub fn is_new_week(
+    now_ms: u64,
+    last_summary_ms: Option<u64>,
+    offset_minutes: i32,
+    first_weekday: usize,
+) -> bool {
+    last_summary_ms.is_some_and(|last| last < week_start_ms(now_ms, offset_minutes, first_weekday))
+}
+
+/// Notification body summarizing usage and launches in `[start_ms, end_ms)`, with the total rounded for display.
+pub fn weekly_digest(
+    sessions: &[UsageSession],
+    launches: usize,
+    start_ms: u64,
+    end_ms: u64,
+    rounding: UsageRounding,
+) -> String {
+    let usage = usage_in_range(sessions, start_ms, end_ms);
+    let launches_label = if launches == 1 { "launch" } else { "launches" };
+    let Some(top) = usage.first() else {
+        return format!("Last week: no tracked usage. {launches} {launches_label}.");
+    };
+    let total_ms: u64 = usage.iter().map(|record| record.total_active_ms).sum();
+    let apps_label = if usage.len() == 1 { "app" } else { "apps" };
+    format!(
+        "Last week: {} across {} {apps_label}, most in {}. {launches} {launches_label}.",
+        format_tray_duration(round_ms(total_ms, rounding)),
+        usage.len(),
+        top.name,
+    )
+}
+
+/// Sends the digest for the week before the one containing `now_ms`.
+fn send_weekly_summary(
+    app: &AppHandle,
+    now_ms: u64,
+    offset_minutes: i32,
+    first_weekday: usize,
+    rounding: UsageRounding,
+) {
+    let end_ms = week_start_ms(now_ms, offset_minutes, first_weekday);
+    let start_ms = end_ms.saturating_sub(WEEK_MS);
+    let sessions = app
+        .state::<UsageHistory>()
+        .sessions_between(start_ms, end_ms);
+    let launches = app
+        .state::<StartupMetrics>()
+        .records()
+        .iter()
+        .filter(|record| (start_ms..end_ms).contains(&record.recorded_at_ms))
+        .count();
+
+    if let Err(err) = app
+        .notification()
+        .builder()
+        .title("Time Wise weekly summary")
+        .body(weekly_digest(
+            &sessions, launches, start_ms, end_ms, rounding,
+        ))
+        .show()
+    {
+        log_error(app, &format!("failed to show weekly summary: {err}"));
+    }
+}
+
+/// Checks periodically whether a new week has begun and sends the previous week's digest.
+pub fn spawn_weekly_summary_task(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let store = app.state::<PreferencesStore>();
+            let preferences = store.get();
+            if preferences.weekly_summary {
+                let now_ms = system_time_to_ms(SystemTime::now());
+                let offset_minutes = local_day_offset_minutes(preferences.day_rollover_hour);
+                let first_weekday = preferences.week_start.weekday_index();
+                let due = is_new_week(
+                    now_ms,
+                    preferences.last_weekly_summary_ms,
+                    offset_minutes,
+                    first_weekday,
+                );
+                if due {
+                    send_weekly_summary(
+                        &app,
+                        now_ms,
+                        offset_minutes,
+                        first_weekday,
+                        preferences.usage_rounding,
+                    );
+                }
+                // The first check only starts the count, so enabling it never sends a partial week.
+                if due || preferences.last_weekly_summary_ms.is_none() {
+                    if let Err(err) = store.set("last_weekly_summary_ms", serde_json::json!(now_ms))
+                    {
+                        log_error(&app, &format!("failed to record weekly summary: {err}"));
+                    }
+                }
+            }
+            tokio::time::sleep(WEEKLY_SUMMARY_CHECK_INTERVAL).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR_MS: u64 = 60 * 60 * 1_000;
+    const DAY_MS: u64 = 24 * HOUR_MS;
+    /// Sunday, 4 January 1970, 00:00 UTC.
+    const SUNDAY: u64 = 3 * DAY_MS;
+    const MONDAY_INDEX: usize = 0;
+    const SUNDAY_INDEX: usize = 6;
+
+    #[test]
+    fn new_week_follows_the_configured_first_day() {
+        let sunday_noon = SUNDAY + 12 * HOUR_MS;
+        let monday_morning = SUNDAY + DAY_MS + HOUR_MS;
+        assert!(is_new_week(
+            monday_morning,
+            Some(sunday_noon),
+            0,
+            MONDAY_INDEX
+        ));
+        assert!(!is_new_week(
+            monday_morning,
+            Some(sunday_noon),
+            0,
+            SUNDAY_INDEX
+        ));
+
+        let saturday_noon = SUNDAY - 12 * HOUR_MS;
+        let sunday_morning = SUNDAY + HOUR_MS;
+        assert!(is_new_week(
+            sunday_morning,
+            Some(saturday_noon),
+            0,
+            SUNDAY_INDEX
+        ));
+        assert!(!is_new_week(
+            sunday_morning,
+            Some(saturday_noon),
+            0,
+            MONDAY_INDEX
+        ));
+
+        assert!(!is_new_week(monday_morning, None, 0, MONDAY_INDEX));
+    }
+
+    #[test]
+    fn new_week_uses_the_local_calendar() {
+        // 23:00 UTC on Sunday is already Monday at UTC+2.
+        let sunday_late = SUNDAY + 23 * HOUR_MS;
+        let sunday_noon = SUNDAY + 12 * HOUR_MS;
+        assert!(!is_new_week(
+            sunday_late,
+            Some(sunday_noon),
+            0,
+            MONDAY_INDEX
+        ));
+        assert!(is_new_week(
+            sunday_late,
+            Some(sunday_noon),
+            120,
+            MONDAY_INDEX
+        ));
+    }
+
+    #[test]
+    fn digest_summarizes_usage_and_launches() {
+        let session = |name: &str, start_ms: u64, end_ms: u64| UsageSession {
+            name: name.to_string(),
+            executable: None,
+            start_ms,
+            end_ms,
+        };
+        let sessions = vec![
+            session("Code", 0, 2 * HOUR_MS),
+            session("Mail", 3 * HOUR_MS, 3 * HOUR_MS + 30 * 60_000),
+        ];
+        assert_eq!(
+            weekly_digest(&sessions, 5, 0, WEEK_MS, UsageRounding::None),
+            "Last week: 2h 30m across 2 apps, most in Code. 5 launches."
+        );
+        assert_eq!(
+            weekly_digest(&[], 1, 0, WEEK_MS, UsageRounding::None),
+            "Last week: no tracked usage. 1 launch."
+        );
+
+        let almost = vec![session("Code", 0, 2 * HOUR_MS - 20_000)];
+        assert_eq!(
+            weekly_digest(&almost, 0, 0, WEEK_MS, UsageRounding::None),
+            "Last week: 1h 59m across 1 app, most in Code. 0 launches."
+        );
+        assert_eq!(
+            weekly_digest(&almost, 0, 0, WEEK_MS, UsageRounding::Minute),
+            "Last week: 2h across 1 app, most in Code. 0 launches."
+        );
+    }
+}
@@ -0,0 +1,90 @@
+//! "Welcome back" nudges when an opted-in app is reopened after a long break.
+
+use std::time::Duration;
+
+use tauri::{AppHandle, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::app_usage::{AppUsageRecorder, ReopenedApp};
+use crate::preferences::PreferencesStore;
+
+/// Returns true when a reopened app was gone for at least `threshold_minutes`.
+pub fn is_long_gap(gap: Duration, threshold_minutes: u32) -> bool {
+    gap >= Duration::from_secs(u64::from(threshold_minutes) * 60)
+}
+
+/// Describes a gap as "3 days ago", "5 hours ago", or "20 minutes ago".
+pub fn format_gap(gap: Duration) -> String {
+    let minutes = gap.as_secs() / 60;
+    let (count, unit) = if minutes >= 24 * 60 {
+        (minutes / (24 * 60), "day")
+    } else if minutes >= 60 {
+        (minutes / 60, "hour")
+    } else {
+        (minutes, "minute")
+    };
+    let plural = if count == 1 { "" } else { "s" };
+    format!("{count} {unit}{plural} ago")
+}
+
+fn welcome_back_message(reopened: &ReopenedApp) -> String {
+    format!(
+        "Welcome back to {}, you last used it {}",
+        reopened.name,
+        format_gap(reopened.gap)
+    )
+}
+
+/// Notifies for opted-in apps reopened after the configured gap; other reopenings are discarded.
+pub fn notify_reopened_apps(app: &AppHandle) {
+    let reopened = app.state::<AppUsageRecorder>().drain_reopened();
+    let preferences = app.state::<PreferencesStore>().get();
+    if !preferences.welcome_back {
+        return;
+    }
+
+    for entry in reopened {
+        if !preferences.welcome_back_apps.contains(&entry.name)
+            || !is_long_gap(entry.gap, preferences.welcome_back_minutes)
+        {
+            continue;
+        }
+        if let Err(err) = app
+            .notification()
+            .builder()
+            .title("Time Wise")
+            .body(welcome_back_message(&entry))
+            .show()
+        {
+            eprintln!("failed to show welcome back notification: {err}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn long_gap_respects_threshold() {
+        assert!(!is_long_gap(Duration::from_secs(59 * 60), 60));
+        assert!(is_long_gap(Duration::from_secs(60 * 60), 60));
+    }
+
+    #[test]
+    fn gap_uses_largest_whole_unit() {
+        assert_eq!(
+            format_gap(Duration::from_secs(3 * 24 * 3_600 + 600)),
+            "3 days ago"
+        );
+        assert_eq!(format_gap(Duration::from_secs(3_600)), "1 hour ago");
+        assert_eq!(format_gap(Duration::from_secs(20 * 60)), "20 minutes ago");
+        assert_eq!(
+            welcome_back_message(&ReopenedApp {
+                name: "Focus".to_string(),
+                gap: Duration::from_secs(2 * 24 * 3_600),
+            }),
+            "Welcome back to Focus, you last used it 2 days ago"
+        );
+    }
+}
@@ -0,0 +1,148 @@
+//! Reads the titles of visible top-level windows so usage tiles can be labelled by window title.
+
+use std::collections::HashMap;
+use std::process::Command;
+
+/// Title of one visible window per process id; empty when the platform tool is missing or fails.
+#[cfg(target_os = "linux")]
+pub fn window_titles_by_pid() -> HashMap<u32, String> {
+    command_output(Command::new("wmctrl").arg("-lp"))
+        .map(|output| parse_wmctrl(&output))
+        .unwrap_or_default()
+}
+
+/// Title of one visible window per process id; empty when the platform tool is missing or fails.
+#[cfg(target_os = "windows")]
+pub fn window_titles_by_pid() -> HashMap<u32, String> {
+    use std::os::windows::process::CommandExt;
+
+    /// Keeps PowerShell from flashing a console window on every poll.
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+    let mut command = Command::new("powershell");
+    command
+        .args([
+            "-NoProfile",
+            "-NonInteractive",
+            "-Command",
+            "Get-Process | Where-Object { $_.MainWindowTitle } | ForEach-Object { \"$($_.Id)`t$($_.MainWindowTitle)\" }",
+        ])
+        .creation_flags(CREATE_NO_WINDOW);
+    command_output(&mut command)
+        .map(|output| parse_pid_tab_title(&output))
+        .unwrap_or_default()
+}
+
+/// Title of one visible window per process id; empty when the platform tool is missing or fails.
+///
+/// Reading window names through System Events needs the Accessibility permission; without it
+/// `osascript` fails and every tile falls back to the process name.
+#[cfg(target_os = "macos")]
+pub fn window_titles_by_pid() -> HashMap<u32, String> {
+    const SCRIPT: &str = r#"set output to ""
+tell application "System Events"
+    repeat with proc in (every process whose background only is false)
+        try
+            set output to output & (unix id of proc) & tab & (name of front window of proc) & linefeed
+        end try
+    end repeat
+end tell
+return output"#;
+
+    command_output(Command::new("osascript").args(["-e", SCRIPT]))
+        .map(|output| parse_pid_tab_title(&output))
+        .unwrap_or_default()
+}
+
+/// Title of one visible window per process id; empty when the platform tool is missing or fails.
+#[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+pub fn window_titles_by_pid() -> HashMap<u32, String> {
+    HashMap::new()
+}
+
+#[cfg(any(target_os = "linux", target_os = "windows", target_os = "macos"))]
+fn command_output(command: &mut Command) -> Option<String> {
+    let output = command.output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Parses `wmctrl -lp` lines (`<window id> <desktop> <pid> <host> <title>`), keeping each pid's first titled window.
+#[cfg(any(target_os = "linux", test))]
+fn parse_wmctrl(output: &str) -> HashMap<u32, String> {
+    let mut titles = HashMap::new();
+    for line in output.lines() {
+        let mut rest = line.trim_start();
+        let mut fields = [""; 4];
+        for field in &mut fields {
+            let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            *field = &rest[..end];
+            rest = rest[end..].trim_start();
+        }
+        if let Ok(pid) = fields[2].parse() {
+            insert_title(&mut titles, pid, rest);
+        }
+    }
+    titles
+}
+
+/// Parses `<pid>\t<title>` lines, keeping each pid's first titled window.
+#[cfg(any(target_os = "windows", target_os = "macos", test))]
+fn parse_pid_tab_title(output: &str) -> HashMap<u32, String> {
+    let mut titles = HashMap::new();
+    for line in output.lines() {
+        let Some((pid, title)) = line.split_once('\t') else {
+            continue;
+        };
+        if let Ok(pid) = pid.trim().parse() {
+            insert_title(&mut titles, pid, title);
+        }
+    }
+    titles
+}
+
+/// Records `title` for `pid` unless it is blank, the pid is unknown (0), or the pid already has one.
+#[cfg(any(target_os = "linux", target_os = "windows", target_os = "macos", test))]
+fn insert_title(titles: &mut HashMap<u32, String>, pid: u32, title: &str) {
+    let title = title.trim();
+    if pid != 0 && !title.is_empty() {
+        titles.entry(pid).or_insert_with(|| title.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wmctrl_lines_map_pids_to_their_first_titled_window() {
+        let output = "0x03a00003  0 4242   host main.rs — Code\n\
+                      0x03a00007  0 4242   host Settings — Code\n\
+                      0x01e00001 -1 0      N/A  Desktop\n\
+                      0x04200002  0 777    host   \n\
+                      0x04400002  1 901    host Inbox  -  Mail\n";
+
+        let titles = parse_wmctrl(output);
+
+        assert_eq!(titles.len(), 2);
+        assert_eq!(titles[&4242], "main.rs — Code");
+        assert_eq!(titles[&901], "Inbox  -  Mail");
+    }
+
+    #[test]
+    fn tab_separated_lines_skip_malformed_and_blank_entries() {
+        let output = "1200\tQuarterly report - Word\n\
+                      not-a-pid\tIgnored\n\
+                      1300\t  \n\
+                      no tab here\n\
+                      1400\tSlack | general\n";
+
+        let titles = parse_pid_tab_title(output);
+
+        assert_eq!(titles.len(), 2);
+        assert_eq!(titles[&1200], "Quarterly report - Word");
+        assert_eq!(titles[&1400], "Slack | general");
+    }
+}
@@ -1,2 +1,3 @@
 pub mod startup_service;
+pub mod update_service;
 pub mod usage_service;
@@ -3,77 +3,367 @@
 use js_sys::Date;
 use wasm_bindgen::JsValue;
 
-use crate::domain::startup_record::StartupRecord;
-use crate::presentation::models::{CategorySummary, ChartPoint, StartupTile};
+pub use time_wise_shared::chart_data::{format_duration, format_duration_with_precision};
+use time_wise_shared::{
+    build_environment::RELEASE_ENVIRONMENT,
+    chart_data::{
+        category_totals, chart_points, distribution_points, forecast_next, format_duration_compact,
+        linear_slope, time_saved_vs_worst, ChartSample, FORECAST_WINDOW,
+    },
+    stats::median_ms,
+};
 
-/// Builds the chart points from the latest samples.
-pub fn compute_chart_points(records: &[StartupRecord]) -> Vec<ChartPoint> {
-    let mut points: Vec<ChartPoint> = records
+use crate::domain::{
+    community_baseline::CommunityBaseline, launcher_share::LauncherShare,
+    startup_record::StartupRecord,
+};
+use crate::presentation::models::{
+    CategorySummary, ChartMode, ChartPoint, FineBucketSummary, SpeedCategory, StartupTile, Trend,
+};
+
+/// Builds the chart points from the latest `count` samples, padding with placeholders.
+pub fn compute_chart_points(records: &[StartupRecord], count: usize) -> Vec<ChartPoint> {
+    chart_points(&chart_samples(records), count, format_time_of_day)
+}
+
+/// Builds `bucket_count` equal-width duration buckets holding the number of runs in each.
+pub fn compute_distribution_points(
+    records: &[StartupRecord],
+    bucket_count: usize,
+) -> Vec<ChartPoint> {
+    distribution_points(&chart_samples(records), bucket_count)
+}
+
+fn chart_samples(records: &[StartupRecord]) -> Vec<ChartSample> {
+    records
         .iter()
-        .take(5)
-        .map(|record| ChartPoint {
-            label: format_time_of_day(record.recorded_at_ms),
+        .map(|record| ChartSample {
+            recorded_at_ms: record.recorded_at_ms,
             duration_ms: record.duration_ms,
         })
+        .collect()
+}
+
+/// Formats a chart axis annotation for the given mode.
+pub fn format_chart_annotation(value: u64, mode: ChartMode) -> String {
+    match mode {
+        ChartMode::Recent => format_duration_compact(value),
+        ChartMode::Distribution => value.to_string(),
+    }
+}
+
+const CHART_SVG_WIDTH: f64 = 360.0;
+const CHART_SVG_HEIGHT: f64 = 180.0;
+const CHART_SVG_AXIS_WIDTH: f64 = 56.0;
+const CHART_SVG_LABEL_HEIGHT: f64 = 24.0;
+const CHART_SVG_PADDING: f64 = 12.0;
+
+/// Renders the chart as a self-contained SVG document with inline styles and axis annotations.
+pub fn chart_svg(points: &[ChartPoint], max_value: u64, mode: ChartMode) -> String {
+    let plot_left = CHART_SVG_AXIS_WIDTH;
+    let plot_top = CHART_SVG_PADDING;
+    let plot_width = CHART_SVG_WIDTH - plot_left - CHART_SVG_PADDING;
+    let plot_height = CHART_SVG_HEIGHT - plot_top - CHART_SVG_LABEL_HEIGHT;
+    let plot_bottom = plot_top + plot_height;
+    let slot_width = plot_width / points.len().max(1) as f64;
+    let bar_width = slot_width * 0.6;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{CHART_SVG_WIDTH}\" height=\"{CHART_SVG_HEIGHT}\" viewBox=\"0 0 {CHART_SVG_WIDTH} {CHART_SVG_HEIGHT}\" style=\"font-family:sans-serif;font-size:10px\">"
+    );
+    svg.push_str(&format!(
+        "<rect width=\"{CHART_SVG_WIDTH}\" height=\"{CHART_SVG_HEIGHT}\" style=\"fill:#ffffff\"/>"
+    ));
+
+    let annotations = [
+        (plot_top, format_chart_annotation(max_value, mode)),
+        (
+            plot_top + plot_height / 2.0,
+            format_chart_annotation(max_value / 2, mode),
+        ),
+        (plot_bottom, "0".to_string()),
+    ];
+    for (y, text) in annotations {
+        svg.push_str(&format!(
+            "<line x1=\"{plot_left}\" y1=\"{y}\" x2=\"{x2}\" y2=\"{y}\" style=\"stroke:#e2e8f0;stroke-width:1\"/>",
+            x2 = plot_left + plot_width,
+        ));
+        svg.push_str(&format!(
+            "<text x=\"{x}\" y=\"{y}\" style=\"fill:#64748b;text-anchor:end;dominant-baseline:middle\">{text}</text>",
+            x = plot_left - 6.0,
+            text = escape_xml(&text),
+        ));
+    }
+
+    for (index, point) in points.iter().enumerate() {
+        let height = if max_value == 0 {
+            0.0
+        } else {
+            point.value.min(max_value) as f64 / max_value as f64 * plot_height
+        };
+        let slot_left = plot_left + slot_width * index as f64;
+        svg.push_str(&format!(
+            "<rect x=\"{x:.1}\" y=\"{y:.1}\" width=\"{bar_width:.1}\" height=\"{height:.1}\" rx=\"3\" style=\"fill:#6366f1\"/>",
+            x = slot_left + (slot_width - bar_width) / 2.0,
+            y = plot_bottom - height,
+        ));
+        svg.push_str(&format!(
+            "<text x=\"{x:.1}\" y=\"{y}\" style=\"fill:#64748b;text-anchor:middle\">{label}</text>",
+            x = slot_left + slot_width / 2.0,
+            y = CHART_SVG_HEIGHT - 8.0,
+            label = escape_xml(&point.label),
+        ));
+    }
+
+    svg.push_str("</svg>");
+    svg
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Colors of the launcher donut slices, reused in order when there are more launchers.
+const LAUNCHER_DONUT_COLORS: [&str; 6] = [
+    "#7c3aed", "#6366f1", "#0ea5e9", "#14b8a6", "#f59e0b", "#f43f5e",
+];
+
+/// Color of the `index`-th launcher slice and legend swatch.
+pub fn launcher_donut_color(index: usize) -> &'static str {
+    LAUNCHER_DONUT_COLORS[index % LAUNCHER_DONUT_COLORS.len()]
+}
+
+/// CSS `conic-gradient` drawing one slice per launcher share, in order.
+pub fn launcher_donut_gradient(shares: &[LauncherShare]) -> String {
+    let mut start = 0.0;
+    let stops: Vec<String> = shares
+        .iter()
+        .enumerate()
+        .map(|(index, share)| {
+            let end = (start + share.fraction * 100.0).min(100.0);
+            let stop = format!("{} {start:.1}% {end:.1}%", launcher_donut_color(index));
+            start = end;
+            stop
+        })
         .collect();
+    format!("conic-gradient({})", stops.join(", "))
+}
 
-    points.reverse();
+/// Runs slower than this land in the slow bucket.
+const SLOW_START_MS: u64 = 1_500;
+/// Battery level under which a start counts as low-battery.
+const LOW_BATTERY_PERCENT: u8 = 20;
 
-    while points.len() < 5 {
-        points.insert(
-            0,
-            ChartPoint {
-                label: "-".to_string(),
-                duration_ms: 0,
-            },
-        );
+/// Names the launch condition shared by most slow starts, ignoring runs where it is unknown.
+pub fn slow_start_correlation(records: &[StartupRecord]) -> Option<String> {
+    let slow: Vec<_> = records
+        .iter()
+        .filter(|record| record.duration_ms > SLOW_START_MS)
+        .collect();
+    let mostly = |matching: usize, known: usize| known > 0 && matching * 2 > known;
+
+    let battery: Vec<u8> = slow
+        .iter()
+        .filter_map(|record| record.battery_percent)
+        .collect();
+    let low_battery = battery
+        .iter()
+        .filter(|percent| **percent < LOW_BATTERY_PERCENT)
+        .count();
+    if mostly(low_battery, battery.len()) {
+        return Some(format!(
+            "Slow starts mostly happen under {LOW_BATTERY_PERCENT}% battery"
+        ));
     }
 
-    points
+    let thermal: Vec<bool> = slow
+        .iter()
+        .filter_map(|record| record.thermal_pressure)
+        .collect();
+    let throttled = thermal.iter().filter(|pressure| **pressure).count();
+    if mostly(throttled, thermal.len()) {
+        return Some("Slow starts mostly happen under thermal pressure".to_string());
+    }
+
+    None
 }
 
-/// Summarizes runs into fast, steady, slow buckets.
-pub fn compute_category_summary(records: &[StartupRecord]) -> Vec<CategorySummary> {
-    let mut fast: (u64, usize) = (0, 0);
-    let mut steady: (u64, usize) = (0, 0);
-    let mut slow: (u64, usize) = (0, 0);
+/// CPU time over wall time for a run, or `None` when CPU time was not recorded.
+///
+/// A ratio well below 1 means startup spent much of its time waiting, often on a busy system.
+pub fn cpu_wall_ratio(record: &StartupRecord) -> Option<f64> {
+    let cpu_time_ms = record.cpu_time_ms?;
+    (record.duration_ms > 0).then(|| cpu_time_ms as f64 / record.duration_ms as f64)
+}
+
+/// Labels the run's CPU/wall ratio, such as "CPU/wall 0.42".
+pub fn format_cpu_wall_ratio(record: &StartupRecord) -> Option<String> {
+    cpu_wall_ratio(record).map(|ratio| format!("CPU/wall {ratio:.2}"))
+}
+
+/// Runs a launcher needs before it is compared with others.
+const LAUNCHER_MIN_RUNS: usize = 3;
+/// Smallest gap between launcher means, in percent, worth calling out.
+const LAUNCHER_MIN_SLOWDOWN_PERCENT: u64 = 20;
 
+/// Returns true for blank and unknown launchers and for those in `ignored`, ignoring ASCII case.
+pub fn is_hidden_launcher(launcher: &str, ignored: &[String]) -> bool {
+    let launcher = launcher.trim();
+    launcher.is_empty()
+        || launcher.eq_ignore_ascii_case("unknown")
+        || ignored
+            .iter()
+            .any(|name| name.trim().eq_ignore_ascii_case(launcher))
+}
+
+/// Compares the slowest and fastest launchers by mean startup, when both have enough runs.
+///
+/// Like the launcher averages, launchers differing only in case are grouped and `ignored` ones skipped.
+pub fn slow_launcher_insight(records: &[StartupRecord], ignored: &[String]) -> Option<String> {
+    let mut by_launcher: std::collections::BTreeMap<String, (&str, u64, usize)> =
+        std::collections::BTreeMap::new();
     for record in records {
-        match record.duration_ms {
-            0..=500 => {
-                fast.0 += record.duration_ms;
-                fast.1 += 1;
-            }
-            501..=1_500 => {
-                steady.0 += record.duration_ms;
-                steady.1 += 1;
-            }
-            _ => {
-                slow.0 += record.duration_ms;
-                slow.1 += 1;
-            }
+        let launcher = record.launcher.trim();
+        if is_hidden_launcher(launcher, ignored) {
+            continue;
         }
+        let entry = by_launcher
+            .entry(launcher.to_ascii_lowercase())
+            .or_insert((launcher, 0, 0));
+        entry.0 = entry.0.min(launcher);
+        entry.1 += record.duration_ms;
+        entry.2 += 1;
     }
 
+    let means: Vec<(&str, u64)> = by_launcher
+        .into_values()
+        .filter(|(_, _, runs)| *runs >= LAUNCHER_MIN_RUNS)
+        .map(|(launcher, total, runs)| (launcher, total / runs as u64))
+        .collect();
+    let (slow, slow_mean) = means.iter().max_by_key(|(_, mean)| *mean)?;
+    let (fast, fast_mean) = means.iter().min_by_key(|(_, mean)| *mean)?;
+    if slow == fast || *fast_mean == 0 {
+        return None;
+    }
+
+    let percent = ((slow_mean - fast_mean) as f64 / *fast_mean as f64 * 100.0).round() as u64;
+    (percent >= LAUNCHER_MIN_SLOWDOWN_PERCENT)
+        .then(|| format!("Starts launched via {slow} are {percent}% slower than via {fast}"))
+}
+
+/// Celebrates how much time recorded starts saved compared with the slowest one, or `None` when nothing was saved.
+pub fn time_saved_insight(records: &[StartupRecord]) -> Option<String> {
+    let saved_ms = time_saved_vs_worst(&chart_samples(records));
+    let saved = if saved_ms >= 60_000 {
+        format!("{:.1} min", saved_ms as f64 / 60_000.0)
+    } else if saved_ms > 0 {
+        format_duration(saved_ms)
+    } else {
+        return None;
+    };
+    Some(format!("You've saved {saved} vs your worst start"))
+}
+
+/// Summarizes runs into fast, steady, slow buckets.
+pub fn compute_category_summary(records: &[StartupRecord]) -> Vec<CategorySummary> {
+    let totals = category_totals(&chart_samples(records));
+
     vec![
         CategorySummary {
+            category: SpeedCategory::Fast,
             name: "Fast starts (<0.5s)",
             class_names: "app__category-name app__category-name--social",
-            summary: summarize_bucket(fast.0, fast.1),
+            summary: summarize_bucket(totals.fast.total_ms, totals.fast.runs),
         },
         CategorySummary {
+            category: SpeedCategory::Steady,
             name: "Steady starts (0.5–1.5s)",
             class_names: "app__category-name app__category-name--utilities",
-            summary: summarize_bucket(steady.0, steady.1),
+            summary: summarize_bucket(totals.steady.total_ms, totals.steady.runs),
         },
         CategorySummary {
+            category: SpeedCategory::Slow,
             name: "Slow starts (>1.5s)",
             class_names: "app__category-name app__category-name--health",
-            summary: summarize_bucket(slow.0, slow.1),
+            summary: summarize_bucket(totals.slow.total_ms, totals.slow.runs),
         },
     ]
 }
 
+/// Bucket width of the detailed breakdown, in milliseconds.
+pub const FINE_BUCKET_MS: u64 = 100;
+/// Runs at or above this many milliseconds share the detailed breakdown's last bucket.
+const FINE_BUCKET_CAP_MS: u64 = 1_000;
+
+/// Summarizes runs into `bucket_ms`-wide buckets up to `FINE_BUCKET_CAP_MS`, plus one for slower runs.
+///
+/// A run exactly on a boundary counts toward the bucket starting there; zero-duration placeholders are skipped.
+/// Every label is in milliseconds, such as "100–200 ms" and "≥1000 ms".
+pub fn compute_category_summary_fine(
+    records: &[StartupRecord],
+    bucket_ms: u64,
+) -> Vec<FineBucketSummary> {
+    let bucket_ms = bucket_ms.clamp(1, FINE_BUCKET_CAP_MS);
+    let bucket_count = FINE_BUCKET_CAP_MS.div_ceil(bucket_ms) as usize;
+    let mut totals = vec![(0u64, 0usize); bucket_count + 1];
+    for record in records.iter().filter(|record| record.duration_ms > 0) {
+        let index = if record.duration_ms >= FINE_BUCKET_CAP_MS {
+            bucket_count
+        } else {
+            (record.duration_ms / bucket_ms) as usize
+        };
+        totals[index].0 += record.duration_ms;
+        totals[index].1 += 1;
+    }
+
+    totals
+        .into_iter()
+        .enumerate()
+        .map(|(index, (total_ms, runs))| {
+            let start_ms = index as u64 * bucket_ms;
+            let name = if index == bucket_count {
+                format!("≥{FINE_BUCKET_CAP_MS} ms")
+            } else {
+                let end_ms = (start_ms + bucket_ms).min(FINE_BUCKET_CAP_MS);
+                format!("{start_ms}–{end_ms} ms")
+            };
+            FineBucketSummary {
+                name,
+                summary: summarize_bucket(total_ms, runs),
+            }
+        })
+        .collect()
+}
+
+/// Keeps only the runs in `category`, or every run without one.
+pub fn filter_by_category(
+    records: &[StartupRecord],
+    category: Option<SpeedCategory>,
+) -> Vec<StartupRecord> {
+    records
+        .iter()
+        .filter(|record| {
+            category.is_none_or(|category| SpeedCategory::of(record.duration_ms) == Some(category))
+        })
+        .cloned()
+        .collect()
+}
+
+/// Keeps only release-build runs when `release_only` is set, so development runs do not skew the stats.
+pub fn filter_by_build_environment(
+    records: &[StartupRecord],
+    release_only: bool,
+) -> Vec<StartupRecord> {
+    records
+        .iter()
+        .filter(|record| !release_only || record.build_environment == RELEASE_ENVIRONMENT)
+        .cloned()
+        .collect()
+}
+
 /// Formats the bucket label with average duration.
 fn summarize_bucket(total_ms: u64, count: usize) -> String {
     if count == 0 {
@@ -90,19 +380,112 @@ fn summarize_bucket(total_ms: u64, count: usize) -> String {
     }
 }
 
-/// Builds the tile grid from the latest runs.
-pub fn compute_tiles(records: &[StartupRecord]) -> Vec<StartupTile> {
+/// Builds the tile grid from the latest runs, showing seconds with `precision` decimals.
+pub fn compute_tiles(records: &[StartupRecord], precision: u32) -> Vec<StartupTile> {
     records
         .iter()
         .take(6)
         .map(|record| StartupTile {
             icon: duration_icon(record.duration_ms),
             label: format_time_of_day(record.recorded_at_ms),
-            duration: format_duration(record.duration_ms),
+            duration: format_duration_with_precision(record.duration_ms, precision),
+            duration_ms: record.duration_ms,
         })
         .collect()
 }
 
+/// Signed difference in milliseconds between the latest run and the one before it.
+pub fn latest_delta(records: &[StartupRecord]) -> Option<i64> {
+    let [latest, previous, ..] = records else {
+        return None;
+    };
+    Some(latest.duration_ms as i64 - previous.duration_ms as i64)
+}
+
+/// Describes a run-over-run delta for the dashboard chip.
+pub fn format_delta(delta_ms: i64) -> String {
+    let magnitude = format_duration(delta_ms.unsigned_abs());
+    match delta_ms.signum() {
+        1 => format!("+{magnitude} slower than last time"),
+        -1 => format!("-{magnitude} faster than last time"),
+        _ => "Same as last time".to_string(),
+    }
+}
+
+/// Runs considered by the trend verdict.
+const TREND_WINDOW: usize = 10;
+/// Fewest runs needed before the trend verdict commits to a direction.
+const TREND_MIN_RUNS: usize = 4;
+/// Change across the window, as a share of the mean, treated as noise.
+const TREND_DEADBAND: f64 = 0.1;
+
+/// Classifies the slope of the last runs' durations, oldest to newest, against a deadband.
+pub fn trend_verdict(records: &[StartupRecord]) -> Trend {
+    let durations: Vec<f64> = records
+        .iter()
+        .take(TREND_WINDOW)
+        .rev()
+        .map(|record| record.duration_ms as f64)
+        .collect();
+    if durations.len() < TREND_MIN_RUNS {
+        return Trend::Unknown;
+    }
+    let Some(slope) = linear_slope(&durations) else {
+        return Trend::Unknown;
+    };
+    let mean = durations.iter().sum::<f64>() / durations.len() as f64;
+    let change = slope * (durations.len() - 1) as f64;
+    if change.abs() <= mean * TREND_DEADBAND {
+        Trend::Stable
+    } else if change > 0.0 {
+        Trend::Regressing
+    } else {
+        Trend::Improving
+    }
+}
+
+/// Playful estimate of the next startup from the recent trend, or `None` with too few runs.
+pub fn startup_forecast_label(records: &[StartupRecord]) -> Option<String> {
+    let forecast_ms = forecast_next(&chart_samples(records), FORECAST_WINDOW)?;
+    Some(format!(
+        "Estimate: tomorrow's first start will likely be ~{:.2} s",
+        forecast_ms as f64 / 1_000.0
+    ))
+}
+
+/// Median startup duration across all records.
+pub fn median_duration_ms(records: &[StartupRecord]) -> Option<u64> {
+    let durations: Vec<u64> = records.iter().map(|record| record.duration_ms).collect();
+    median_ms(&durations)
+}
+
+/// Describes how the user's median startup compares with the community baseline.
+pub fn community_comparison_message(p50_ms: u64, baseline: &CommunityBaseline) -> String {
+    let p50 = format_duration_compact(p50_ms);
+    if !baseline.percentiles.is_empty() {
+        let slower_users = baseline
+            .percentiles
+            .iter()
+            .filter(|duration| **duration > p50_ms)
+            .count();
+        let faster_than = slower_users * 100 / baseline.percentiles.len();
+        return format!("Your p50 ({p50}) is faster than {faster_than}% of users");
+    }
+
+    let median = format_duration_compact(baseline.median_ms);
+    match p50_ms.cmp(&baseline.median_ms) {
+        std::cmp::Ordering::Less => {
+            format!("Your p50 ({p50}) is faster than the community median ({median})")
+        }
+        std::cmp::Ordering::Equal => {
+            format!("Your p50 ({p50}) matches the community median")
+        }
+        std::cmp::Ordering::Greater => {
+            format!("Your p50 ({p50}) is slower than the community median ({median})")
+        }
+    }
+}
+
 /// Chooses an icon matching the duration bucket.
 fn duration_icon(duration_ms: u64) -> &'static str {
     match duration_ms {
@@ -128,25 +511,83 @@ pub fn format_total_duration(total_ms: u64) -> String {
     }
 }
 
-/// Compact human-readable duration.
-pub fn format_duration_compact(ms: u64) -> String {
-    if ms == 0 {
-        "0".to_string()
-    } else if ms >= 60_000 {
-        format!("{:.1} m", ms as f64 / 60_000.0)
-    } else if ms >= 1_000 {
-        format!("{:.1} s", ms as f64 / 1_000.0)
+/// Builds the plain-text startup summary copied from the dashboard.
+pub fn startup_summary_text(records: &[StartupRecord]) -> String {
+    let mut lines = vec!["Startup performance".to_string()];
+    let Some(latest) = records.first() else {
+        lines.push("No runs yet".to_string());
+        return lines.join("\n");
+    };
+
+    let total_ms: u128 = records
+        .iter()
+        .map(|record| record.duration_ms as u128)
+        .sum();
+    let runs_label = if records.len() == 1 { "run" } else { "runs" };
+    lines.push(format!("{} {} recorded", records.len(), runs_label));
+    lines.push(format!("Latest: {}", format_duration(latest.duration_ms)));
+    lines.push(format!(
+        "Total: {}",
+        format_total_duration(total_ms.min(u64::MAX as u128) as u64)
+    ));
+    lines.join("\n")
+}
+
+/// Records ordered oldest first, each paired with its signed change from the previous run.
+///
+/// The oldest run has no predecessor, so its delta is `None`.
+pub fn records_with_deltas(records: &[StartupRecord]) -> Vec<(&StartupRecord, Option<i64>)> {
+    let mut ordered: Vec<&StartupRecord> = records.iter().collect();
+    ordered.sort_by_key(|record| record.recorded_at_ms);
+    let mut previous: Option<u64> = None;
+    ordered
+        .into_iter()
+        .map(|record| {
+            let delta = previous.map(|previous| record.duration_ms as i64 - previous as i64);
+            previous = Some(record.duration_ms);
+            (record, delta)
+        })
+        .collect()
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
     } else {
-        format!("{ms} ms")
+        value.to_string()
     }
 }
 
-pub fn format_duration(ms: u64) -> String {
-    if ms >= 1_000 {
-        format!("{:.2} s", ms as f64 / 1_000.0)
-    } else {
-        format!("{ms} ms")
+/// Builds a CSV of every run, oldest first, optionally with a `delta_ms` column.
+pub fn startup_csv(records: &[StartupRecord], include_delta: bool) -> String {
+    let mut header =
+        "recorded_at_ms,duration_ms,launcher,battery_percent,thermal_pressure".to_string();
+    if include_delta {
+        header.push_str(",delta_ms");
+    }
+    let mut lines = vec![header];
+    for (record, delta) in records_with_deltas(records) {
+        let mut line = format!(
+            "{},{},{},{},{}",
+            record.recorded_at_ms,
+            record.duration_ms,
+            csv_field(&record.launcher),
+            record
+                .battery_percent
+                .map(|percent| percent.to_string())
+                .unwrap_or_default(),
+            record
+                .thermal_pressure
+                .map(|pressure| pressure.to_string())
+                .unwrap_or_default(),
+        );
+        if include_delta {
+            line.push(',');
+            line.push_str(&delta.map(|delta| delta.to_string()).unwrap_or_default());
+        }
+        lines.push(line);
     }
+    lines.join("\n")
 }
 
 /// Formats the timestamp into a locale-aware date string.
@@ -156,11 +597,18 @@ pub fn format_timestamp(ms: u64) -> String {
 }
 
 /// Formats the timestamp into a locale-aware time string.
+#[cfg(target_arch = "wasm32")]
 fn format_time_of_day(ms: u64) -> String {
     let date = Date::new(&JsValue::from_f64(ms as f64));
     Date::to_locale_time_string(&date, "default").into()
 }
 
+/// Formats the timestamp as a UTC time when no browser locale is available, as in native tests.
+#[cfg(not(target_arch = "wasm32"))]
+fn format_time_of_day(ms: u64) -> String {
+    time_wise_shared::chart_data::format_clock_time(ms, 0)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -187,16 +635,28 @@ mod tests {
                 recorded_at_ms: 10,
                 duration_ms: 300,
                 launcher: "test".to_string(),
+                battery_percent: None,
+                thermal_pressure: None,
+                cpu_time_ms: None,
+                build_environment: "release".to_string(),
             },
             StartupRecord {
                 recorded_at_ms: 20,
                 duration_ms: 800,
                 launcher: "test".to_string(),
+                battery_percent: None,
+                thermal_pressure: None,
+                cpu_time_ms: None,
+                build_environment: "release".to_string(),
             },
             StartupRecord {
                 recorded_at_ms: 30,
                 duration_ms: 2_200,
                 launcher: "test".to_string(),
+                battery_percent: None,
+                thermal_pressure: None,
+                cpu_time_ms: None,
+                build_environment: "release".to_string(),
             },
         ];
 
@@ -212,6 +672,495 @@ mod tests {
         assert_eq!(summary[2].summary, "2.20 s avg · 1 run");
     }
 
+    #[test]
+    fn compute_category_summary_fine_puts_boundary_runs_in_the_upper_bucket() {
+        let records = records_with_durations(&[0, 99, 100, 199, 999, 1_000, 2_500]);
+
+        let summary = compute_category_summary_fine(&records, 100);
+
+        assert_eq!(summary.len(), 11);
+        assert_eq!(summary[0].name, "0–100 ms");
+        assert_eq!(summary[0].summary, "99 ms avg · 1 run");
+        assert_eq!(summary[1].name, "100–200 ms");
+        assert_eq!(summary[1].summary, "149 ms avg · 2 runs");
+        assert_eq!(summary[2].summary, "No runs yet");
+        assert_eq!(summary[9].name, "900–1000 ms");
+        assert_eq!(summary[9].summary, "999 ms avg · 1 run");
+        assert_eq!(summary[10].name, "≥1000 ms");
+        assert_eq!(summary[10].summary, "1.75 s avg · 2 runs");
+    }
+
+    #[test]
+    fn compute_category_summary_fine_ends_the_last_bucket_at_the_cap() {
+        let summary = compute_category_summary_fine(&records_with_durations(&[950]), 300);
+
+        assert_eq!(summary.len(), 5);
+        assert_eq!(summary[3].name, "900–1000 ms");
+        assert_eq!(summary[3].summary, "950 ms avg · 1 run");
+    }
+
+    #[test]
+    fn compute_category_summary_fine_labels_every_bucket_in_milliseconds() {
+        let names: Vec<String> = compute_category_summary_fine(&[], 100)
+            .into_iter()
+            .map(|bucket| bucket.name)
+            .collect();
+
+        assert_eq!(
+            names,
+            [
+                "0–100 ms",
+                "100–200 ms",
+                "200–300 ms",
+                "300–400 ms",
+                "400–500 ms",
+                "500–600 ms",
+                "600–700 ms",
+                "700–800 ms",
+                "800–900 ms",
+                "900–1000 ms",
+                "≥1000 ms",
+            ]
+        );
+    }
+
+    #[test]
+    fn filter_by_category_keeps_only_runs_in_the_bucket() {
+        let records = records_with_durations(&[0, 300, 500, 501, 1_500, 1_501, 4_000]);
+        let durations = |category| -> Vec<u64> {
+            filter_by_category(&records, category)
+                .iter()
+                .map(|record| record.duration_ms)
+                .collect()
+        };
+
+        assert_eq!(durations(Some(SpeedCategory::Fast)), vec![300, 500]);
+        assert_eq!(durations(Some(SpeedCategory::Steady)), vec![501, 1_500]);
+        assert_eq!(durations(Some(SpeedCategory::Slow)), vec![1_501, 4_000]);
+        assert_eq!(durations(None).len(), records.len());
+    }
+
+    #[test]
+    fn filter_by_build_environment_drops_development_runs_when_release_only() {
+        let mut records = records_with_durations(&[300, 400, 500]);
+        records[1].build_environment = "dev".to_string();
+        records[2].build_environment = "debug".to_string();
+
+        let release: Vec<_> = filter_by_build_environment(&records, true)
+            .iter()
+            .map(|record| record.duration_ms)
+            .collect();
+        assert_eq!(release, vec![300]);
+        assert_eq!(filter_by_build_environment(&records, false).len(), 3);
+    }
+
+    #[test]
+    fn compute_category_summary_ignores_zero_duration_placeholders() {
+        let records = records_with_durations(&[0, 300, 0, 400]);
+
+        let summary = compute_category_summary(&records);
+
+        assert_eq!(summary[0].summary, "350 ms avg · 2 runs");
+    }
+
+    fn records_with_durations(durations: &[u64]) -> Vec<StartupRecord> {
+        durations
+            .iter()
+            .enumerate()
+            .map(|(index, duration_ms)| StartupRecord {
+                recorded_at_ms: 1_000 - index as u64,
+                duration_ms: *duration_ms,
+                launcher: "test".to_string(),
+                battery_percent: None,
+                thermal_pressure: None,
+                cpu_time_ms: None,
+                build_environment: "release".to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn compute_chart_points_respects_recent_bar_count() {
+        let records = records_with_durations(&[100, 200, 300]);
+        let points = compute_chart_points(&records[..0], 10);
+        assert_eq!(points.len(), 10);
+        assert!(points.iter().all(|point| point.value == 0));
+
+        let values: Vec<_> = compute_chart_points(&records[..2], 3)
+            .into_iter()
+            .map(|point| point.value)
+            .collect();
+        assert_eq!(values, vec![0, 200, 100]);
+    }
+
+    #[test]
+    fn compute_distribution_points_respects_bucket_count() {
+        let records = records_with_durations(&[100, 250, 500, 990, 1_000, 1_000]);
+
+        let points = compute_distribution_points(&records, 4);
+        let counts: Vec<_> = points.iter().map(|point| point.value).collect();
+        assert_eq!(counts, vec![1, 1, 1, 3]);
+
+        let six = compute_distribution_points(&records, 6);
+        assert_eq!(six.len(), 6);
+        assert_eq!(six.iter().map(|point| point.value).sum::<u64>(), 6);
+    }
+
+    #[test]
+    fn compute_distribution_points_handles_empty_history() {
+        let points = compute_distribution_points(&[], 6);
+        assert_eq!(points.len(), 6);
+        assert!(points.iter().all(|point| point.value == 0));
+    }
+
+    #[test]
+    fn deltas_compare_each_run_with_the_older_one() {
+        // Newest first: 700 ms is the latest run, 1000 ms the older one.
+        let two = records_with_durations(&[700, 1_000]);
+        let deltas: Vec<_> = records_with_deltas(&two)
+            .into_iter()
+            .map(|(record, delta)| (record.duration_ms, delta))
+            .collect();
+        assert_eq!(deltas, vec![(1_000, None), (700, Some(-300))]);
+
+        let single = records_with_durations(&[450]);
+        assert_eq!(records_with_deltas(&single)[0].1, None);
+    }
+
+    #[test]
+    fn startup_csv_adds_delta_column_on_request() {
+        let records = records_with_durations(&[700, 1_000]);
+        assert_eq!(
+            startup_csv(&records, true),
+            "recorded_at_ms,duration_ms,launcher,battery_percent,thermal_pressure,delta_ms\n\
+             999,1000,test,,,\n\
+             1000,700,test,,,-300"
+        );
+        assert_eq!(
+            startup_csv(&records[..1], false),
+            "recorded_at_ms,duration_ms,launcher,battery_percent,thermal_pressure\n1000,700,test,,"
+        );
+    }
+
+    #[test]
+    fn trend_verdict_classifies_direction() {
+        // Records are newest first, so a newest-first decreasing list got slower over time.
+        let slower = records_with_durations(&[1_000, 900, 800, 700, 600, 500]);
+        assert_eq!(trend_verdict(&slower), Trend::Regressing);
+
+        let faster = records_with_durations(&[500, 600, 700, 800, 900, 1_000]);
+        assert_eq!(trend_verdict(&faster), Trend::Improving);
+
+        let flat = records_with_durations(&[800, 810, 790, 805, 795, 800]);
+        assert_eq!(trend_verdict(&flat), Trend::Stable);
+    }
+
+    #[test]
+    fn startup_forecast_label_projects_the_trend() {
+        let improving = records_with_durations(&[450, 600, 750, 900]);
+        assert_eq!(
+            startup_forecast_label(&improving).as_deref(),
+            Some("Estimate: tomorrow's first start will likely be ~0.30 s")
+        );
+        assert_eq!(
+            startup_forecast_label(&records_with_durations(&[450, 600])),
+            None
+        );
+    }
+
+    #[test]
+    fn trend_verdict_needs_enough_runs() {
+        assert_eq!(trend_verdict(&[]), Trend::Unknown);
+        let few = records_with_durations(&[1_000, 500, 100]);
+        assert_eq!(trend_verdict(&few), Trend::Unknown);
+    }
+
+    fn record_with_environment(
+        duration_ms: u64,
+        battery_percent: Option<u8>,
+        thermal_pressure: Option<bool>,
+    ) -> StartupRecord {
+        StartupRecord {
+            recorded_at_ms: 0,
+            duration_ms,
+            launcher: "test".to_string(),
+            battery_percent,
+            thermal_pressure,
+            cpu_time_ms: None,
+            build_environment: "release".to_string(),
+        }
+    }
+
+    #[test]
+    fn cpu_wall_ratio_divides_cpu_time_by_duration() {
+        let mut record = record_with_environment(2_000, None, None);
+        assert_eq!(cpu_wall_ratio(&record), None);
+        assert_eq!(format_cpu_wall_ratio(&record), None);
+
+        record.cpu_time_ms = Some(500);
+        assert_eq!(cpu_wall_ratio(&record), Some(0.25));
+        assert_eq!(
+            format_cpu_wall_ratio(&record).as_deref(),
+            Some("CPU/wall 0.25")
+        );
+
+        record.duration_ms = 0;
+        assert_eq!(cpu_wall_ratio(&record), None);
+    }
+
+    #[test]
+    fn slow_start_correlation_reports_low_battery_majority() {
+        let records = vec![
+            record_with_environment(2_000, Some(12), Some(false)),
+            record_with_environment(2_500, Some(18), None),
+            record_with_environment(3_000, Some(80), Some(false)),
+            record_with_environment(400, Some(5), Some(true)),
+        ];
+        assert_eq!(
+            slow_start_correlation(&records).as_deref(),
+            Some("Slow starts mostly happen under 20% battery")
+        );
+    }
+
+    #[test]
+    fn slow_start_correlation_falls_back_to_thermal_pressure() {
+        let records = vec![
+            record_with_environment(2_000, None, Some(true)),
+            record_with_environment(2_500, Some(90), Some(true)),
+            record_with_environment(3_000, Some(60), Some(false)),
+        ];
+        assert_eq!(
+            slow_start_correlation(&records).as_deref(),
+            Some("Slow starts mostly happen under thermal pressure")
+        );
+    }
+
+    fn record_via(launcher: &str, duration_ms: u64) -> StartupRecord {
+        StartupRecord {
+            recorded_at_ms: 0,
+            duration_ms,
+            launcher: launcher.to_string(),
+            battery_percent: None,
+            thermal_pressure: None,
+            cpu_time_ms: None,
+            build_environment: "release".to_string(),
+        }
+    }
+
+    #[test]
+    fn slow_launcher_insight_compares_launcher_means() {
+        let records = vec![
+            record_via("Terminal", 1_300),
+            record_via("Finder", 1_000),
+            record_via("Terminal", 1_400),
+            record_via("Finder", 900),
+            record_via("Terminal", 1_500),
+            record_via("Finder", 1_100),
+            record_via("unknown", 9_000),
+            record_via("unknown", 9_000),
+            record_via("unknown", 9_000),
+        ];
+        assert_eq!(
+            slow_launcher_insight(&records, &[]).as_deref(),
+            Some("Starts launched via Terminal are 40% slower than via Finder")
+        );
+    }
+
+    #[test]
+    fn slow_launcher_insight_groups_case_and_skips_ignored_launchers() {
+        let records = vec![
+            record_via("Terminal", 1_300),
+            record_via("terminal", 1_400),
+            record_via("TERMINAL", 1_500),
+            record_via("Finder", 1_000),
+            record_via("Finder", 900),
+            record_via("Finder", 1_100),
+            record_via("bash", 100),
+            record_via("bash", 100),
+            record_via("bash", 100),
+        ];
+        assert_eq!(
+            slow_launcher_insight(&records, &["Bash".to_string()]).as_deref(),
+            Some("Starts launched via TERMINAL are 40% slower than via Finder")
+        );
+    }
+
+    #[test]
+    fn slow_launcher_insight_needs_enough_runs_and_a_real_gap() {
+        let few_runs = vec![
+            record_via("Terminal", 3_000),
+            record_via("Finder", 1_000),
+            record_via("Finder", 1_000),
+            record_via("Finder", 1_000),
+        ];
+        assert_eq!(slow_launcher_insight(&few_runs, &[]), None);
+
+        let close = vec![
+            record_via("Terminal", 1_100),
+            record_via("Terminal", 1_100),
+            record_via("Terminal", 1_100),
+            record_via("Finder", 1_000),
+            record_via("Finder", 1_000),
+            record_via("Finder", 1_000),
+        ];
+        assert_eq!(slow_launcher_insight(&close, &[]), None);
+        assert_eq!(slow_launcher_insight(&[], &[]), None);
+    }
+
+    #[test]
+    fn time_saved_insight_sums_gaps_to_the_slowest_start() {
+        let records = vec![
+            record_via("Finder", 100_000),
+            record_via("Finder", 352_000),
+            record_via("Finder", 100_000),
+        ];
+        assert_eq!(
+            time_saved_insight(&records).as_deref(),
+            Some("You've saved 8.4 min vs your worst start")
+        );
+        assert_eq!(
+            time_saved_insight(&[record_via("Finder", 800), record_via("Finder", 1_000)])
+                .as_deref(),
+            Some("You've saved 200 ms vs your worst start")
+        );
+        assert_eq!(time_saved_insight(&[record_via("Finder", 1_000)]), None);
+    }
+
+    #[test]
+    fn slow_start_correlation_needs_known_conditions() {
+        let records = vec![
+            record_with_environment(2_000, None, None),
+            record_with_environment(2_500, Some(50), Some(false)),
+        ];
+        assert_eq!(slow_start_correlation(&records), None);
+        assert_eq!(slow_start_correlation(&[]), None);
+    }
+
+    #[test]
+    fn launcher_donut_gradient_stacks_slices_in_order() {
+        let share = |launcher: &str, count, fraction| LauncherShare {
+            launcher: launcher.to_string(),
+            count,
+            fraction,
+        };
+        let shares = [
+            share("Finder", 2, 0.5),
+            share("Dock", 1, 0.25),
+            share("Terminal", 1, 0.25),
+        ];
+        assert_eq!(
+            launcher_donut_gradient(&shares),
+            "conic-gradient(#7c3aed 0.0% 50.0%, #6366f1 50.0% 75.0%, #0ea5e9 75.0% 100.0%)"
+        );
+        assert_eq!(launcher_donut_color(LAUNCHER_DONUT_COLORS.len()), "#7c3aed");
+    }
+
+    #[test]
+    fn chart_svg_renders_bars_labels_and_annotations() {
+        let points = vec![
+            ChartPoint {
+                label: "09:00".to_string(),
+                value: 2_000,
+            },
+            ChartPoint {
+                label: "<1s".to_string(),
+                value: 1_000,
+            },
+        ];
+
+        let svg = chart_svg(&points, 2_000, ChartMode::Recent);
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.ends_with("</svg>"));
+        assert!(svg.contains(">2.0 s</text>"));
+        assert!(svg.contains(">1.0 s</text>"));
+        assert!(svg.contains(">0</text>"));
+        assert!(svg.contains(">09:00</text>"));
+        assert!(svg.contains(">&lt;1s</text>"));
+        assert!(svg.contains("y=\"12.0\" width=\"87.6\" height=\"144.0\""));
+        assert!(svg.contains("y=\"84.0\" width=\"87.6\" height=\"72.0\""));
+        assert!(!svg.contains("class="));
+    }
+
+    #[test]
+    fn latest_delta_is_signed_against_previous_run() {
+        assert_eq!(
+            latest_delta(&records_with_durations(&[1_320, 1_000])),
+            Some(320)
+        );
+        assert_eq!(
+            latest_delta(&records_with_durations(&[800, 1_000, 5])),
+            Some(-200)
+        );
+        assert_eq!(latest_delta(&records_with_durations(&[900, 900])), Some(0));
+    }
+
+    #[test]
+    fn latest_delta_needs_two_records() {
+        assert_eq!(latest_delta(&records_with_durations(&[900])), None);
+        assert_eq!(latest_delta(&[]), None);
+    }
+
+    #[test]
+    fn format_delta_describes_direction() {
+        assert_eq!(format_delta(320), "+320 ms slower than last time");
+        assert_eq!(format_delta(-200), "-200 ms faster than last time");
+        assert_eq!(format_delta(0), "Same as last time");
+    }
+
+    #[test]
+    fn median_duration_handles_odd_even_and_empty() {
+        assert_eq!(
+            median_duration_ms(&records_with_durations(&[300, 100, 200])),
+            Some(200)
+        );
+        assert_eq!(
+            median_duration_ms(&records_with_durations(&[100, 400])),
+            Some(250)
+        );
+        assert_eq!(median_duration_ms(&[]), None);
+    }
+
+    #[test]
+    fn community_comparison_uses_percentiles_when_available() {
+        let baseline = CommunityBaseline {
+            median_ms: 1_300,
+            percentiles: vec![
+                600, 800, 900, 1_000, 1_200, 1_300, 1_500, 1_800, 2_200, 3_000,
+            ],
+        };
+        assert_eq!(
+            community_comparison_message(1_100, &baseline),
+            "Your p50 (1.1 s) is faster than 60% of users"
+        );
+    }
+
+    #[test]
+    fn community_comparison_falls_back_to_median() {
+        let baseline = CommunityBaseline {
+            median_ms: 1_300,
+            percentiles: Vec::new(),
+        };
+        assert_eq!(
+            community_comparison_message(900, &baseline),
+            "Your p50 (900 ms) is faster than the community median (1.3 s)"
+        );
+        assert_eq!(
+            community_comparison_message(1_300, &baseline),
+            "Your p50 (1.3 s) matches the community median"
+        );
+        assert_eq!(
+            community_comparison_message(2_000, &baseline),
+            "Your p50 (2.0 s) is slower than the community median (1.3 s)"
+        );
+    }
+
+    #[test]
+    fn format_chart_annotation_depends_on_mode() {
+        assert_eq!(format_chart_annotation(1_200, ChartMode::Recent), "1.2 s");
+        assert_eq!(format_chart_annotation(12, ChartMode::Distribution), "12");
+    }
+
     #[test]
     fn duration_icon_matches_duration_bucket() {
         assert_eq!(duration_icon(100), "⚡");
@@ -236,9 +1185,50 @@ mod tests {
         assert_eq!(format_duration_compact(120_000), "2.0 m");
     }
 
+    #[test]
+    fn startup_summary_text_lists_latest_and_total() {
+        let records = vec![
+            StartupRecord {
+                recorded_at_ms: 20,
+                duration_ms: 1_200,
+                launcher: "test".to_string(),
+                battery_percent: None,
+                thermal_pressure: None,
+                cpu_time_ms: None,
+                build_environment: "release".to_string(),
+            },
+            StartupRecord {
+                recorded_at_ms: 10,
+                duration_ms: 300,
+                launcher: "test".to_string(),
+                battery_percent: None,
+                thermal_pressure: None,
+                cpu_time_ms: None,
+                build_environment: "release".to_string(),
+            },
+        ];
+
+        assert_eq!(
+            startup_summary_text(&records),
+            "Startup performance\n2 runs recorded\nLatest: 1.20 s\nTotal: 1.5 s"
+        );
+        assert_eq!(
+            startup_summary_text(&[]),
+            "Startup performance\nNo runs yet"
+        );
+    }
+
     #[test]
     fn format_duration_retains_precision_for_seconds() {
         assert_eq!(format_duration(500), "500 ms");
         assert_eq!(format_duration(2_345), "2.35 s");
     }
+
+    #[test]
+    fn format_duration_with_precision_controls_second_decimals() {
+        assert_eq!(format_duration_with_precision(1_234, 0), "1 s");
+        assert_eq!(format_duration_with_precision(1_234, 1), "1.2 s");
+        assert_eq!(format_duration_with_precision(1_234, 2), "1.23 s");
+        assert_eq!(format_duration_with_precision(640, 0), "640 ms");
+    }
 }
@@ -0,0 +1,48 @@
+//! Compares the running version with the latest published one.
+
+/// Parses `1.2.3`, `v1.2`, or `1.2.3-beta` into numeric parts, ignoring any pre-release or build suffix.
+fn parse_version(raw: &str) -> Option<(u64, u64, u64)> {
+    let trimmed = raw.trim();
+    let trimmed = trimmed.strip_prefix(['v', 'V']).unwrap_or(trimmed);
+    let core = trimmed.split(['-', '+']).next()?;
+    let mut parts = core.split('.').map(|part| part.parse::<u64>().ok());
+    let major = parts.next()??;
+    let minor = parts.next().unwrap_or(Some(0))?;
+    let patch = parts.next().unwrap_or(Some(0))?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((major, minor, patch))
+}
+
+/// Whether `latest` is newer than `current`, or `None` when either version cannot be parsed.
+pub fn is_newer_version(latest: &str, current: &str) -> Option<bool> {
+    Some(parse_version(latest)? > parse_version(current)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn newer_versions_are_detected() {
+        assert_eq!(is_newer_version("0.2.0", "0.1.9"), Some(true));
+        assert_eq!(is_newer_version("v1.0", "0.9.12"), Some(true));
+        assert_eq!(is_newer_version("0.1.10", "0.1.9"), Some(true));
+    }
+
+    #[test]
+    fn older_and_equal_versions_are_not_updates() {
+        assert_eq!(is_newer_version("0.1.0", "0.2.0"), Some(false));
+        assert_eq!(is_newer_version("0.1.0", "0.1.0"), Some(false));
+        assert_eq!(is_newer_version("0.1.0-beta", "0.1.0"), Some(false));
+    }
+
+    #[test]
+    fn malformed_versions_are_rejected() {
+        assert_eq!(is_newer_version("", "0.1.0"), None);
+        assert_eq!(is_newer_version("latest", "0.1.0"), None);
+        assert_eq!(is_newer_version("1.2.3.4", "0.1.0"), None);
+        assert_eq!(is_newer_version("1.x", "0.1.0"), None);
+    }
+}
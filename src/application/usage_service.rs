@@ -1,24 +1,123 @@
-use crate::application::startup_service::format_duration;
+use js_sys::Date;
+use time_wise_shared::usage_rounding::{round_ms, UsageRounding};
+
 #[cfg(target_arch = "wasm32")]
 use crate::application::startup_service::format_timestamp;
 use crate::domain::app_usage_record::AppUsageRecord;
+use crate::domain::budget_usage::BudgetUsage;
+use crate::domain::preferences::{TileLabelSource, UsageSort};
+use crate::domain::tracking_coverage::TrackingCoverage;
 use crate::presentation::models::UsageTile;
 
-/// Builds the usage tiles shown in the dashboard from the recorder output.
-pub fn compute_usage_tiles(records: &[AppUsageRecord]) -> Vec<UsageTile> {
-    let mut items: Vec<_> = records.iter().collect();
+/// How long an inactive application still counts as "recently inactive".
+pub const RECENTLY_INACTIVE_WINDOW_MS: u64 = 30 * 60 * 1_000;
+
+/// Filter applied to the dashboard usage list.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum UsageFilter {
+    #[default]
+    All,
+    Active,
+    RecentlyInactive,
+}
+
+impl UsageFilter {
+    pub const ALL: [UsageFilter; 3] = [
+        UsageFilter::All,
+        UsageFilter::Active,
+        UsageFilter::RecentlyInactive,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            UsageFilter::All => "All",
+            UsageFilter::Active => "Active",
+            UsageFilter::RecentlyInactive => "Recently inactive",
+        }
+    }
+
+    /// Returns whether the record passes the filter at `now_ms`.
+    pub fn matches(self, record: &AppUsageRecord, now_ms: u64) -> bool {
+        match self {
+            UsageFilter::All => true,
+            UsageFilter::Active => record.active,
+            UsageFilter::RecentlyInactive => {
+                !record.active
+                    && now_ms.saturating_sub(record.last_seen_at_ms) <= RECENTLY_INACTIVE_WINDOW_MS
+            }
+        }
+    }
+}
+
+/// Keeps only the records matching the filter.
+///
+/// Header counts are computed from the unfiltered records so they always describe the whole desktop.
+pub fn filter_usage_records(
+    records: &[AppUsageRecord],
+    filter: UsageFilter,
+    now_ms: u64,
+) -> Vec<AppUsageRecord> {
+    records
+        .iter()
+        .filter(|record| filter.matches(record, now_ms))
+        .cloned()
+        .collect()
+}
+
+/// Picks the tile label: the window title when preferred and captured, else the process name.
+pub fn usage_tile_label(record: &AppUsageRecord, source: TileLabelSource) -> String {
+    let title = match source {
+        TileLabelSource::Name => None,
+        TileLabelSource::WindowTitle => record
+            .window_title
+            .as_deref()
+            .map(str::trim)
+            .filter(|title| !title.is_empty()),
+    };
+    title.unwrap_or(&record.name).to_string()
+}
+
+/// Builds the usage tiles shown in the dashboard from the recorder output, leaving out apps no longer running unless `show_inactive`.
+pub fn compute_usage_tiles(
+    records: &[AppUsageRecord],
+    label_source: TileLabelSource,
+    sort: UsageSort,
+    show_inactive: bool,
+    limit: usize,
+    rounding: UsageRounding,
+) -> Vec<UsageTile> {
+    let mut items: Vec<_> = records
+        .iter()
+        .filter(|record| show_inactive || record.active)
+        .collect();
     items.sort_by(|a, b| {
-        b.active
-            .cmp(&a.active)
-            .then_with(|| b.total_active_ms.cmp(&a.total_active_ms))
-            .then_with(|| b.last_seen_at_ms.cmp(&a.last_seen_at_ms))
+        let by_total = || b.total_active_ms.cmp(&a.total_active_ms);
+        let by_recent = || b.last_seen_at_ms.cmp(&a.last_seen_at_ms);
+        let ordering = match sort {
+            UsageSort::ActiveFirst => b
+                .active
+                .cmp(&a.active)
+                .then_with(by_total)
+                .then_with(by_recent),
+            UsageSort::TotalTime => by_total().then_with(by_recent),
+            UsageSort::Recent => by_recent().then_with(by_total),
+            UsageSort::Name => a
+                .name
+                .to_lowercase()
+                .cmp(&b.name.to_lowercase())
+                .then_with(by_total),
+        };
+        // Records tied on every key keep a stable order, so tiles do not swap between refreshes.
+        ordering.then_with(|| a.name.cmp(&b.name))
     });
     items
         .into_iter()
-        .take(6)
+        .take(limit)
         .map(|record| UsageTile {
             name: record.name.clone(),
-            duration: format_duration(record.total_active_ms),
+            label: usage_tile_label(record, label_source),
+            duration: format_usage_duration(round_ms(record.total_active_ms, rounding)),
+            total_ms: record.total_active_ms,
             subtitle: if record.active {
                 "Active now".to_string()
             } else {
@@ -29,6 +128,49 @@ pub fn compute_usage_tiles(records: &[AppUsageRecord]) -> Vec<UsageTile> {
         .collect()
 }
 
+/// Formats accumulated app usage as "6h 12m", "45m", or "30s".
+pub fn format_usage_duration(ms: u64) -> String {
+    let total_seconds = ms / 1_000;
+    let hours = total_seconds / 3_600;
+    let minutes = (total_seconds % 3_600) / 60;
+    if hours > 0 {
+        if minutes == 0 {
+            format!("{hours}h")
+        } else {
+            format!("{hours}h {minutes}m")
+        }
+    } else if minutes > 0 {
+        format!("{minutes}m")
+    } else if ms > 0 && total_seconds == 0 {
+        "<1s".to_string()
+    } else {
+        format!("{total_seconds}s")
+    }
+}
+
+/// Builds the plain-text usage summary copied from the dashboard.
+pub fn usage_summary_text(
+    records: &[AppUsageRecord],
+    label_source: TileLabelSource,
+    sort: UsageSort,
+    limit: usize,
+    rounding: UsageRounding,
+) -> String {
+    let tiles = compute_usage_tiles(records, label_source, sort, true, limit, rounding);
+    let mut lines = vec!["Desktop usage".to_string()];
+    if tiles.is_empty() {
+        lines.push("No desktop activity yet".to_string());
+    }
+    lines.extend(tiles.into_iter().map(|tile| {
+        if tile.active {
+            format!("{} — {} (active)", tile.label, tile.duration)
+        } else {
+            format!("{} — {}", tile.label, tile.duration)
+        }
+    }));
+    lines.join("\n")
+}
+
 /// Counts applications that are currently marked active.
 pub fn active_app_count(records: &[AppUsageRecord]) -> usize {
     records.iter().filter(|record| record.active).count()
@@ -42,6 +184,66 @@ pub fn latest_usage_timestamp(records: &[AppUsageRecord]) -> Option<String> {
         .map(|record| format_last_seen_human(record.last_seen_at_ms))
 }
 
+/// Share of the run time matched by summed app usage, capped at 100%.
+pub fn coverage_percent(run_ms: u64, tracked_ms: u64) -> u8 {
+    if run_ms == 0 {
+        return 0;
+    }
+    let percent = (tracked_ms as f64 / run_ms as f64 * 100.0).round();
+    percent.min(100.0) as u8
+}
+
+/// Formats the diagnostics line describing today's tracking coverage.
+pub fn coverage_label(coverage: &TrackingCoverage) -> String {
+    format!(
+        "Tracked {}% of today",
+        coverage_percent(coverage.run_ms, coverage.tracked_ms)
+    )
+}
+
+/// Share of a daily budget used so far, capped at 100% for the progress bar.
+pub fn budget_percent(usage: &BudgetUsage) -> u8 {
+    if usage.budget_ms == 0 {
+        return 0;
+    }
+    let percent = (usage.used_ms as f64 / usage.budget_ms as f64 * 100.0).floor();
+    percent.min(100.0) as u8
+}
+
+/// Formats budget progress as "45m of 1h".
+pub fn budget_label(usage: &BudgetUsage) -> String {
+    format!(
+        "{} of {}",
+        format_usage_duration(usage.used_ms),
+        format_usage_duration(usage.budget_ms)
+    )
+}
+
+/// Formats the busiest-hour insight, such as "You're most active at 10:00".
+pub fn busiest_hour_label(hour: u8) -> String {
+    format!("You're most active at {:02}:00", hour.min(23))
+}
+
+/// Returns the start of the current local day, which begins at `rollover_hour`, in epoch milliseconds.
+pub fn start_of_today_ms(rollover_hour: u32) -> u64 {
+    let rollover_hour = rollover_hour.min(23);
+    let date = Date::new_0();
+    if date.get_hours() < rollover_hour {
+        // Before the rollover the day that started yesterday is still running.
+        date.set_date(date.get_date() - 1);
+    }
+    date.set_hours(rollover_hour);
+    date.set_minutes(0);
+    date.set_seconds(0);
+    date.set_milliseconds(0);
+    date.get_time().max(0.0) as u64
+}
+
+/// Returns the local UTC offset in minutes east of UTC, as the backend's `tz_offset` expects.
+pub fn local_utc_offset_minutes() -> i32 {
+    -(Date::new_0().get_timezone_offset() as i32)
+}
+
 fn format_last_active_label(last_seen_ms: u64) -> String {
     format!("Last active {}", format_last_seen_human(last_seen_ms))
 }
@@ -70,9 +272,16 @@ mod tests {
             last_seen_at_ms: last_seen,
             first_seen_at_ms: last_seen.saturating_sub(1_000),
             active,
+            window_title: None,
         }
     }
 
+    #[test]
+    fn busiest_hour_label_pads_the_hour() {
+        assert_eq!(busiest_hour_label(9), "You're most active at 09:00");
+        assert_eq!(busiest_hour_label(22), "You're most active at 22:00");
+    }
+
     #[test]
     fn compute_usage_tiles_prioritizes_active_records() {
         let records = vec![
@@ -81,7 +290,14 @@ mod tests {
             record("Music", true, 300, 40),
         ];
 
-        let tiles = compute_usage_tiles(&records);
+        let tiles = compute_usage_tiles(
+            &records,
+            TileLabelSource::Name,
+            UsageSort::ActiveFirst,
+            true,
+            6,
+            UsageRounding::None,
+        );
         assert_eq!(tiles.len(), 3);
         assert_eq!(tiles[0].name, "Code");
         assert!(tiles[0].active);
@@ -91,6 +307,221 @@ mod tests {
         assert!(!tiles[2].active);
     }
 
+    #[test]
+    fn compute_usage_tiles_orders_ties_by_name() {
+        let names = |records: &[AppUsageRecord]| {
+            compute_usage_tiles(
+                records,
+                TileLabelSource::Name,
+                UsageSort::ActiveFirst,
+                true,
+                6,
+                UsageRounding::None,
+            )
+            .into_iter()
+            .map(|tile| tile.name)
+            .collect::<Vec<_>>()
+        };
+        let records = vec![record("Zed", true, 0, 30), record("Atom", true, 0, 30)];
+        let reversed: Vec<_> = records.iter().rev().cloned().collect();
+
+        assert_eq!(names(&records), vec!["Atom", "Zed"]);
+        assert_eq!(names(&reversed), vec!["Atom", "Zed"]);
+    }
+
+    #[test]
+    fn compute_usage_tiles_can_hide_inactive_records() {
+        let records = vec![
+            record("Mail", false, 800, 20),
+            record("Code", true, 1_200, 50),
+            record("Music", true, 300, 40),
+            record("Notes", false, 100, 10),
+        ];
+        let tiles = |show_inactive| {
+            compute_usage_tiles(
+                &records,
+                TileLabelSource::Name,
+                UsageSort::TotalTime,
+                show_inactive,
+                6,
+                UsageRounding::None,
+            )
+        };
+
+        assert_eq!(tiles(true).len(), 4);
+        let active_only = tiles(false);
+        assert_eq!(active_only.len(), 2);
+        assert!(active_only.iter().all(|tile| tile.active));
+    }
+
+    #[test]
+    fn compute_usage_tiles_follows_each_sort_mode() {
+        let records = vec![
+            record("mail", false, 800, 60),
+            record("Code", true, 1_200, 50),
+            record("Music", true, 300, 40),
+            record("Browser", false, 2_000, 10),
+        ];
+        let order = |sort| {
+            compute_usage_tiles(
+                &records,
+                TileLabelSource::Name,
+                sort,
+                true,
+                6,
+                UsageRounding::None,
+            )
+            .into_iter()
+            .map(|tile| tile.name)
+            .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            order(UsageSort::ActiveFirst),
+            vec!["Code", "Music", "Browser", "mail"]
+        );
+        assert_eq!(
+            order(UsageSort::TotalTime),
+            vec!["Browser", "Code", "mail", "Music"]
+        );
+        assert_eq!(
+            order(UsageSort::Recent),
+            vec!["mail", "Code", "Music", "Browser"]
+        );
+        assert_eq!(
+            order(UsageSort::Name),
+            vec!["Browser", "Code", "mail", "Music"]
+        );
+    }
+
+    #[test]
+    fn compute_usage_tiles_honors_the_limit() {
+        let records: Vec<_> = (0..10)
+            .map(|index| record(&format!("App {index}"), true, 1_000 - index * 10, 10))
+            .collect();
+        let names = |limit| {
+            compute_usage_tiles(
+                &records,
+                TileLabelSource::Name,
+                UsageSort::TotalTime,
+                true,
+                limit,
+                UsageRounding::None,
+            )
+            .into_iter()
+            .map(|tile| tile.name)
+            .collect::<Vec<_>>()
+        };
+
+        assert_eq!(names(3), vec!["App 0", "App 1", "App 2"]);
+        assert_eq!(names(8).len(), 8);
+        assert_eq!(names(30).len(), 10);
+    }
+
+    #[test]
+    fn compute_usage_tiles_rounds_only_the_displayed_duration() {
+        let records = vec![record("Code", true, 90_000, 10)];
+        let tile = |rounding| {
+            compute_usage_tiles(
+                &records,
+                TileLabelSource::Name,
+                UsageSort::ActiveFirst,
+                true,
+                6,
+                rounding,
+            )
+            .remove(0)
+        };
+
+        assert_eq!(tile(UsageRounding::None).duration, "1m");
+        let rounded = tile(UsageRounding::Minute);
+        assert_eq!(rounded.duration, "2m");
+        assert_eq!(rounded.total_ms, 90_000);
+    }
+
+    #[test]
+    fn usage_summary_text_lists_tiles_in_display_order() {
+        let records = vec![
+            record("Mail", false, 800, 20),
+            record("Code", true, 1_200, 50),
+        ];
+
+        assert_eq!(
+            usage_summary_text(
+                &records,
+                TileLabelSource::Name,
+                UsageSort::ActiveFirst,
+                6,
+                UsageRounding::None
+            ),
+            "Desktop usage\nCode — 1s (active)\nMail — <1s"
+        );
+        assert_eq!(
+            usage_summary_text(
+                &[],
+                TileLabelSource::Name,
+                UsageSort::ActiveFirst,
+                6,
+                UsageRounding::None
+            ),
+            "Desktop usage\nNo desktop activity yet"
+        );
+    }
+
+    #[test]
+    fn usage_filter_all_keeps_every_record() {
+        let records = vec![
+            record("Mail", false, 800, 20),
+            record("Code", true, 1_200, 50),
+        ];
+        let filtered = filter_usage_records(&records, UsageFilter::All, 100);
+        assert_eq!(filtered.len(), 2);
+    }
+
+    #[test]
+    fn usage_filter_active_keeps_running_apps() {
+        assert!(UsageFilter::Active.matches(&record("Code", true, 1_200, 50), 100));
+        assert!(!UsageFilter::Active.matches(&record("Mail", false, 800, 20), 100));
+    }
+
+    #[test]
+    fn usage_filter_recently_inactive_respects_window() {
+        let now_ms = RECENTLY_INACTIVE_WINDOW_MS + 10_000;
+        let recent = record("Mail", false, 800, 10_000);
+        let stale = record("Music", false, 800, 9_999);
+        let running = record("Code", true, 1_200, now_ms);
+
+        assert!(UsageFilter::RecentlyInactive.matches(&recent, now_ms));
+        assert!(!UsageFilter::RecentlyInactive.matches(&stale, now_ms));
+        assert!(!UsageFilter::RecentlyInactive.matches(&running, now_ms));
+    }
+
+    #[test]
+    fn format_usage_duration_prefers_hours_and_minutes() {
+        assert_eq!(format_usage_duration(0), "0s");
+        assert_eq!(format_usage_duration(400), "<1s");
+        assert_eq!(format_usage_duration(30_000), "30s");
+        assert_eq!(format_usage_duration(59_999), "59s");
+        assert_eq!(format_usage_duration(45 * 60_000 + 20_000), "45m");
+        assert_eq!(format_usage_duration(2 * 3_600_000), "2h");
+        assert_eq!(format_usage_duration(6 * 3_600_000 + 12 * 60_000), "6h 12m");
+        assert_eq!(format_usage_duration(21_600_000), "6h");
+    }
+
+    #[test]
+    fn coverage_percent_rounds_and_caps_at_hundred() {
+        assert_eq!(coverage_percent(0, 500), 0);
+        assert_eq!(coverage_percent(1_000, 870), 87);
+        assert_eq!(coverage_percent(1_000, 1_500), 100);
+        assert_eq!(
+            coverage_label(&TrackingCoverage {
+                run_ms: 3_000,
+                tracked_ms: 1_000,
+            }),
+            "Tracked 33% of today"
+        );
+    }
+
     #[test]
     fn active_app_count_counts_active_entries() {
         let records = vec![
@@ -110,4 +541,60 @@ mod tests {
         let timestamp = latest_usage_timestamp(&records);
         assert!(timestamp.is_some());
     }
+
+    #[test]
+    fn budget_progress_caps_bar_and_labels_usage() {
+        let usage = BudgetUsage {
+            name: "Game".to_string(),
+            used_ms: 45 * 60_000,
+            budget_ms: 60 * 60_000,
+        };
+        assert_eq!(budget_percent(&usage), 75);
+        assert_eq!(budget_label(&usage), "45m of 1h");
+
+        let over = BudgetUsage {
+            used_ms: 90 * 60_000,
+            ..usage
+        };
+        assert_eq!(budget_percent(&over), 100);
+    }
+
+    #[test]
+    fn usage_tile_label_prefers_title_only_when_chosen_and_captured() {
+        let untitled = record("code", true, 1_000, 10);
+        let titled = AppUsageRecord {
+            window_title: Some("main.rs — Code".to_string()),
+            ..untitled.clone()
+        };
+        let blank_title = AppUsageRecord {
+            window_title: Some("  ".to_string()),
+            ..untitled.clone()
+        };
+
+        assert_eq!(usage_tile_label(&untitled, TileLabelSource::Name), "code");
+        assert_eq!(usage_tile_label(&titled, TileLabelSource::Name), "code");
+        assert_eq!(
+            usage_tile_label(&untitled, TileLabelSource::WindowTitle),
+            "code"
+        );
+        assert_eq!(
+            usage_tile_label(&titled, TileLabelSource::WindowTitle),
+            "main.rs — Code"
+        );
+        assert_eq!(
+            usage_tile_label(&blank_title, TileLabelSource::WindowTitle),
+            "code"
+        );
+
+        let tiles = compute_usage_tiles(
+            &[titled],
+            TileLabelSource::WindowTitle,
+            UsageSort::ActiveFirst,
+            true,
+            6,
+            UsageRounding::None,
+        );
+        assert_eq!(tiles[0].name, "code");
+        assert_eq!(tiles[0].label, "main.rs — Code");
+    }
 }
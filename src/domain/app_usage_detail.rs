@@ -0,0 +1,13 @@
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AppUsageDetail {
+    pub name: String,
+    pub total_ms: u64,
+    pub launch_count: u64,
+    pub longest_session_ms: u64,
+    pub average_session_ms: u64,
+    pub first_seen_ms: u64,
+    pub last_seen_ms: u64,
+}
@@ -9,4 +9,7 @@ pub struct AppUsageRecord {
     pub last_seen_at_ms: u64,
     pub first_seen_at_ms: u64,
     pub active: bool,
+    /// Title of the app's main window, when title capture recorded one.
+    #[serde(default)]
+    pub window_title: Option<String>,
 }
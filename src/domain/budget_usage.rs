@@ -0,0 +1,9 @@
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetUsage {
+    pub name: String,
+    pub used_ms: u64,
+    pub budget_ms: u64,
+}
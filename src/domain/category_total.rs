@@ -0,0 +1,8 @@
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryTotal {
+    pub category: String,
+    pub total_ms: u64,
+}
@@ -0,0 +1,11 @@
+use serde::Deserialize;
+
+/// Anonymized startup statistics shared by other users on the same OS.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CommunityBaseline {
+    pub median_ms: u64,
+    /// Startup durations at evenly spaced ranks, fastest first (e.g. deciles).
+    #[serde(default)]
+    pub percentiles: Vec<u64>,
+}
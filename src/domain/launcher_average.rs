@@ -0,0 +1,9 @@
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LauncherAverage {
+    pub launcher: String,
+    pub average_ms: u64,
+    pub runs: u64,
+}
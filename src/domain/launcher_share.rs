@@ -0,0 +1,9 @@
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LauncherShare {
+    pub launcher: String,
+    pub count: usize,
+    pub fraction: f64,
+}
@@ -1,2 +1,12 @@
+pub mod app_usage_detail;
 pub mod app_usage_record;
+pub mod budget_usage;
+pub mod category_total;
+pub mod community_baseline;
+pub mod launcher_average;
+pub mod launcher_share;
+pub mod preferences;
 pub mod startup_record;
+pub mod today_glance;
+pub mod tracking_coverage;
+pub mod usage_category;
@@ -0,0 +1,5 @@
+pub use time_wise_shared::preferences::{
+    BackupSchedule, Preferences, StartupMeasurement, StartupMilestone, TileLabelSource, UsageSort,
+    WeekStart, AVATAR_ICON,
+};
+pub use time_wise_shared::usage_rounding::UsageRounding;
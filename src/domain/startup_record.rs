@@ -1,8 +1,21 @@
 use serde::Deserialize;
+use time_wise_shared::build_environment::UNKNOWN_ENVIRONMENT;
 
 #[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
 pub struct StartupRecord {
     pub recorded_at_ms: u64,
     pub duration_ms: u64,
     pub launcher: String,
+    #[serde(default)]
+    pub battery_percent: Option<u8>,
+    #[serde(default)]
+    pub thermal_pressure: Option<bool>,
+    #[serde(default)]
+    pub cpu_time_ms: Option<u64>,
+    #[serde(default = "unknown_build_environment")]
+    pub build_environment: String,
+}
+
+fn unknown_build_environment() -> String {
+    UNKNOWN_ENVIRONMENT.to_string()
 }
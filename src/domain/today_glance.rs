@@ -0,0 +1,10 @@
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TodayGlance {
+    pub launches_today: u64,
+    pub usage_today_ms: u64,
+    pub active_app: Option<String>,
+    pub last_startup_ms: Option<u64>,
+}
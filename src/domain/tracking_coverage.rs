@@ -0,0 +1,8 @@
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TrackingCoverage {
+    pub run_ms: u64,
+    pub tracked_ms: u64,
+}
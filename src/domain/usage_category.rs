@@ -0,0 +1,11 @@
+use serde::Deserialize;
+
+use crate::domain::app_usage_record::AppUsageRecord;
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageCategory {
+    pub category: String,
+    pub total_ms: u64,
+    pub apps: Vec<AppUsageRecord>,
+}
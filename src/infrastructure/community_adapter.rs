@@ -0,0 +1,37 @@
+//! Fetches the opt-in community startup baseline from a configured HTTP endpoint.
+
+use wasm_bindgen_futures::JsFuture;
+use web_sys::window;
+
+use crate::domain::community_baseline::CommunityBaseline;
+use crate::infrastructure::http_adapter::{fetch_with_timeout, network_error};
+use crate::infrastructure::tauri_adapter::AdapterError;
+
+/// Short OS identifier sent to the baseline endpoint.
+pub fn current_os() -> &'static str {
+    let platform = window()
+        .and_then(|win| win.navigator().platform().ok())
+        .unwrap_or_default();
+    if platform.starts_with("Mac") {
+        "macos"
+    } else if platform.starts_with("Win") {
+        "windows"
+    } else {
+        "linux"
+    }
+}
+
+/// Requests the community baseline for `os`, aborting after a fixed timeout.
+pub async fn fetch_community_baseline(
+    endpoint: &str,
+    os: &str,
+) -> Result<CommunityBaseline, AdapterError> {
+    let separator = if endpoint.contains('?') { '&' } else { '?' };
+    let url = format!("{endpoint}{separator}os={os}");
+    let response = fetch_with_timeout(&url).await?;
+
+    let body = JsFuture::from(response.json().map_err(network_error)?)
+        .await
+        .map_err(network_error)?;
+    serde_wasm_bindgen::from_value(body).map_err(|err| AdapterError::Network(err.to_string()))
+}
@@ -0,0 +1,42 @@
+//! Plain HTTP requests from the webview, shared by the opt-in network features.
+
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{window, AbortController, RequestInit, Response};
+
+use crate::infrastructure::tauri_adapter::AdapterError;
+
+const REQUEST_TIMEOUT_MILLIS: i32 = 5_000;
+
+/// Wraps a JavaScript failure as a network error.
+pub fn network_error(err: JsValue) -> AdapterError {
+    AdapterError::Network(format!("{err:?}"))
+}
+
+/// Fetches `url`, aborting after a fixed timeout and rejecting non-success statuses.
+pub async fn fetch_with_timeout(url: &str) -> Result<Response, AdapterError> {
+    let win = window().ok_or_else(|| AdapterError::Network("missing window".to_string()))?;
+
+    let controller = AbortController::new().map_err(network_error)?;
+    let init = RequestInit::new();
+    init.set_signal(Some(&controller.signal()));
+    let abort = Closure::once_into_js(move || controller.abort());
+    win.set_timeout_with_callback_and_timeout_and_arguments_0(
+        abort.unchecked_ref(),
+        REQUEST_TIMEOUT_MILLIS,
+    )
+    .map_err(network_error)?;
+
+    let response: Response = JsFuture::from(win.fetch_with_str_and_init(url, &init))
+        .await
+        .and_then(|value| value.dyn_into())
+        .map_err(network_error)?;
+    if !response.ok() {
+        return Err(AdapterError::Network(format!(
+            "{url} returned {}",
+            response.status()
+        )));
+    }
+    Ok(response)
+}
@@ -0,0 +1,248 @@
+//! Typed access to the startup and app usage commands exposed by the desktop backend.
+
+use crate::domain::{
+    app_usage_detail::AppUsageDetail, app_usage_record::AppUsageRecord, budget_usage::BudgetUsage,
+    category_total::CategoryTotal, launcher_average::LauncherAverage,
+    launcher_share::LauncherShare, startup_record::StartupRecord, today_glance::TodayGlance,
+    tracking_coverage::TrackingCoverage, usage_category::UsageCategory,
+};
+use crate::infrastructure::tauri_adapter::{call, call_without_args, AdapterError};
+
+/// Loads startup records, most recent first.
+pub async fn load_startup_records() -> Result<Vec<StartupRecord>, AdapterError> {
+    let mut records: Vec<StartupRecord> = call_without_args("fetch_startup_records").await?;
+    records.sort_by_key(|record| std::cmp::Reverse(record.recorded_at_ms));
+    Ok(records)
+}
+
+/// Loads app usage records, active and longest-running apps first.
+pub async fn load_app_usage_records() -> Result<Vec<AppUsageRecord>, AdapterError> {
+    let mut records: Vec<AppUsageRecord> = call_without_args("fetch_app_usage_records").await?;
+    sort_app_usage_records(&mut records);
+    Ok(records)
+}
+
+pub async fn load_usage_tree() -> Result<Vec<UsageCategory>, AdapterError> {
+    call_without_args("fetch_usage_tree").await
+}
+
+/// Loads today's usage per category, largest first.
+pub async fn load_usage_by_category() -> Result<Vec<CategoryTotal>, AdapterError> {
+    call_without_args("fetch_usage_by_category").await
+}
+
+#[derive(serde::Serialize)]
+struct RelabelLauncherPayload<'a> {
+    from: &'a str,
+    to: &'a str,
+}
+
+pub async fn relabel_launcher(from: &str, to: &str) -> Result<usize, AdapterError> {
+    call("relabel_launcher", &RelabelLauncherPayload { from, to }).await
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DeleteStartupRecordPayload {
+    recorded_at_ms: u64,
+}
+
+/// Deletes the startup record written at `recorded_at_ms`, returning whether one was removed.
+pub async fn delete_startup_record(recorded_at_ms: u64) -> Result<bool, AdapterError> {
+    call(
+        "delete_startup_record",
+        &DeleteStartupRecordPayload { recorded_at_ms },
+    )
+    .await
+}
+
+/// Restores the most recently deleted startup record, returning whether one was restored.
+pub async fn undo_delete_startup_record() -> Result<bool, AdapterError> {
+    call_without_args("undo_delete_startup_record").await
+}
+
+/// Loads average startup per launcher, slowest first, without ignored launchers.
+pub async fn load_launcher_averages() -> Result<Vec<LauncherAverage>, AdapterError> {
+    call_without_args("fetch_launcher_averages").await
+}
+
+/// Loads each launcher's share of all starts, most-used first, without ignored launchers.
+pub async fn load_launcher_distribution() -> Result<Vec<LauncherShare>, AdapterError> {
+    call_without_args("fetch_launcher_distribution").await
+}
+
+/// Loads why the latest startup could not be recorded, or `None` while recording works.
+pub async fn load_record_failure() -> Result<Option<String>, AdapterError> {
+    call_without_args("last_record_failure").await
+}
+
+/// Reports whether the latest startup is the first one recorded today.
+pub async fn load_first_launch_today() -> Result<bool, AdapterError> {
+    call_without_args("is_first_launch_today").await
+}
+
+/// Loads the shareable one-line startup summary.
+pub async fn load_startup_headline() -> Result<String, AdapterError> {
+    call_without_args("startup_headline").await
+}
+
+/// Loads the wall-clock time at which the current app run started.
+pub async fn load_session_start_ms() -> Result<u64, AdapterError> {
+    call_without_args("session_start_ms").await
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TrackingCoveragePayload {
+    since_ms: u64,
+}
+
+pub async fn load_tracking_coverage(since_ms: u64) -> Result<TrackingCoverage, AdapterError> {
+    call(
+        "fetch_tracking_coverage",
+        &TrackingCoveragePayload { since_ms },
+    )
+    .await
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BusiestHourPayload {
+    start_ms: u64,
+    end_ms: u64,
+}
+
+/// Loads the local hour with the most usage in `[start_ms, end_ms)` and its total, if any.
+pub async fn load_busiest_hour(
+    start_ms: u64,
+    end_ms: u64,
+) -> Result<Option<(u8, u64)>, AdapterError> {
+    call(
+        "fetch_busiest_hour",
+        &BusiestHourPayload { start_ms, end_ms },
+    )
+    .await
+}
+
+#[derive(serde::Serialize)]
+struct StaleAppsPayload {
+    days: u32,
+}
+
+/// Loads every app the category table can assign, including ones not tracked recently, sorted by name.
+pub async fn load_categorizable_apps() -> Result<Vec<String>, AdapterError> {
+    call_without_args("fetch_categorizable_apps").await
+}
+
+pub async fn load_stale_apps(days: u32) -> Result<Vec<(String, u64)>, AdapterError> {
+    call("fetch_stale_apps", &StaleAppsPayload { days }).await
+}
+
+/// Loads today's usage for every app with a daily budget.
+pub async fn load_budget_usage() -> Result<Vec<BudgetUsage>, AdapterError> {
+    call_without_args("fetch_budget_usage").await
+}
+
+#[derive(serde::Serialize)]
+struct UsageByWeekdayPayload<'a> {
+    app: &'a str,
+}
+
+/// Loads an app's stored usage per local weekday, Monday first.
+pub async fn load_usage_by_weekday(app: &str) -> Result<[u64; 7], AdapterError> {
+    call("fetch_usage_by_weekday", &UsageByWeekdayPayload { app }).await
+}
+
+#[derive(serde::Serialize)]
+struct AppUsageDetailPayload<'a> {
+    name: &'a str,
+}
+
+/// Loads totals and session stats for one app, or `None` when it was never seen.
+pub async fn load_app_usage_detail(name: &str) -> Result<Option<AppUsageDetail>, AdapterError> {
+    call("fetch_app_usage_detail", &AppUsageDetailPayload { name }).await
+}
+
+/// Loads an app's stored usage per local day over the last two weeks, oldest first.
+pub async fn load_daily_usage(app: &str) -> Result<Vec<(u64, u64)>, AdapterError> {
+    call("fetch_daily_usage", &UsageByWeekdayPayload { app }).await
+}
+
+/// Loads the current usage streak, in days, of each pinned app that has one.
+pub async fn load_usage_streaks() -> Result<Vec<(String, usize)>, AdapterError> {
+    call_without_args("fetch_usage_streaks").await
+}
+
+/// Loads today's launches, usage, current app, and latest startup in one call.
+pub async fn load_today_glance() -> Result<TodayGlance, AdapterError> {
+    call_without_args("fetch_today_glance").await
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FocusScorePayload {
+    tz_offset: i32,
+}
+
+/// Loads today's 0–100 steady-use score, with the day taken in the zone `tz_offset` minutes east of UTC.
+pub async fn load_focus_score(tz_offset: i32) -> Result<u8, AdapterError> {
+    call("fetch_focus_score", &FocusScorePayload { tz_offset }).await
+}
+
+/// Vacuums the backend databases, returning the bytes reclaimed.
+pub async fn vacuum_database() -> Result<u64, AdapterError> {
+    call_without_args("vacuum_database").await
+}
+
+fn sort_app_usage_records(records: &mut [AppUsageRecord]) {
+    records.sort_by(|a, b| {
+        b.active
+            .cmp(&a.active)
+            .then_with(|| b.total_active_ms.cmp(&a.total_active_ms))
+            .then_with(|| b.last_seen_at_ms.cmp(&a.last_seen_at_ms))
+    });
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(
+        name: &str,
+        active: bool,
+        total_active_ms: u64,
+        last_seen_at_ms: u64,
+    ) -> AppUsageRecord {
+        AppUsageRecord {
+            name: name.to_string(),
+            executable: None,
+            total_active_ms,
+            last_seen_at_ms,
+            first_seen_at_ms: 0,
+            active,
+            window_title: None,
+        }
+    }
+
+    #[test]
+    fn sort_app_usage_records_prioritizes_active_then_duration_then_recent() {
+        let mut records = vec![
+            record("inactive-long", false, 5_000, 1_000),
+            record("active-short", true, 1_000, 2_000),
+            record("active-long", true, 10_000, 500),
+            record("inactive-recent", false, 5_000, 10_000),
+        ];
+
+        sort_app_usage_records(&mut records);
+
+        let names: Vec<_> = records.iter().map(|entry| entry.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "active-long",
+                "active-short",
+                "inactive-recent",
+                "inactive-long"
+            ]
+        );
+    }
+}
@@ -1 +1,6 @@
+pub mod community_adapter;
+pub mod http_adapter;
+pub mod metrics_adapter;
+pub mod preferences_adapter;
 pub mod tauri_adapter;
+pub mod update_adapter;
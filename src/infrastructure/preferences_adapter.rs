@@ -0,0 +1,130 @@
+//! Typed access to the preferences stored by the desktop backend.
+
+use crate::domain::preferences::Preferences;
+use crate::infrastructure::tauri_adapter::{call, call_without_args, AdapterError};
+
+/// Loads every preference in a single round-trip.
+pub async fn fetch_preferences() -> Result<Preferences, AdapterError> {
+    call_without_args("fetch_preferences").await
+}
+
+#[derive(serde::Serialize)]
+struct PreferencePayload<'a, T: serde::Serialize> {
+    key: &'a str,
+    value: &'a T,
+}
+
+/// Updates one preference and returns the full stored set.
+pub async fn set_preference<T>(key: &str, value: &T) -> Result<Preferences, AdapterError>
+where
+    T: serde::Serialize,
+{
+    call("set_preference", &PreferencePayload { key, value }).await
+}
+
+/// Restores every preference to its default and returns the defaults.
+pub async fn reset_preferences() -> Result<Preferences, AdapterError> {
+    call_without_args("reset_preferences").await
+}
+
+#[derive(serde::Serialize)]
+struct MinSessionPayload {
+    seconds: u32,
+}
+
+/// Sets the shortest app session that counts toward usage, applied immediately, and returns the stored set.
+pub async fn set_min_session_seconds(seconds: u32) -> Result<Preferences, AdapterError> {
+    call("set_min_session_seconds", &MinSessionPayload { seconds }).await
+}
+
+/// Writes a backup to the configured folder now and returns the preferences with its time.
+pub async fn back_up_now() -> Result<Preferences, AdapterError> {
+    call_without_args("back_up_now").await
+}
+
+/// Adds every currently running app to the allowlist and returns how many were new.
+pub async fn seed_allowlist_from_current() -> Result<usize, AdapterError> {
+    call_without_args("seed_allowlist_from_current").await
+}
+
+/// Loads the dashboard view selected last time.
+pub async fn fetch_last_view() -> Result<String, AdapterError> {
+    call_without_args("get_last_view").await
+}
+
+#[derive(serde::Serialize)]
+struct LastViewPayload<'a> {
+    view: &'a str,
+}
+
+/// Remembers the selected dashboard view, returning the stored value.
+pub async fn set_last_view(view: &str) -> Result<String, AdapterError> {
+    call("set_last_view", &LastViewPayload { view }).await
+}
+
+#[derive(serde::Serialize)]
+struct AppBudgetPayload<'a> {
+    name: &'a str,
+    minutes: u32,
+}
+
+/// Sets an app's daily budget in minutes, removing it when zero, and returns the stored set.
+pub async fn set_app_budget(name: &str, minutes: u32) -> Result<Preferences, AdapterError> {
+    call("set_app_budget", &AppBudgetPayload { name, minutes }).await
+}
+
+#[derive(serde::Serialize)]
+struct AppCategoryPayload<'a> {
+    name: &'a str,
+    category: &'a str,
+}
+
+/// Assigns an app to a category, clearing it when blank, and returns the stored set.
+pub async fn set_app_category(name: &str, category: &str) -> Result<Preferences, AdapterError> {
+    call("set_app_category", &AppCategoryPayload { name, category }).await
+}
+
+#[derive(serde::Serialize)]
+struct AppCategoriesPayload<'a> {
+    assignments: &'a [(String, String)],
+}
+
+/// Assigns many apps to categories in one call and returns how many were applied.
+pub async fn set_app_categories(assignments: &[(String, String)]) -> Result<usize, AdapterError> {
+    call("set_app_categories", &AppCategoriesPayload { assignments }).await
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AutostartPausePayload {
+    until_ms: Option<u64>,
+}
+
+/// Suspends launch-on-boot until `until_ms`, or resumes it with `None`, and returns the stored preferences.
+pub async fn set_autostart_paused_until(
+    until_ms: Option<u64>,
+) -> Result<Preferences, AdapterError> {
+    call(
+        "set_autostart_paused_until",
+        &AutostartPausePayload { until_ms },
+    )
+    .await
+}
+
+#[derive(serde::Serialize)]
+struct LauncherIgnoredPayload<'a> {
+    launcher: &'a str,
+    ignored: bool,
+}
+
+/// Adds a launcher to, or removes it from, the set left out of launcher stats.
+pub async fn set_launcher_ignored(
+    launcher: &str,
+    ignored: bool,
+) -> Result<Preferences, AdapterError> {
+    call(
+        "set_launcher_ignored",
+        &LauncherIgnoredPayload { launcher, ignored },
+    )
+    .await
+}
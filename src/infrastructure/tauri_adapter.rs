@@ -1,9 +1,29 @@
 use js_sys::{Function, Promise, Reflect};
+use wasm_bindgen::closure::Closure;
 use wasm_bindgen::{JsCast, JsValue};
 use wasm_bindgen_futures::JsFuture;
 use web_sys::{console, window};
 
-use crate::domain::{app_usage_record::AppUsageRecord, startup_record::StartupRecord};
+/// Failure talking to the desktop backend.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AdapterError {
+    /// The command arguments could not be serialized for the bridge.
+    Payload(String),
+    /// The bridge is unavailable or the command itself failed.
+    Command(String),
+    /// An HTTP request failed, timed out, or returned an unexpected body.
+    Network(String),
+}
+
+impl std::fmt::Display for AdapterError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AdapterError::Payload(message) => write!(f, "invalid command payload: {message}"),
+            AdapterError::Command(message) => f.write_str(message),
+            AdapterError::Network(message) => write!(f, "network error: {message}"),
+        }
+    }
+}
 
 async fn invoke_command_with<T>(command: &str, payload: JsValue) -> Result<T, JsValue>
 where
@@ -44,11 +64,94 @@ where
     serde_wasm_bindgen::from_value(response).map_err(|err| JsValue::from_str(&err.to_string()))
 }
 
-async fn invoke_command<T>(command: &str) -> Result<T, JsValue>
+/// Invokes a backend command with serialized arguments, logging any failure.
+pub(crate) async fn call<T, P>(command: &str, payload: &P) -> Result<T, AdapterError>
 where
     T: serde::de::DeserializeOwned,
+    P: serde::Serialize + ?Sized,
 {
-    invoke_command_with(command, JsValue::UNDEFINED).await
+    let payload = serde_wasm_bindgen::to_value(payload).map_err(|err| {
+        log_error(&format!("failed to serialize {command} payload: {err}"));
+        AdapterError::Payload(err.to_string())
+    })?;
+    invoke_logged(command, payload).await
+}
+
+/// Invokes a backend command that takes no arguments, logging any failure.
+pub(crate) async fn call_without_args<T>(command: &str) -> Result<T, AdapterError>
+where
+    T: serde::de::DeserializeOwned,
+{
+    invoke_logged(command, JsValue::UNDEFINED).await
+}
+
+async fn invoke_logged<T>(command: &str, payload: JsValue) -> Result<T, AdapterError>
+where
+    T: serde::de::DeserializeOwned,
+{
+    invoke_command_with(command, payload).await.map_err(|err| {
+        log_error(&format!("{command} failed: {err:?}"));
+        AdapterError::Command(
+            err.as_string()
+                .unwrap_or_else(|| format!("{command} failed")),
+        )
+    })
+}
+
+/// Event emitted by the backend whenever the dashboard window is shown.
+pub const WINDOW_SHOWN_EVENT: &str = "window-shown";
+
+/// Event emitted by the backend once this run's startup time has been stored.
+pub const STARTUP_RECORDED_EVENT: &str = "startup-recorded";
+
+/// Event emitted by the backend after every preference was reset at once.
+pub const PREFERENCES_CHANGED_EVENT: &str = "preferences-changed";
+
+/// Registers `handler` for a backend event for the lifetime of the page.
+pub fn listen_event<F>(event: &str, mut handler: F)
+where
+    F: FnMut() + 'static,
+{
+    let listen = window()
+        .ok_or_else(|| JsValue::from_str("missing window"))
+        .and_then(|window| Reflect::get(&window, &JsValue::from_str("__TAURI__")))
+        .and_then(|tauri| Reflect::get(&tauri, &JsValue::from_str("event")))
+        .and_then(|events| {
+            let listen = Reflect::get(&events, &JsValue::from_str("listen"))?;
+            Ok((events, listen.dyn_into::<Function>()?))
+        });
+    let (events, listen) = match listen {
+        Ok(listen) => listen,
+        Err(err) => {
+            log_error(&format!("failed to listen for {event}: {err:?}"));
+            return;
+        }
+    };
+
+    let callback =
+        Closure::wrap(Box::new(move |_payload: JsValue| handler()) as Box<dyn FnMut(JsValue)>);
+    let registration = match listen.call2(
+        &events,
+        &JsValue::from_str(event),
+        callback.as_ref().unchecked_ref(),
+    ) {
+        Ok(registration) => registration,
+        Err(err) => {
+            log_error(&format!("failed to listen for {event}: {err:?}"));
+            return;
+        }
+    };
+    callback.forget();
+
+    // `listen` resolves asynchronously, so a denied permission only shows up as a rejection.
+    if let Ok(promise) = registration.dyn_into::<Promise>() {
+        let event = event.to_string();
+        wasm_bindgen_futures::spawn_local(async move {
+            if let Err(err) = JsFuture::from(promise).await {
+                log_error(&format!("failed to listen for {event}: {err:?}"));
+            }
+        });
+    }
 }
 
 #[derive(Clone, Copy)]
@@ -62,129 +165,133 @@ struct AutostartPayload {
     enabled: bool,
 }
 
-pub async fn fetch_autostart_enabled() -> Result<bool, ()> {
-    match invoke_command::<bool>("get_autostart_enabled").await {
-        Ok(value) => Ok(value),
-        Err(err) => {
-            log_error(&format!("failed to fetch autostart state: {err:?}"));
-            Err(())
-        }
-    }
+pub async fn fetch_autostart_enabled() -> Result<bool, AdapterError> {
+    call_without_args("get_autostart_enabled").await
 }
 
 async fn autostart_status_from_fetch(fallback: bool) -> AutostartStatus {
-    match fetch_autostart_enabled().await {
-        Ok(value) => AutostartStatus {
-            enabled: value,
-            success: false,
-        },
-        Err(_) => AutostartStatus {
-            enabled: fallback,
-            success: false,
-        },
+    AutostartStatus {
+        enabled: fetch_autostart_enabled().await.unwrap_or(fallback),
+        success: false,
     }
 }
 
 pub async fn set_autostart_enabled(enabled: bool) -> AutostartStatus {
-    let payload = match serde_wasm_bindgen::to_value(&AutostartPayload { enabled }) {
-        Ok(payload) => payload,
-        Err(err) => {
-            log_error(&format!("failed to serialize autostart payload: {err}"));
-            return autostart_status_from_fetch(enabled).await;
-        }
-    };
-
-    match invoke_command_with::<bool>("set_autostart_enabled", payload).await {
+    match call::<bool, _>("set_autostart_enabled", &AutostartPayload { enabled }).await {
         Ok(value) => AutostartStatus {
             enabled: value,
             success: value == enabled,
         },
-        Err(err) => {
-            log_error(&format!("failed to update autostart state: {err:?}"));
-            autostart_status_from_fetch(enabled).await
-        }
+        Err(_) => autostart_status_from_fetch(enabled).await,
     }
 }
 
-pub async fn load_startup_records() -> Vec<StartupRecord> {
-    match invoke_command::<Vec<StartupRecord>>("fetch_startup_records").await {
-        Ok(mut records) => {
-            records.sort_by_key(|record| std::cmp::Reverse(record.recorded_at_ms));
-            records
-        }
-        Err(err) => {
-            log_error(&format!("failed to fetch startup records: {err:?}"));
-            Vec::new()
-        }
-    }
+pub async fn fetch_window_effects_active() -> bool {
+    call_without_args("fetch_window_effects_active")
+        .await
+        .unwrap_or(false)
 }
 
-pub async fn load_app_usage_records() -> Result<Vec<AppUsageRecord>, String> {
-    match invoke_command::<Vec<AppUsageRecord>>("fetch_app_usage_records").await {
-        Ok(mut records) => {
-            sort_app_usage_records(&mut records);
-            Ok(records)
-        }
-        Err(err) => {
-            log_error(&format!("failed to fetch app usage records: {err:?}"));
-            Err(format!("failed to fetch app usage records: {err:?}"))
-        }
-    }
+/// Loads the names of the connected monitors.
+pub async fn load_monitors() -> Result<Vec<String>, AdapterError> {
+    call_without_args("list_monitors").await
 }
 
-fn log_error(message: &str) {
-    console::error_1(&JsValue::from_str(message));
+/// Tells the backend the dashboard has rendered, for the first-paint startup metric.
+pub async fn notify_frontend_ready() {
+    let _: Result<(), AdapterError> = call_without_args("frontend_ready").await;
 }
 
-fn sort_app_usage_records(records: &mut [AppUsageRecord]) {
-    records.sort_by(|a, b| {
-        b.active
-            .cmp(&a.active)
-            .then_with(|| b.total_active_ms.cmp(&a.total_active_ms))
-            .then_with(|| b.last_seen_at_ms.cmp(&a.last_seen_at_ms))
-    });
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    fn record(
-        name: &str,
-        active: bool,
-        total_active_ms: u64,
-        last_seen_at_ms: u64,
-    ) -> AppUsageRecord {
-        AppUsageRecord {
-            name: name.to_string(),
-            executable: None,
-            total_active_ms,
-            last_seen_at_ms,
-            first_seen_at_ms: 0,
-            active,
-        }
-    }
+/// Restarts the dashboard's auto-hide countdown after user input.
+pub async fn notify_dashboard_interaction() {
+    let _: Result<(), AdapterError> = call_without_args("note_dashboard_interaction").await;
+}
 
-    #[test]
-    fn sort_app_usage_records_prioritizes_active_then_duration_then_recent() {
-        let mut records = vec![
-            record("inactive-long", false, 5_000, 1_000),
-            record("active-short", true, 1_000, 2_000),
-            record("active-long", true, 10_000, 500),
-            record("inactive-recent", false, 5_000, 10_000),
-        ];
-
-        sort_app_usage_records(&mut records);
-
-        let names: Vec<_> = records.iter().map(|entry| entry.name.as_str()).collect();
-        assert_eq!(
-            names,
-            vec![
-                "active-long",
-                "active-short",
-                "inactive-recent",
-                "inactive-long"
-            ]
-        );
-    }
+#[derive(serde::Serialize)]
+struct DataDirPayload<'a> {
+    path: &'a str,
+}
+
+/// Checks that `path` exists or can be created and is writable.
+pub async fn validate_data_dir(path: &str) -> Result<(), AdapterError> {
+    call("validate_data_dir", &DataDirPayload { path }).await
+}
+
+/// Writes startup records, usage, and preferences to a zip bundle at `path`.
+pub async fn export_bundle(path: &str) -> Result<(), AdapterError> {
+    call("export_bundle", &DataDirPayload { path }).await
+}
+
+/// Replaces startup records, usage, and preferences with the zip bundle at `path`.
+pub async fn import_bundle(path: &str) -> Result<(), AdapterError> {
+    call("import_bundle", &DataDirPayload { path }).await
+}
+
+#[derive(serde::Serialize)]
+struct CopyTextPayload<'a> {
+    text: &'a str,
+}
+
+pub async fn copy_text(text: &str) -> Result<(), AdapterError> {
+    call("copy_text", &CopyTextPayload { text }).await
+}
+
+/// Loads how many unexpected exits were detected in the last week, or `None` when not tracked.
+pub async fn load_unclean_exits_this_week() -> Result<Option<usize>, AdapterError> {
+    call_without_args("unclean_exits_this_week").await
+}
+
+/// Loads whether this dashboard is pinned against the tray toggle.
+pub async fn fetch_dashboard_pinned() -> bool {
+    call_without_args("is_dashboard_pinned")
+        .await
+        .unwrap_or(false)
+}
+
+#[derive(serde::Serialize)]
+struct DashboardPinnedPayload {
+    pinned: bool,
+}
+
+/// Pins or unpins this dashboard, returning the stored state.
+pub async fn set_dashboard_pinned(pinned: bool) -> Result<bool, AdapterError> {
+    call("set_dashboard_pinned", &DashboardPinnedPayload { pinned }).await
+}
+
+#[derive(serde::Serialize)]
+struct RecentErrorsPayload {
+    limit: usize,
+}
+
+/// Loads up to `limit` recent backend errors, newest first.
+pub async fn load_recent_errors(limit: usize) -> Result<Vec<String>, AdapterError> {
+    call("recent_errors", &RecentErrorsPayload { limit }).await
+}
+
+/// Empties the backend error log.
+pub async fn clear_error_log() -> Result<(), AdapterError> {
+    call_without_args("clear_error_log").await
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SaveTextFilePayload<'a> {
+    contents: &'a str,
+    file_name: &'a str,
+}
+
+/// Opens the native save dialog and writes `contents`; resolves to `false` when cancelled.
+pub async fn save_text_file(contents: &str, file_name: &str) -> Result<bool, AdapterError> {
+    call(
+        "save_text_file",
+        &SaveTextFilePayload {
+            contents,
+            file_name,
+        },
+    )
+    .await
+}
+
+fn log_error(message: &str) {
+    console::error_1(&JsValue::from_str(message));
 }
@@ -0,0 +1,17 @@
+//! Fetches the latest published version string from a configured HTTP endpoint.
+
+use wasm_bindgen_futures::JsFuture;
+
+use crate::infrastructure::http_adapter::{fetch_with_timeout, network_error};
+use crate::infrastructure::tauri_adapter::AdapterError;
+
+/// Requests the latest version, served as the plain-text response body.
+pub async fn fetch_latest_version(endpoint: &str) -> Result<String, AdapterError> {
+    let response = fetch_with_timeout(endpoint).await?;
+    let body = JsFuture::from(response.text().map_err(network_error)?)
+        .await
+        .map_err(network_error)?;
+    body.as_string()
+        .map(|version| version.trim().to_string())
+        .ok_or_else(|| AdapterError::Network("version response was not text".to_string()))
+}
@@ -3,23 +3,65 @@ mod domain;
 mod infrastructure;
 mod presentation;
 
+use infrastructure::tauri_adapter::notify_frontend_ready;
 use leptos::prelude::*;
+use leptos::task::spawn_local;
 use presentation::dashboard::Dashboard;
+use presentation::glance::Glance;
 use presentation::settings::Settings;
-use web_sys::window;
+use wasm_bindgen::JsValue;
+use web_sys::{console, window};
 
-fn should_render_settings() -> bool {
-    window()
-        .and_then(|win| win.location().search().ok())
-        .map(|query| query.contains("view=settings"))
-        .unwrap_or(false)
+/// Root view chosen from the page URL.
+#[derive(Debug, PartialEq, Eq)]
+enum EntryView {
+    Dashboard,
+    Settings,
+    Glance,
+    /// No `window` exists, so there is no document to mount any view into.
+    Unsupported,
+}
+
+/// Picks the root view from the page's query string, or `Unsupported` when there is no window.
+fn entry_view(query: Option<&str>) -> EntryView {
+    let Some(query) = query else {
+        return EntryView::Unsupported;
+    };
+    if query.contains("view=settings") {
+        EntryView::Settings
+    } else if query.contains("view=glance") {
+        EntryView::Glance
+    } else {
+        EntryView::Dashboard
+    }
 }
 
 fn main() {
     console_error_panic_hook::set_once();
-    if should_render_settings() {
-        mount_to_body(|| view! { <Settings /> });
-    } else {
-        mount_to_body(|| view! { <Dashboard /> });
+    let query = window().map(|win| win.location().search().unwrap_or_default());
+    match entry_view(query.as_deref()) {
+        EntryView::Settings => mount_to_body(|| view! { <Settings /> }),
+        EntryView::Glance => mount_to_body(|| view! { <Glance /> }),
+        EntryView::Dashboard => {
+            mount_to_body(|| view! { <Dashboard /> });
+            spawn_local(notify_frontend_ready());
+        }
+        EntryView::Unsupported => console::error_1(&JsValue::from_str(
+            "Time Wise needs a browser window; open it from the desktop app instead",
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entry_view_follows_the_query_and_needs_a_window() {
+        assert_eq!(entry_view(Some("")), EntryView::Dashboard);
+        assert_eq!(entry_view(Some("?view=settings")), EntryView::Settings);
+        assert_eq!(entry_view(Some("?view=glance")), EntryView::Glance);
+        assert_eq!(entry_view(Some("?view=other")), EntryView::Dashboard);
+        assert_eq!(entry_view(None), EntryView::Unsupported);
     }
 }
@@ -1,23 +1,67 @@
 //! Leptos component definitions that render startup metrics fetched from the Tauri backend.
 
+use std::collections::BTreeSet;
+
+use js_sys::Date;
+use leptos::ev;
 use leptos::prelude::*;
 use leptos::task::spawn_local;
 use wasm_bindgen::closure::Closure;
-use wasm_bindgen::{JsCast, JsValue};
-use web_sys::{console, window};
+use wasm_bindgen::JsCast;
+use web_sys::{console, window, KeyboardEvent};
 
 use crate::application::startup_service::{
-    compute_category_summary, compute_chart_points, compute_tiles, format_duration,
-    format_duration_compact, format_timestamp, format_total_duration,
+    chart_svg, community_comparison_message, compute_category_summary,
+    compute_category_summary_fine, compute_chart_points, compute_distribution_points,
+    compute_tiles, filter_by_build_environment, filter_by_category, format_chart_annotation,
+    format_cpu_wall_ratio, format_delta, format_duration_with_precision, format_timestamp,
+    format_total_duration, is_hidden_launcher, latest_delta, launcher_donut_color,
+    launcher_donut_gradient, median_duration_ms, slow_launcher_insight, slow_start_correlation,
+    startup_csv, startup_forecast_label, startup_summary_text, time_saved_insight, trend_verdict,
+    FINE_BUCKET_MS,
 };
 use crate::application::usage_service::{
-    active_app_count, compute_usage_tiles, latest_usage_timestamp,
+    active_app_count, budget_label, budget_percent, busiest_hour_label, compute_usage_tiles,
+    filter_usage_records, format_usage_duration, latest_usage_timestamp, local_utc_offset_minutes,
+    usage_summary_text, UsageFilter,
+};
+use crate::domain::{
+    app_usage_detail::AppUsageDetail,
+    app_usage_record::AppUsageRecord,
+    budget_usage::BudgetUsage,
+    category_total::CategoryTotal,
+    community_baseline::CommunityBaseline,
+    launcher_average::LauncherAverage,
+    launcher_share::LauncherShare,
+    preferences::{Preferences, AVATAR_ICON},
+    startup_record::StartupRecord,
+    usage_category::UsageCategory,
+};
+use crate::infrastructure::community_adapter::{current_os, fetch_community_baseline};
+use crate::infrastructure::metrics_adapter::{
+    delete_startup_record, load_app_usage_detail, load_app_usage_records, load_budget_usage,
+    load_busiest_hour, load_daily_usage, load_first_launch_today, load_focus_score,
+    load_launcher_averages, load_launcher_distribution, load_record_failure, load_session_start_ms,
+    load_startup_headline, load_startup_records, load_usage_by_category, load_usage_by_weekday,
+    load_usage_streaks, load_usage_tree, undo_delete_startup_record,
 };
-use crate::domain::{app_usage_record::AppUsageRecord, startup_record::StartupRecord};
-use crate::infrastructure::tauri_adapter::{load_app_usage_records, load_startup_records};
+use crate::infrastructure::preferences_adapter::{
+    fetch_last_view, fetch_preferences, set_last_view,
+};
+use crate::infrastructure::tauri_adapter::{
+    copy_text, fetch_dashboard_pinned, fetch_window_effects_active, listen_event,
+    notify_dashboard_interaction, save_text_file, set_dashboard_pinned, PREFERENCES_CHANGED_EVENT,
+    STARTUP_RECORDED_EVENT, WINDOW_SHOWN_EVENT,
+};
+use crate::presentation::lifecycle::MountGuard;
+use crate::presentation::models::{ChartMode, SpeedCategory, Trend};
 
 const STARTUP_HISTORY_LIMIT: usize = 5;
 const APP_USAGE_REFRESH_MILLIS: i32 = 15_000;
+/// How long the undo toast stays up after a delete, matching the backend's undo window.
+const UNDO_TOAST_MILLIS: i32 = 10_000;
+/// Days of history the busiest-hour insight looks back over.
+const BUSIEST_HOUR_DAYS: u64 = 30;
 
 /// Returns percentage height style for chart bars.
 fn bar_height(bin: u64, max_bin: u64) -> String {
@@ -29,39 +73,289 @@ fn bar_height(bin: u64, max_bin: u64) -> String {
     format!("height:{height:.0}%")
 }
 
-fn launcher_display_label(launcher: &str) -> Option<String> {
-    let trimmed = launcher.trim();
-    if trimmed.is_empty() || trimmed.eq_ignore_ascii_case("unknown") {
-        None
-    } else {
-        Some(trimmed.to_string())
+/// Hover text with the exact milliseconds behind a formatted duration, when enabled.
+fn raw_ms_title(show_raw: bool, ms: u64) -> Option<String> {
+    show_raw.then(|| format!("{ms} ms"))
+}
+
+/// Badge text for a usage streak of `days` consecutive days.
+fn streak_label(days: usize) -> String {
+    format!("{days}-day streak 🔥")
+}
+
+/// Column labels of the weekday chart, Monday first.
+const WEEKDAY_LABELS: [&str; 7] = ["M", "T", "W", "T", "F", "S", "S"];
+
+/// Launcher shown in a run's subtitle; unknown and ignored launchers get none.
+fn launcher_display_label(launcher: &str, ignored: &[String]) -> Option<String> {
+    (!is_hidden_launcher(launcher, ignored)).then(|| launcher.trim().to_string())
+}
+
+/// Hides the undo toast for `recorded_at_ms` once its window has passed, unless a newer delete replaced it.
+fn hide_undo_toast_later(set_undo_toast: WriteSignal<Option<u64>>, recorded_at_ms: u64) {
+    let Some(win) = window() else {
+        return;
+    };
+    let hide = Closure::once_into_js(move || {
+        set_undo_toast.update(|toast| {
+            if *toast == Some(recorded_at_ms) {
+                *toast = None;
+            }
+        });
+    });
+    if let Err(err) = win.set_timeout_with_callback_and_timeout_and_arguments_0(
+        hide.unchecked_ref(),
+        UNDO_TOAST_MILLIS,
+    ) {
+        console::error_1(&err);
     }
 }
 
+/// Toggles the body class that switches the page to a translucent background.
+fn sync_translucent_background() {
+    spawn_local(async move {
+        let active = fetch_window_effects_active().await;
+        if let Some(body) = window()
+            .and_then(|win| win.document())
+            .and_then(|doc| doc.body())
+        {
+            let _ = body
+                .class_list()
+                .toggle_with_force("app--translucent", active);
+        }
+    });
+}
+
+/// Narrowest dashboard width, in CSS pixels, that keeps the full layout.
+const COMPACT_LAYOUT_BREAKPOINT_PX: f64 = 400.0;
+
+/// Whether a dashboard `width_px` wide should collapse to the compact layout.
+fn should_use_compact(width_px: f64) -> bool {
+    width_px < COMPACT_LAYOUT_BREAKPOINT_PX
+}
+
+fn window_inner_width() -> Option<f64> {
+    window()?.inner_width().ok()?.as_f64()
+}
+
+/// Restarts the backend's auto-hide countdown for this dashboard.
+fn note_interaction() {
+    spawn_local(notify_dashboard_interaction());
+}
+
+fn is_copy_shortcut(event: &KeyboardEvent) -> bool {
+    (event.ctrl_key() || event.meta_key()) && event.key().eq_ignore_ascii_case("c")
+}
+
+/// Returns true when the native copy should win: text is selected or an input has focus.
+fn native_copy_pending() -> bool {
+    let Some(win) = window() else {
+        return true;
+    };
+    let editing = win
+        .document()
+        .and_then(|document| document.active_element())
+        .map(|element| matches!(element.tag_name().as_str(), "INPUT" | "TEXTAREA"))
+        .unwrap_or(false);
+    let selected = win
+        .get_selection()
+        .ok()
+        .flatten()
+        .map(|selection| !String::from(selection.to_string()).is_empty())
+        .unwrap_or(false);
+    editing || selected
+}
+
 #[component]
 /// Main dashboard component rendering startup metrics.
 pub fn Dashboard() -> impl IntoView {
     let (startup_records, set_startup_records) = signal(Vec::<StartupRecord>::new());
     let (usage_records, set_usage_records) = signal(Vec::<AppUsageRecord>::new());
     let (loaded, set_loaded) = signal(false);
+    let (usage_loaded, set_usage_loaded) = signal(false);
+    let (usage_filter, set_usage_filter) = signal(UsageFilter::default());
+    let (usage_tree, set_usage_tree) = signal(Vec::<UsageCategory>::new());
+    // Expanded categories, kept across refetches so an open group stays open while it is read.
+    let (open_categories, set_open_categories) = signal(BTreeSet::<String>::new());
+    let (category_totals, set_category_totals) = signal(Vec::<CategoryTotal>::new());
+    let (chart_mode, set_chart_mode) = signal(ChartMode::default());
+    let (preferences, set_preferences) = signal(Preferences::default());
+    let (community_baseline, set_community_baseline) = signal(None::<CommunityBaseline>);
+    let (budget_usage, set_budget_usage) = signal(Vec::<BudgetUsage>::new());
+    let (weekday_usage, set_weekday_usage) = signal(None::<(String, [u64; 7])>);
+    let (daily_usage, set_daily_usage) = signal(Vec::<(u64, u64)>::new());
+    let (app_detail, set_app_detail) = signal(None::<AppUsageDetail>);
+    let (usage_streaks, set_usage_streaks) = signal(Vec::<(String, usize)>::new());
+    let (steady_use_score, set_steady_use_score) = signal(None::<u8>);
+    let (busiest_hour, set_busiest_hour) = signal(None::<u8>);
+    let (session_start_ms, set_session_start_ms) = signal(None::<u64>);
+    let (launcher_averages, set_launcher_averages) = signal(Vec::<LauncherAverage>::new());
+    let (launcher_shares, set_launcher_shares) = signal(Vec::<LauncherShare>::new());
+    let (record_failure, set_record_failure) = signal(None::<String>);
+    let (undo_toast, set_undo_toast) = signal(None::<u64>);
+    let (window_pinned, set_window_pinned) = signal(false);
+    let (selected_category, set_selected_category) = signal(None::<SpeedCategory>);
+    let (detailed_breakdown, set_detailed_breakdown) = signal(false);
+    let (release_only, set_release_only) = signal(false);
+    let (compact, set_compact) = signal(window_inner_width().is_some_and(should_use_compact));
+    let (first_launch_today, set_first_launch_today) = signal(false);
 
-    fn schedule_usage_fetch(setter: WriteSignal<Vec<AppUsageRecord>>) {
+    fn schedule_usage_fetch(
+        setter: WriteSignal<Vec<AppUsageRecord>>,
+        set_usage_loaded: WriteSignal<bool>,
+    ) {
         spawn_local(async move {
-            match load_app_usage_records().await {
-                Ok(records) => setter.set(records),
-                Err(error_message) => {
-                    console::error_1(&JsValue::from_str(&error_message));
-                }
+            if let Ok(records) = load_app_usage_records().await {
+                setter.set(records);
             }
+            set_usage_loaded.set(true);
         });
     }
 
-    schedule_usage_fetch(set_usage_records);
+    fn schedule_budget_fetch(setter: WriteSignal<Vec<BudgetUsage>>) {
+        spawn_local(async move {
+            if let Ok(usage) = load_budget_usage().await {
+                setter.set(usage);
+            }
+        });
+    }
+
+    fn schedule_streak_fetch(setter: WriteSignal<Vec<(String, usize)>>) {
+        spawn_local(async move {
+            if let Ok(streaks) = load_usage_streaks().await {
+                setter.set(streaks);
+            }
+        });
+    }
+
+    fn schedule_steady_use_score_fetch(setter: WriteSignal<Option<u8>>) {
+        spawn_local(async move {
+            if let Ok(score) = load_focus_score(local_utc_offset_minutes()).await {
+                setter.set(Some(score));
+            }
+        });
+    }
+
+    fn schedule_busiest_hour_fetch(setter: WriteSignal<Option<u8>>) {
+        spawn_local(async move {
+            let now_ms = Date::now() as u64;
+            let start_ms = now_ms.saturating_sub(BUSIEST_HOUR_DAYS * 24 * 60 * 60 * 1_000);
+            if let Ok(busiest) = load_busiest_hour(start_ms, now_ms).await {
+                setter.set(busiest.map(|(hour, _)| hour));
+            }
+        });
+    }
+
+    fn schedule_category_totals_fetch(setter: WriteSignal<Vec<CategoryTotal>>) {
+        spawn_local(async move {
+            if let Ok(totals) = load_usage_by_category().await {
+                setter.set(totals);
+            }
+        });
+    }
+
+    fn schedule_usage_tree_fetch(setter: WriteSignal<Vec<UsageCategory>>) {
+        spawn_local(async move {
+            if let Ok(tree) = load_usage_tree().await {
+                setter.set(tree);
+            }
+        });
+    }
+
+    schedule_usage_fetch(set_usage_records, set_usage_loaded);
+    schedule_usage_tree_fetch(set_usage_tree);
+    schedule_category_totals_fetch(set_category_totals);
+    schedule_budget_fetch(set_budget_usage);
+    schedule_streak_fetch(set_usage_streaks);
+    schedule_steady_use_score_fetch(set_steady_use_score);
+    schedule_busiest_hour_fetch(set_busiest_hour);
+    sync_translucent_background();
+    spawn_local(async move {
+        if let Ok(started) = load_session_start_ms().await {
+            set_session_start_ms.set(Some(started));
+        }
+    });
+    spawn_local(async move {
+        set_window_pinned.set(fetch_dashboard_pinned().await);
+    });
+    let mount = MountGuard::for_current_owner();
+    spawn_local({
+        let mount = mount.clone();
+        async move {
+            if let Ok(view) = fetch_last_view().await {
+                mount.set(set_chart_mode, ChartMode::from_key(&view));
+            }
+        }
+    });
+    spawn_local(async move {
+        let Ok(loaded_preferences) = fetch_preferences().await else {
+            return;
+        };
+        let endpoint = loaded_preferences.network_endpoint(
+            loaded_preferences.community_comparison,
+            &loaded_preferences.community_endpoint,
+        );
+        mount.set(set_preferences, loaded_preferences);
+        if let Some(endpoint) = endpoint.filter(|_| mount.is_mounted()) {
+            // Network failures simply leave the comparison hidden.
+            if let Ok(baseline) = fetch_community_baseline(&endpoint, current_os()).await {
+                mount.set(set_community_baseline, Some(baseline));
+            }
+        }
+    });
+
+    listen_event(PREFERENCES_CHANGED_EVENT, move || {
+        sync_translucent_background();
+        spawn_local(async move {
+            if let Ok(stored) = fetch_preferences().await {
+                set_preferences.set(stored);
+            }
+        });
+    });
+
+    // The startup is stored off the main thread, so it can land after the first fetch.
+    listen_event(STARTUP_RECORDED_EVENT, move || {
+        spawn_local(async move {
+            if let Ok(records) = load_startup_records().await {
+                set_startup_records.set(records);
+            }
+        });
+    });
+
+    listen_event(WINDOW_SHOWN_EVENT, move || {
+        schedule_usage_fetch(set_usage_records, set_usage_loaded);
+        schedule_usage_tree_fetch(set_usage_tree);
+        schedule_category_totals_fetch(set_category_totals);
+        schedule_budget_fetch(set_budget_usage);
+        schedule_streak_fetch(set_usage_streaks);
+        schedule_steady_use_score_fetch(set_steady_use_score);
+        schedule_busiest_hour_fetch(set_busiest_hour);
+        spawn_local(async move {
+            if let Ok(records) = load_startup_records().await {
+                set_startup_records.set(records);
+            }
+            if let Ok(averages) = load_launcher_averages().await {
+                set_launcher_averages.set(averages);
+            }
+            if let Ok(shares) = load_launcher_distribution().await {
+                set_launcher_shares.set(shares);
+            }
+            if let Ok(failure) = load_record_failure().await {
+                set_record_failure.set(failure);
+            }
+            if let Ok(first) = load_first_launch_today().await {
+                set_first_launch_today.set(first);
+            }
+        });
+    });
 
     if let Some(win) = window() {
         let setter = set_usage_records;
         let callback = Closure::wrap(Box::new(move || {
-            schedule_usage_fetch(setter);
+            schedule_usage_fetch(setter, set_usage_loaded);
+            schedule_usage_tree_fetch(set_usage_tree);
+            schedule_category_totals_fetch(set_category_totals);
+            schedule_budget_fetch(set_budget_usage);
         }) as Box<dyn FnMut()>);
 
         if let Err(err) = win.set_interval_with_callback_and_timeout_and_arguments_0(
@@ -74,6 +368,35 @@ pub fn Dashboard() -> impl IntoView {
         callback.forget();
     }
 
+    let _resize_listener = window_event_listener(ev::resize, move |_| {
+        if let Some(width) = window_inner_width() {
+            set_compact.set(should_use_compact(width));
+        }
+    });
+
+    let _copy_listener = window_event_listener(ev::keydown, move |event| {
+        if !is_copy_shortcut(&event) || native_copy_pending() {
+            return;
+        }
+        event.prevent_default();
+        let text = [
+            startup_records.with_untracked(|records| startup_summary_text(records)),
+            usage_records.with_untracked(|records| {
+                usage_summary_text(
+                    records,
+                    preferences.with_untracked(|preferences| preferences.tile_label_source),
+                    preferences.with_untracked(|preferences| preferences.usage_sort),
+                    preferences.with_untracked(|preferences| preferences.usage_tile_limit as usize),
+                    preferences.with_untracked(|preferences| preferences.usage_rounding),
+                )
+            }),
+        ]
+        .join("\n\n");
+        spawn_local(async move {
+            let _ = copy_text(&text).await;
+        });
+    });
+
     Effect::new(move |_| {
         if loaded.get() {
             return;
@@ -82,18 +405,41 @@ pub fn Dashboard() -> impl IntoView {
             let set_startup_records = set_startup_records;
             let set_loaded = set_loaded;
             async move {
-                let records = load_startup_records().await;
-                set_startup_records.set(records);
+                if let Ok(records) = load_startup_records().await {
+                    set_startup_records.set(records);
+                }
+                if let Ok(averages) = load_launcher_averages().await {
+                    set_launcher_averages.set(averages);
+                }
+                if let Ok(shares) = load_launcher_distribution().await {
+                    set_launcher_shares.set(shares);
+                }
+                if let Ok(failure) = load_record_failure().await {
+                    set_record_failure.set(failure);
+                }
+                if let Ok(first) = load_first_launch_today().await {
+                    set_first_launch_today.set(first);
+                }
                 set_loaded.set(true);
             }
         });
     });
 
-    let total_runs = Signal::derive(move || startup_records.with(|records| records.len()));
+    // Runs behind every startup stat, without development runs when release-only is selected.
+    let shown_records = Signal::derive(move || {
+        let release_only = release_only.get();
+        startup_records.with(|records| filter_by_build_environment(records, release_only))
+    });
+    let total_runs = Signal::derive(move || shown_records.with(|records| records.len()));
     let latest_record =
-        Signal::derive(move || startup_records.with(|records| records.first().cloned()));
+        Signal::derive(move || shown_records.with(|records| records.first().cloned()));
+    // Runs behind the chart and history list, narrowed to the selected speed bucket.
+    let filtered_records = Signal::derive(move || {
+        let category = selected_category.get();
+        shown_records.with(|records| filter_by_category(records, category))
+    });
     let history_records = Signal::derive(move || {
-        startup_records.with(|records| {
+        filtered_records.with(|records| {
             let mut limited = records.clone();
             if limited.len() > STARTUP_HISTORY_LIMIT {
                 limited.truncate(STARTUP_HISTORY_LIMIT);
@@ -102,7 +448,7 @@ pub fn Dashboard() -> impl IntoView {
         })
     });
     let total_duration = Signal::derive(move || {
-        startup_records.with(|records| {
+        shown_records.with(|records| {
             let total_ms: u128 = records
                 .iter()
                 .map(|record| record.duration_ms as u128)
@@ -110,25 +456,92 @@ pub fn Dashboard() -> impl IntoView {
             format_total_duration(total_ms as u64)
         })
     });
-    let chart_points =
-        Signal::derive(move || startup_records.with(|records| compute_chart_points(records)));
-    let chart_max = Signal::derive(move || {
-        chart_points.with(|points| {
-            points
-                .iter()
-                .map(|point| point.duration_ms)
-                .max()
-                .unwrap_or(0)
+    let chart_points = Signal::derive(move || {
+        let mode = chart_mode.get();
+        let (recent_bars, distribution_bars) = preferences.with(|preferences| {
+            (
+                preferences.recent_chart_bars as usize,
+                preferences.distribution_chart_bars as usize,
+            )
+        });
+        filtered_records.with(|records| match mode {
+            ChartMode::Recent => compute_chart_points(records, recent_bars),
+            ChartMode::Distribution => compute_distribution_points(records, distribution_bars),
         })
     });
-    let chart_annotation_top = Signal::derive(move || format_duration_compact(chart_max.get()));
+    let chart_max = Signal::derive(move || {
+        chart_points.with(|points| points.iter().map(|point| point.value).max().unwrap_or(0))
+    });
+    let current_chart_svg = move || {
+        chart_points.with_untracked(|points| {
+            chart_svg(
+                points,
+                chart_max.get_untracked(),
+                chart_mode.get_untracked(),
+            )
+        })
+    };
+    let chart_annotation_top =
+        Signal::derive(move || format_chart_annotation(chart_max.get(), chart_mode.get()));
     let chart_annotation_middle =
-        Signal::derive(move || format_duration_compact(chart_max.get() / 2));
+        Signal::derive(move || format_chart_annotation(chart_max.get() / 2, chart_mode.get()));
     let category_usage =
-        Signal::derive(move || startup_records.with(|records| compute_category_summary(records)));
-    let tiles = Signal::derive(move || startup_records.with(|records| compute_tiles(records)));
-    let usage_tiles =
-        Signal::derive(move || usage_records.with(|records| compute_usage_tiles(records)));
+        Signal::derive(move || shown_records.with(|records| compute_category_summary(records)));
+    let fine_category_usage = Signal::derive(move || {
+        shown_records.with(|records| compute_category_summary_fine(records, FINE_BUCKET_MS))
+    });
+    let startup_delta = Signal::derive(move || shown_records.with(|records| latest_delta(records)));
+    let startup_trend =
+        Signal::derive(move || shown_records.with(|records| trend_verdict(records)));
+    let community_message = Signal::derive(move || {
+        let baseline = community_baseline.get()?;
+        let p50 = shown_records.with(|records| median_duration_ms(records))?;
+        Some(community_comparison_message(p50, &baseline))
+    });
+    let slow_start_insight =
+        Signal::derive(move || shown_records.with(|records| slow_start_correlation(records)));
+    let launcher_insight = Signal::derive(move || {
+        preferences.with(|preferences| {
+            shown_records
+                .with(|records| slow_launcher_insight(records, &preferences.ignored_launchers))
+        })
+    });
+    let time_saved =
+        Signal::derive(move || shown_records.with(|records| time_saved_insight(records)));
+    let startup_forecast =
+        Signal::derive(move || shown_records.with(|records| startup_forecast_label(records)));
+    let duration_precision =
+        Signal::derive(move || preferences.with(|preferences| preferences.duration_precision));
+    let tiles = Signal::derive(move || {
+        let precision = duration_precision.get();
+        shown_records.with(|records| compute_tiles(records, precision))
+    });
+    let show_raw_durations =
+        Signal::derive(move || preferences.with(|preferences| preferences.show_raw_durations));
+    let usage_tiles = Signal::derive(move || {
+        let filter = usage_filter.get();
+        usage_records.with(|records| {
+            let filtered = filter_usage_records(records, filter, Date::now() as u64);
+            let (label_source, sort, show_inactive, limit, rounding) =
+                preferences.with(|preferences| {
+                    (
+                        preferences.tile_label_source,
+                        preferences.usage_sort,
+                        preferences.show_inactive_apps,
+                        preferences.usage_tile_limit as usize,
+                        preferences.usage_rounding,
+                    )
+                });
+            compute_usage_tiles(
+                &filtered,
+                label_source,
+                sort,
+                show_inactive,
+                limit,
+                rounding,
+            )
+        })
+    });
     let usage_status_text = Signal::derive(move || {
         usage_records.with(|records| match active_app_count(records) {
             0 => "No active apps".to_string(),
@@ -143,154 +556,580 @@ pub fn Dashboard() -> impl IntoView {
                 .unwrap_or_else(|| "Waiting for desktop activity…".to_string())
         })
     });
+    // Recomputed whenever usage refreshes, which is often enough for a minute-level duration.
+    let session_length = Signal::derive(move || {
+        usage_records.track();
+        session_start_ms.get().map(|started| {
+            format!(
+                "This session: {}",
+                format_total_duration((Date::now() as u64).saturating_sub(started))
+            )
+        })
+    });
 
     view! {
-        <main class="app">
+        <main
+            class=move || if compact.get() { "app app--compact" } else { "app" }
+            on:pointerdown=move |_| note_interaction()
+            on:keydown=move |_| note_interaction()
+            on:wheel=move |_| note_interaction()
+        >
             <section class="app__card">
-                <div class="app__summary">
-                    <header class="app__profile">
-                        <div class="app__avatar">
-                            "A"
-                        </div>
-                        <div>
-                            <div class="app__total">{move || total_duration.get()}</div>
-                            <div class="app__label">"Startup time collected"
+                <Show when=move || preferences.with(|preferences| preferences.show_startup_metrics)>
+                    <div class="app__summary">
+                        <header class="app__profile">
+                            <div class="app__avatar">
+                                {move || {
+                                    let avatar = preferences.with(|preferences| preferences.avatar.clone());
+                                    if avatar == AVATAR_ICON {
+                                        view! {
+                                            <img class="app__avatar-icon" src="/icons/128x128.png" alt="" />
+                                        }
+                                            .into_any()
+                                    } else {
+                                        avatar.into_any()
+                                    }
+                                }}
                             </div>
-                        </div>
-                    </header>
-                    <div class="app__startup">
-                        <div class="app__startup-header">
-                            <span class="app__startup-title">"Startup performance"</span>
-                            <span class="app__startup-count">{move || {
-                                let count = total_runs.get();
-                                match count {
-                                    0 => "No runs yet".to_string(),
-                                    1 => "1 run recorded".to_string(),
-                                    _ => format!("{count} runs recorded"),
+                            <div>
+                                <div class="app__total">{move || total_duration.get()}</div>
+                                <div class="app__label">"Startup time collected"
+                                </div>
+                            </div>
+                            <button
+                                type="button"
+                                class=move || {
+                                    if window_pinned.get() { "app__pin app__pin--active" } else { "app__pin" }
                                 }
-                            }}</span>
-                        </div>
-                        <Show
-                            when=move || latest_record.get().is_some()
-                            fallback=move || {
-                                let message = if loaded.get() {
-                                    "Collecting first startup measurement…"
-                                } else {
-                                    "Loading startup metrics…"
-                                };
-                                view! { <div class="app__startup-empty">{message}</div> }
-                            }
-                        >
+                                title=move || {
+                                    if window_pinned.get() {
+                                        "Unpin: the tray icon hides the dashboard again"
+                                    } else {
+                                        "Pin: the tray icon only brings the dashboard to front"
+                                    }
+                                }
+                                aria-pressed=move || window_pinned.get().to_string()
+                                on:click=move |_| {
+                                    let pinned = !window_pinned.get_untracked();
+                                    spawn_local(async move {
+                                        if let Ok(stored) = set_dashboard_pinned(pinned).await {
+                                            set_window_pinned.set(stored);
+                                        }
+                                    });
+                                }
+                            >
+                                "📌"
+                            </button>
+                        </header>
+                        <div class="app__startup">
                             {move || {
-                                let record = latest_record
+                                record_failure
                                     .get()
-                                    .expect("checked by Show predicate");
-                                view! {
-                                    <div class="app__startup-latest">
-                                        <span class="app__startup-value">{format_duration(record.duration_ms)}</span>
-                                        <span class="app__startup-subtext">{
-                                            let timestamp = format_timestamp(record.recorded_at_ms);
-                                            match launcher_display_label(&record.launcher) {
-                                                Some(launcher) => {
-                                                    format!("Recorded {timestamp} • via {launcher}")
+                                    .map(|err| {
+                                        view! {
+                                            <div class="app__record-failure" role="alert">
+                                                {format!("Startup times are not being saved: {err}")}
+                                            </div>
+                                        }
+                                    })
+                            }}
+                            <Show when=move || undo_toast.get().is_some()>
+                                <div class="app__undo-toast" role="status">
+                                    <span>"Startup record deleted."</span>
+                                    <button
+                                        type="button"
+                                        class="app__undo-toast-button"
+                                        on:click=move |_| {
+                                            set_undo_toast.set(None);
+                                            spawn_local(async move {
+                                                if let Ok(true) = undo_delete_startup_record().await {
+                                                    if let Ok(records) = load_startup_records().await {
+                                                        set_startup_records.set(records);
+                                                    }
                                                 }
-                                                None => format!("Recorded {timestamp}"),
+                                            });
+                                        }
+                                    >
+                                        "Undo"
+                                    </button>
+                                </div>
+                            </Show>
+                            <div class="app__startup-header">
+                                <span class="app__startup-title">"Startup performance"</span>
+                                {move || {
+                                    let trend = startup_trend.get();
+                                    let class_names = match trend {
+                                        Trend::Improving => "app__trend app__trend--improving",
+                                        Trend::Regressing => "app__trend app__trend--regressing",
+                                        Trend::Stable | Trend::Unknown => "app__trend",
+                                    };
+                                    (trend != Trend::Unknown)
+                                        .then(|| view! { <span class=class_names>{trend.label()}</span> })
+                                }}
+                                <span class="app__startup-count">{move || {
+                                    let count = total_runs.get();
+                                    match count {
+                                        0 => "No runs yet".to_string(),
+                                        1 => "1 run recorded".to_string(),
+                                        _ => format!("{count} runs recorded"),
+                                    }
+                                }}</span>
+                                <button
+                                    type="button"
+                                    class="app__chart-mode"
+                                    on:click=move |_| {
+                                        spawn_local(async move {
+                                            if let Ok(headline) = load_startup_headline().await {
+                                                let _ = copy_text(&headline).await;
                                             }
-                                        }</span>
-                                    </div>
+                                        });
+                                    }
+                                >
+                                    "Copy headline"
+                                </button>
+                            </div>
+                            <Show
+                                when=move || latest_record.get().is_some()
+                                fallback=move || {
+                                    let message = if loaded.get() {
+                                        "Collecting first startup measurement…"
+                                    } else {
+                                        "Loading startup metrics…"
+                                    };
+                                    view! { <div class="app__startup-empty">{message}</div> }
                                 }
-                            }}
-                        </Show>
-                        <Show
-                            when=move || { history_records.get().len() > 1 }
-                            fallback=move || { view! { <></> } }
-                        >
-                            {move || {
-                                let mut records = history_records.get();
-                                let _ = records.first();
-                                let mut iter = records.into_iter();
-                                let _ = iter.next();
-                                let items = iter
-                                    .map(|record| {
-                                        view! {
-                                            <li class="app__startup-list-item">
-                                                <span class="app__startup-list-time">{format_duration(record.duration_ms)}</span>
-                                                <span class="app__startup-list-date">{
-                                                    let timestamp = format_timestamp(record.recorded_at_ms);
-                                                    match launcher_display_label(&record.launcher) {
-                                                        Some(launcher) => {
-                                                            format!("{timestamp} • via {launcher}")
-                                                        }
-                                                        None => timestamp,
+                            >
+                                {move || {
+                                    let record = latest_record
+                                        .get()
+                                        .expect("checked by Show predicate");
+                                    view! {
+                                        <div class="app__startup-latest">
+                                            <span
+                                                class="app__startup-value"
+                                                title=move || raw_ms_title(show_raw_durations.get(), record.duration_ms)
+                                            >
+                                                {move || format_duration_with_precision(record.duration_ms, duration_precision.get())}
+                                            </span>
+                                            <span class="app__startup-subtext">{
+                                                let timestamp = format_timestamp(record.recorded_at_ms);
+                                                let launcher = preferences.with(|preferences| {
+                                                    launcher_display_label(
+                                                        &record.launcher,
+                                                        &preferences.ignored_launchers,
+                                                    )
+                                                });
+                                                match launcher {
+                                                    Some(launcher) => {
+                                                        format!("Recorded {timestamp} • via {launcher}")
                                                     }
-                                                }</span>
-                                            </li>
-                                        }
+                                                    None => format!("Recorded {timestamp}"),
+                                                }
+                                            }</span>
+                                            {format_cpu_wall_ratio(&record)
+                                                .map(|ratio| {
+                                                    view! {
+                                                        <span
+                                                            class="app__startup-cpu"
+                                                            title="CPU time used before ready divided by wall time; well below 1 suggests contention"
+                                                        >
+                                                            {ratio}
+                                                        </span>
+                                                    }
+                                                })}
+                                            {move || {
+                                                startup_delta
+                                                    .get()
+                                                    .map(|delta| {
+                                                        let class_names = match delta.signum() {
+                                                            1 => "app__startup-delta app__startup-delta--slower",
+                                                            -1 => "app__startup-delta app__startup-delta--faster",
+                                                            _ => "app__startup-delta",
+                                                        };
+                                                        view! { <span class=class_names>{format_delta(delta)}</span> }
+                                                    })
+                                            }}
+                                            {move || {
+                                                first_launch_today.get().then(|| {
+                                                    view! {
+                                                        <span class="app__first-launch">"First launch today 🎉"</span>
+                                                    }
+                                                })
+                                            }}
+                                        </div>
+                                    }
+                                }}
+                            </Show>
+                            {move || {
+                                community_message
+                                    .get()
+                                    .map(|message| {
+                                        view! { <div class="app__startup-community">{message}</div> }
                                     })
-                                    .collect::<Vec<_>>();
-                                view! {
-                                    <ul class="app__startup-list">
-                                        {items.into_view()}
-                                    </ul>
-                                }
                             }}
-                        </Show>
-                    </div>
-                    <div class="app__chart">
-                        <div class="app__chart-overlay">
-                            <div class="app__chart-grid-line app__chart-grid-line--top"></div>
-                            <div class="app__chart-grid-line app__chart-grid-line--middle"></div>
-                            <div class="app__chart-grid-line app__chart-grid-line--bottom"></div>
+                            <Show when=move || !launcher_averages.get().is_empty()>
+                                <div class="app__startup-launchers">
+                                    {move || {
+                                        let averages = launcher_averages
+                                            .get()
+                                            .iter()
+                                            .map(|average| {
+                                                format!(
+                                                    "{} {}",
+                                                    average.launcher,
+                                                    format_duration_with_precision(
+                                                        average.average_ms,
+                                                        duration_precision.get(),
+                                                    ),
+                                                )
+                                            })
+                                            .collect::<Vec<_>>()
+                                            .join(" · ");
+                                        format!("By launcher: {averages}")
+                                    }}
+                                </div>
+                            </Show>
+                            <Show when=move || !launcher_shares.get().is_empty()>
+                                <div class="app__launcher-share">
+                                    <div
+                                        class="app__launcher-donut"
+                                        style=move || {
+                                            launcher_shares
+                                                .with(|shares| format!("background:{}", launcher_donut_gradient(shares)))
+                                        }
+                                    ></div>
+                                    <ul class="app__launcher-legend">
+                                        {move || {
+                                            launcher_shares
+                                                .get()
+                                                .into_iter()
+                                                .enumerate()
+                                                .map(|(index, share)| {
+                                                    view! {
+                                                        <li class="app__launcher-legend-item">
+                                                            <span
+                                                                class="app__launcher-swatch"
+                                                                style=format!("background:{}", launcher_donut_color(index))
+                                                            ></span>
+                                                            {format!(
+                                                                "{} {:.0}% ({})",
+                                                                share.launcher,
+                                                                share.fraction * 100.0,
+                                                                share.count,
+                                                            )}
+                                                        </li>
+                                                    }
+                                                })
+                                                .collect::<Vec<_>>()
+                                        }}
+                                    </ul>
+                                </div>
+                            </Show>
+                            <Show
+                                when=move || { history_records.get().len() > 1 }
+                                fallback=move || { view! { <></> } }
+                            >
+                                {move || {
+                                    let mut records = history_records.get();
+                                    let _ = records.first();
+                                    let mut iter = records.into_iter();
+                                    let _ = iter.next();
+                                    let items = iter
+                                        .map(|record| {
+                                            view! {
+                                                <li class="app__startup-list-item">
+                                                    <span
+                                                        class="app__startup-list-time"
+                                                        title=move || raw_ms_title(show_raw_durations.get(), record.duration_ms)
+                                                    >
+                                                        {move || format_duration_with_precision(record.duration_ms, duration_precision.get())}
+                                                    </span>
+                                                    <span class="app__startup-list-date">{
+                                                        let timestamp = format_timestamp(record.recorded_at_ms);
+                                                        let launcher = preferences.with(|preferences| {
+                                                            launcher_display_label(
+                                                                &record.launcher,
+                                                                &preferences.ignored_launchers,
+                                                            )
+                                                        });
+                                                        match launcher {
+                                                            Some(launcher) => {
+                                                                format!("{timestamp} • via {launcher}")
+                                                            }
+                                                            None => timestamp,
+                                                        }
+                                                    }</span>
+                                                    <button
+                                                        type="button"
+                                                        class="app__startup-list-delete"
+                                                        title="Delete this run"
+                                                        on:click=move |_| {
+                                                            let recorded_at_ms = record.recorded_at_ms;
+                                                            spawn_local(async move {
+                                                                if let Ok(true) = delete_startup_record(recorded_at_ms).await {
+                                                                    if let Ok(records) = load_startup_records().await {
+                                                                        set_startup_records.set(records);
+                                                                    }
+                                                                    set_undo_toast.set(Some(recorded_at_ms));
+                                                                    hide_undo_toast_later(set_undo_toast, recorded_at_ms);
+                                                                }
+                                                            });
+                                                        }
+                                                    >
+                                                        "×"
+                                                    </button>
+                                                </li>
+                                            }
+                                        })
+                                        .collect::<Vec<_>>();
+                                    view! {
+                                        <ul class="app__startup-list">
+                                            {items.into_view()}
+                                        </ul>
+                                    }
+                                }}
+                            </Show>
                         </div>
-                        {move || {
-                            let max_value = chart_max.get();
-                            chart_points
-                                .get()
+                        <div class="app__chart-modes">
+                            {ChartMode::ALL
                                 .into_iter()
-                                .map(|point| {
-                                    let style = bar_height(point.duration_ms, max_value);
+                                .map(|mode| {
                                     view! {
-                                        <div class="app__chart-column">
-                                            <div class="app__chart-column-inner">
-                                                <div class="app__chart-bar" style=style></div>
-                                            </div>
-                                        </div>
+                                        <button
+                                            type="button"
+                                            class=move || {
+                                                if chart_mode.get() == mode {
+                                                    "app__chart-mode app__chart-mode--selected"
+                                                } else {
+                                                    "app__chart-mode"
+                                                }
+                                            }
+                                            on:click=move |_| {
+                                                set_chart_mode.set(mode);
+                                                spawn_local(async move {
+                                                    let _ = set_last_view(mode.key()).await;
+                                                });
+                                            }
+                                        >
+                                            {mode.label()}
+                                        </button>
                                     }
                                 })
-                                .collect::<Vec<_>>()
-                                .into_view()
-                        }}
-                        <div class="app__chart-labels">
+                                .collect::<Vec<_>>()}
+                            <button
+                                type="button"
+                                class=move || {
+                                    if release_only.get() {
+                                        "app__chart-mode app__chart-mode--selected"
+                                    } else {
+                                        "app__chart-mode"
+                                    }
+                                }
+                                on:click=move |_| set_release_only.update(|release_only| *release_only = !*release_only)
+                            >
+                                "Release only"
+                            </button>
+                            <button
+                                type="button"
+                                class="app__chart-mode"
+                                on:click=move |_| {
+                                    let svg = current_chart_svg();
+                                    spawn_local(async move {
+                                        let _ = copy_text(&svg).await;
+                                    });
+                                }
+                            >
+                                "Copy SVG"
+                            </button>
+                            <button
+                                type="button"
+                                class="app__chart-mode"
+                                on:click=move |_| {
+                                    let svg = current_chart_svg();
+                                    spawn_local(async move {
+                                        let _ = save_text_file(&svg, "startup-chart.svg").await;
+                                    });
+                                }
+                            >
+                                "Save SVG"
+                            </button>
+                            <button
+                                type="button"
+                                class="app__chart-mode"
+                                on:click=move |_| {
+                                    let csv = startup_records.with_untracked(|records| startup_csv(records, false));
+                                    spawn_local(async move {
+                                        let _ = save_text_file(&csv, "startup-times.csv").await;
+                                    });
+                                }
+                            >
+                                "Export CSV"
+                            </button>
+                            <button
+                                type="button"
+                                class="app__chart-mode"
+                                on:click=move |_| {
+                                    let csv = startup_records.with_untracked(|records| startup_csv(records, true));
+                                    spawn_local(async move {
+                                        let _ = save_text_file(&csv, "startup-times-deltas.csv").await;
+                                    });
+                                }
+                            >
+                                "Export CSV with deltas"
+                            </button>
+                        </div>
+                        <div class="app__chart">
+                            <div class="app__chart-overlay">
+                                <div class="app__chart-grid-line app__chart-grid-line--top"></div>
+                                <div class="app__chart-grid-line app__chart-grid-line--middle"></div>
+                                <div class="app__chart-grid-line app__chart-grid-line--bottom"></div>
+                            </div>
                             {move || {
+                                let max_value = chart_max.get();
                                 chart_points
                                     .get()
                                     .into_iter()
-                                    .map(|point| view! { <span>{point.label}</span> })
+                                    .map(|point| {
+                                        let style = bar_height(point.value, max_value);
+                                        view! {
+                                            <div class="app__chart-column">
+                                                <div class="app__chart-column-inner">
+                                                    <div class="app__chart-bar" style=style></div>
+                                                </div>
+                                            </div>
+                                        }
+                                    })
                                     .collect::<Vec<_>>()
                                     .into_view()
                             }}
+                            <div class="app__chart-labels">
+                                {move || {
+                                    chart_points
+                                        .get()
+                                        .into_iter()
+                                        .map(|point| view! { <span>{point.label}</span> })
+                                        .collect::<Vec<_>>()
+                                        .into_view()
+                                }}
+                            </div>
+                            <div class="app__chart-annotation app__chart-annotation--top">
+                                {move || chart_annotation_top.get()}
+                            </div>
+                            <div class="app__chart-annotation app__chart-annotation--middle">
+                                {move || chart_annotation_middle.get()}
+                            </div>
+                            <div class="app__chart-annotation app__chart-annotation--bottom">"0"
+                            </div>
                         </div>
-                        <div class="app__chart-annotation app__chart-annotation--top">
-                            {move || chart_annotation_top.get()}
-                        </div>
-                        <div class="app__chart-annotation app__chart-annotation--middle">
-                            {move || chart_annotation_middle.get()}
-                        </div>
-                        <div class="app__chart-annotation app__chart-annotation--bottom">"0"
+                        <div class="app__categories">
+                            {move || {
+                                if detailed_breakdown.get() {
+                                    return fine_category_usage
+                                        .get()
+                                        .into_iter()
+                                        .map(|bucket| {
+                                            view! {
+                                                <div class="app__category">
+                                                    <span class="app__category-name">{bucket.name}</span>
+                                                    <span class="app__category-minutes">{bucket.summary}</span>
+                                                </div>
+                                            }
+                                        })
+                                        .collect::<Vec<_>>()
+                                        .into_any();
+                                }
+                                category_usage
+                                    .get()
+                                    .into_iter()
+                                    .map(|category| {
+                                        let bucket = category.category;
+                                        let selected = selected_category.get() == Some(bucket);
+                                        view! {
+                                            <button
+                                                type="button"
+                                                class=if selected {
+                                                    "app__category app__category--selected"
+                                                } else {
+                                                    "app__category"
+                                                }
+                                                title=if selected {
+                                                    "Show all runs"
+                                                } else {
+                                                    "Show only these runs in the chart and history"
+                                                }
+                                                on:click=move |_| {
+                                                    set_selected_category
+                                                        .update(|current| {
+                                                            *current = (*current != Some(bucket))
+                                                                .then_some(bucket);
+                                                        });
+                                                }
+                                            >
+                                                <span class=category.class_names>
+                                                    {category.name}
+                                                </span>
+                                                <span class="app__category-minutes">{category.summary}</span>
+                                            </button>
+                                        }
+                                    })
+                                    .collect::<Vec<_>>()
+                                    .into_any()
+                            }}
                         </div>
+                        <button
+                            type="button"
+                            class=move || {
+                                if detailed_breakdown.get() {
+                                    "app__chart-mode app__chart-mode--selected"
+                                } else {
+                                    "app__chart-mode"
+                                }
+                            }
+                            on:click=move |_| set_detailed_breakdown.update(|detailed| *detailed = !*detailed)
+                        >
+                            "Detailed breakdown"
+                        </button>
+                        {move || {
+                            slow_start_insight
+                                .get()
+                                .map(|insight| view! { <div class="app__startup-insight">{insight}</div> })
+                        }}
+                        {move || {
+                            launcher_insight
+                                .get()
+                                .map(|insight| view! { <div class="app__startup-insight">{insight}</div> })
+                        }}
+                        {move || {
+                            time_saved
+                                .get()
+                                .map(|insight| view! { <div class="app__startup-insight">{insight}</div> })
+                        }}
+                        {move || {
+                            startup_forecast
+                                .get()
+                                .map(|forecast| view! { <div class="app__startup-insight">{forecast}</div> })
+                        }}
                     </div>
-                    <div class="app__categories">
+                    <div class="app__grid">
                         {move || {
-                            category_usage
+                            tiles
                                 .get()
                                 .into_iter()
-                                .map(|category| {
+                                .map(|tile| {
                                     view! {
-                                        <div class="app__category">
-                                            <span class=category.class_names>
-                                                {category.name}
-                                            </span>
-                                            <span class="app__category-minutes">{category.summary}</span>
+                                        <div class="app__tile">
+                                            <div class="app__tile-icon">
+                                                {tile.icon}
+                                            </div>
+                                            <div class="app__tile-info">
+                                                <span class="app__tile-name">{tile.label}</span>
+                                                <span
+                                                    class="app__tile-minutes"
+                                                    title=move || raw_ms_title(show_raw_durations.get(), tile.duration_ms)
+                                                >
+                                                    {tile.duration}
+                                                </span>
+                                            </div>
                                         </div>
                                     }
                                 })
@@ -298,39 +1137,73 @@ pub fn Dashboard() -> impl IntoView {
                                 .into_view()
                         }}
                     </div>
-                </div>
-                <div class="app__grid">
+                </Show>
+                <div class="app__usage">
+                    <div class="app__usage-header">
+                        <span class="app__usage-title">"Desktop usage"</span>
+                        <span class="app__usage-count">{move || usage_status_text.get()}</span>
+                    </div>
                     {move || {
-                        tiles
+                        steady_use_score
                             .get()
-                            .into_iter()
-                            .map(|tile| {
+                            .map(|score| {
                                 view! {
-                                    <div class="app__tile">
-                                        <div class="app__tile-icon">
-                                            {tile.icon}
-                                        </div>
-                                        <div class="app__tile-info">
-                                            <span class="app__tile-name">{tile.label}</span>
-                                            <span class="app__tile-minutes">{tile.duration}</span>
-                                        </div>
+                                    <div
+                                        class="app__steady-score"
+                                        title="Share of today spent without opening another app for 25 minutes or more, minus a penalty for opening apps often. Based on running apps, not the focused window."
+                                    >
+                                        <span class="app__steady-score-value">{score}</span>
+                                        <span class="app__steady-score-label">"Steady-use score today"</span>
                                     </div>
                                 }
                             })
-                            .collect::<Vec<_>>()
-                            .into_view()
                     }}
-                </div>
-                <div class="app__usage">
-                    <div class="app__usage-header">
-                        <span class="app__usage-title">"Desktop usage"</span>
-                        <span class="app__usage-count">{move || usage_status_text.get()}</span>
-                    </div>
                     <span class="app__usage-updated">{move || usage_last_updated.get()}</span>
+                    <span class="app__usage-updated">{move || session_length.get()}</span>
+                    {move || {
+                        busiest_hour
+                            .get()
+                            .map(|hour| {
+                                view! { <span class="app__usage-updated">{busiest_hour_label(hour)}</span> }
+                            })
+                    }}
+                    <div class="app__usage-filters">
+                        {UsageFilter::ALL
+                            .into_iter()
+                            .map(|filter| {
+                                view! {
+                                    <button
+                                        type="button"
+                                        class=move || {
+                                            if usage_filter.get() == filter {
+                                                "app__usage-filter app__usage-filter--selected"
+                                            } else {
+                                                "app__usage-filter"
+                                            }
+                                        }
+                                        on:click=move |_| set_usage_filter.set(filter)
+                                    >
+                                        {filter.label()}
+                                    </button>
+                                }
+                            })
+                            .collect::<Vec<_>>()}
+                    </div>
                     <Show
                         when=move || !usage_tiles.get().is_empty()
                         fallback=move || {
-                            view! { <div class="app__usage-empty">"Desktop activity will appear once apps launch."</div> }
+                            if !usage_loaded.get() {
+                                return view! { <div class="app__usage-empty">"Loading activity…"</div> }
+                                    .into_any();
+                            }
+                            let message = if !preferences.with(|preferences| preferences.show_inactive_apps) {
+                                "No active apps right now"
+                            } else if usage_filter.get() == UsageFilter::All {
+                                "Desktop activity will appear once apps launch."
+                            } else {
+                                "No apps match this filter."
+                            };
+                            view! { <div class="app__usage-empty">{message}</div> }.into_any()
                         }
                     >
                         {move || {
@@ -343,16 +1216,150 @@ pub fn Dashboard() -> impl IntoView {
                                     } else {
                                         "app__usage-indicator"
                                     };
+                                    let streak = usage_streaks
+                                        .with(|streaks| {
+                                            streaks
+                                                .iter()
+                                                .find(|(name, _)| *name == tile.name)
+                                                .map(|(_, days)| *days)
+                                        })
+                                        .map(|days| {
+                                            view! { <span class="app__usage-streak">{streak_label(days)}</span> }
+                                        });
+                                    let budget = budget_usage
+                                        .with(|usage| {
+                                            usage.iter().find(|usage| usage.name == tile.name).cloned()
+                                        })
+                                        .map(|usage| {
+                                            let percent = budget_percent(&usage);
+                                            let fill_class = if percent >= 100 {
+                                                "app__usage-budget-fill app__usage-budget-fill--exceeded"
+                                            } else {
+                                                "app__usage-budget-fill"
+                                            };
+                                            view! {
+                                                <div class="app__usage-budget" title=budget_label(&usage)>
+                                                    <div
+                                                        class=fill_class
+                                                        style=format!("width:{percent}%")
+                                                    ></div>
+                                                </div>
+                                            }
+                                        });
+                                    let weekday_chart = weekday_usage
+                                        .get()
+                                        .filter(|(name, _)| *name == tile.name)
+                                        .map(|(_, totals)| {
+                                            let max = totals.iter().copied().max().unwrap_or(0);
+                                            let bars = totals
+                                                .iter()
+                                                .zip(WEEKDAY_LABELS)
+                                                .map(|(total, label)| {
+                                                    view! {
+                                                        <div
+                                                            class="app__weekday-column"
+                                                            title=format_usage_duration(*total)
+                                                        >
+                                                            <div
+                                                                class="app__weekday-bar"
+                                                                style=bar_height(*total, max)
+                                                            ></div>
+                                                            <span class="app__weekday-label">{label}</span>
+                                                        </div>
+                                                    }
+                                                })
+                                                .collect::<Vec<_>>();
+                                            let daily = daily_usage.get();
+                                            let daily_max = daily.iter().map(|(_, ms)| *ms).max().unwrap_or(0);
+                                            let sparkline = daily
+                                                .into_iter()
+                                                .map(|(day_ms, ms)| {
+                                                    let style = if ms == 0 {
+                                                        "height:0%".to_string()
+                                                    } else {
+                                                        bar_height(ms, daily_max)
+                                                    };
+                                                    view! {
+                                                        <div
+                                                            class="app__sparkline-bar"
+                                                            style=style
+                                                            title=format!(
+                                                                "{} · {}",
+                                                                format_timestamp(day_ms),
+                                                                format_usage_duration(ms),
+                                                            )
+                                                        ></div>
+                                                    }
+                                                })
+                                                .collect::<Vec<_>>();
+                                            let detail = app_detail.get().map(|detail| {
+                                                view! {
+                                                    <dl class="app__usage-detail">
+                                                        <dt>"Total"</dt>
+                                                        <dd>{format_usage_duration(detail.total_ms)}</dd>
+                                                        <dt>"Launches"</dt>
+                                                        <dd>{detail.launch_count}</dd>
+                                                        <dt>"Longest session"</dt>
+                                                        <dd>{format_usage_duration(detail.longest_session_ms)}</dd>
+                                                        <dt>"Average session"</dt>
+                                                        <dd>{format_usage_duration(detail.average_session_ms)}</dd>
+                                                        <dt>"First seen"</dt>
+                                                        <dd>{format_timestamp(detail.first_seen_ms)}</dd>
+                                                        <dt>"Last seen"</dt>
+                                                        <dd>{format_timestamp(detail.last_seen_ms)}</dd>
+                                                    </dl>
+                                                }
+                                            });
+                                            view! {
+                                                {detail}
+                                                <div class="app__weekday-chart">{bars}</div>
+                                                <div class="app__sparkline">{sparkline}</div>
+                                            }
+                                        });
+                                    let name = tile.name.clone();
                                     view! {
-                                        <li class="app__usage-item">
+                                        <li
+                                            class="app__usage-item"
+                                            on:click=move |_| {
+                                                let selected = weekday_usage
+                                                    .with_untracked(|usage| {
+                                                        usage.as_ref().is_some_and(|(current, _)| *current == name)
+                                                    });
+                                                if selected {
+                                                    set_weekday_usage.set(None);
+                                                    return;
+                                                }
+                                                let name = name.clone();
+                                                spawn_local(async move {
+                                                    set_daily_usage.set(load_daily_usage(&name).await.unwrap_or_default());
+                                                    set_app_detail.set(load_app_usage_detail(&name).await.ok().flatten());
+                                                    match load_usage_by_weekday(&name).await {
+                                                        Ok(totals) => set_weekday_usage.set(Some((name, totals))),
+                                                        Err(err) => {
+                                                            console::error_1(
+                                                                &format!("failed to load weekday usage: {err}").into(),
+                                                            );
+                                                        }
+                                                    }
+                                                });
+                                            }
+                                        >
                                             <div class="app__usage-main">
                                                 <span class=indicator_class></span>
                                                 <div class="app__usage-info">
-                                                    <span class="app__usage-name">{tile.name}</span>
+                                                    <span class="app__usage-name">{tile.label}</span>
                                                     <span class="app__usage-subtitle">{tile.subtitle}</span>
+                                                    {streak}
+                                                    {budget}
+                                                    {weekday_chart}
                                                 </div>
                                             </div>
-                                            <span class="app__usage-duration">{tile.duration}</span>
+                                            <span
+                                                class="app__usage-duration"
+                                                title=move || raw_ms_title(show_raw_durations.get(), tile.total_ms)
+                                            >
+                                                {tile.duration}
+                                            </span>
                                         </li>
                                     }
                                 })
@@ -360,6 +1367,86 @@ pub fn Dashboard() -> impl IntoView {
                             view! { <ul class="app__usage-list">{rows.into_view()}</ul> }
                         }}
                     </Show>
+                    <Show when=move || !category_totals.get().is_empty()>
+                        <ul class="app__category-totals">
+                            {move || {
+                                let totals = category_totals.get();
+                                let max = totals.iter().map(|total| total.total_ms).max().unwrap_or(0);
+                                totals
+                                    .into_iter()
+                                    .map(|total| {
+                                        let width = (total.total_ms * 100)
+                                            .checked_div(max)
+                                            .unwrap_or(0);
+                                        view! {
+                                            <li class="app__category-total">
+                                                <span class="app__category-total-name">{total.category}</span>
+                                                <div class="app__usage-budget">
+                                                    <div
+                                                        class="app__usage-budget-fill"
+                                                        style=format!("width:{width}%")
+                                                    ></div>
+                                                </div>
+                                                <span class="app__category-total-duration">
+                                                    {format_usage_duration(total.total_ms)}
+                                                </span>
+                                            </li>
+                                        }
+                                    })
+                                    .collect::<Vec<_>>()
+                            }}
+                        </ul>
+                    </Show>
+                    <Show when=move || !usage_tree.get().is_empty()>
+                        <div class="app__usage-tree">
+                            {move || {
+                                usage_tree
+                                    .get()
+                                    .into_iter()
+                                    .map(|group| {
+                                        let apps = group
+                                            .apps
+                                            .into_iter()
+                                            .map(|app| {
+                                                view! {
+                                                    <li class="app__usage-tree-app">
+                                                        <span>{app.name}</span>
+                                                        <span>{format_usage_duration(app.total_active_ms)}</span>
+                                                    </li>
+                                                }
+                                            })
+                                            .collect::<Vec<_>>();
+                                        let category = group.category.clone();
+                                        let open_key = category.clone();
+                                        view! {
+                                            <details
+                                                class="app__usage-group"
+                                                prop:open=move || open_categories.with(|open| open.contains(&open_key))
+                                                on:toggle=move |ev| {
+                                                    let open = event_target::<web_sys::Element>(&ev)
+                                                        .has_attribute("open");
+                                                    set_open_categories
+                                                        .update(|categories| {
+                                                            if open {
+                                                                categories.insert(category.clone());
+                                                            } else {
+                                                                categories.remove(&category);
+                                                            }
+                                                        });
+                                                }
+                                            >
+                                                <summary class="app__usage-group-summary">
+                                                    <span>{group.category}</span>
+                                                    <span>{format_usage_duration(group.total_ms)}</span>
+                                                </summary>
+                                                <ul class="app__usage-group-list">{apps}</ul>
+                                            </details>
+                                        }
+                                    })
+                                    .collect::<Vec<_>>()
+                            }}
+                        </div>
+                    </Show>
                 </div>
             </section>
         </main>
@@ -368,7 +1455,36 @@ pub fn Dashboard() -> impl IntoView {
 
 #[cfg(test)]
 mod tests {
-    use super::bar_height;
+    use super::{
+        bar_height, launcher_display_label, raw_ms_title, should_use_compact,
+        COMPACT_LAYOUT_BREAKPOINT_PX,
+    };
+
+    #[test]
+    fn compact_layout_starts_below_breakpoint() {
+        assert!(should_use_compact(320.0));
+        assert!(should_use_compact(COMPACT_LAYOUT_BREAKPOINT_PX - 1.0));
+        assert!(!should_use_compact(COMPACT_LAYOUT_BREAKPOINT_PX));
+        assert!(!should_use_compact(440.0));
+    }
+
+    #[test]
+    fn raw_ms_title_only_appears_when_enabled() {
+        assert_eq!(raw_ms_title(true, 1_234), Some("1234 ms".to_string()));
+        assert_eq!(raw_ms_title(false, 1_234), None);
+    }
+
+    #[test]
+    fn launcher_display_label_hides_unknown_and_ignored_launchers() {
+        let ignored = vec!["bash".to_string()];
+        assert_eq!(
+            launcher_display_label(" Finder ", &ignored),
+            Some("Finder".to_string())
+        );
+        assert_eq!(launcher_display_label("unknown", &ignored), None);
+        assert_eq!(launcher_display_label("bash", &ignored), None);
+        assert_eq!(launcher_display_label("Bash", &ignored), None);
+    }
 
     #[test]
     fn bar_height_zero_max_returns_zero_percent() {
@@ -0,0 +1,67 @@
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use crate::application::startup_service::format_duration;
+use crate::application::usage_service::format_usage_duration;
+use crate::domain::today_glance::TodayGlance;
+use crate::infrastructure::metrics_adapter::load_today_glance;
+use crate::infrastructure::tauri_adapter::{listen_event, WINDOW_SHOWN_EVENT};
+
+/// Loads today's glance in the background and stores it once it arrives.
+fn schedule_glance_fetch(set_glance: WriteSignal<Option<TodayGlance>>) {
+    spawn_local(async move {
+        if let Ok(loaded) = load_today_glance().await {
+            set_glance.set(Some(loaded));
+        }
+    });
+}
+
+#[component]
+/// Small always-on-top summary of today's launches, usage, current app, and latest startup.
+pub fn Glance() -> impl IntoView {
+    let (glance, set_glance) = signal(None::<TodayGlance>);
+
+    schedule_glance_fetch(set_glance);
+    listen_event(WINDOW_SHOWN_EVENT, move || {
+        schedule_glance_fetch(set_glance)
+    });
+
+    view! {
+        <main class="glance">
+            <Show
+                when=move || glance.get().is_some()
+                fallback=|| view! { <p class="glance__empty">"Loading today…"</p> }
+            >
+                {move || {
+                    let glance = glance.get().unwrap_or_default();
+                    let rows = [
+                        ("Launches today", glance.launches_today.to_string()),
+                        ("Usage today", format_usage_duration(glance.usage_today_ms)),
+                        ("Current app", glance.active_app.unwrap_or_else(|| "—".to_string())),
+                        (
+                            "Last startup",
+                            glance
+                                .last_startup_ms
+                                .map_or_else(|| "—".to_string(), format_duration),
+                        ),
+                    ];
+                    view! {
+                        <dl class="glance__list">
+                            {rows
+                                .into_iter()
+                                .map(|(label, value)| {
+                                    view! {
+                                        <div class="glance__row">
+                                            <dt class="glance__label">{label}</dt>
+                                            <dd class="glance__value">{value}</dd>
+                                        </div>
+                                    }
+                                })
+                                .collect::<Vec<_>>()}
+                        </dl>
+                    }
+                }}
+            </Show>
+        </main>
+    }
+}
@@ -0,0 +1,55 @@
+//! Keeps async work spawned by a component from writing to its signals after it was unmounted.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use leptos::prelude::*;
+
+/// Flag cleared when the component that created it is disposed.
+///
+/// Create it in the component body, then route writes from spawned tasks through [`MountGuard::set`].
+#[derive(Clone)]
+pub struct MountGuard(Arc<AtomicBool>);
+
+impl MountGuard {
+    /// Creates a guard that reports unmounted once the current reactive owner is cleaned up.
+    pub fn for_current_owner() -> Self {
+        let mounted = Arc::new(AtomicBool::new(true));
+        let flag = Arc::clone(&mounted);
+        on_cleanup(move || flag.store(false, Ordering::Relaxed));
+        Self(mounted)
+    }
+
+    pub fn is_mounted(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Writes `value` to `signal` only while the component is still mounted.
+    pub fn set<T: Send + Sync + 'static>(&self, signal: WriteSignal<T>, value: T) {
+        if self.is_mounted() {
+            signal.set(value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn guard_drops_writes_after_cleanup() {
+        let owner = Owner::new();
+        let (guard, value, set_value) = owner.with(|| {
+            let (value, set_value) = signal(0);
+            (MountGuard::for_current_owner(), value, set_value)
+        });
+
+        guard.set(set_value, 1);
+        assert!(guard.is_mounted());
+        assert_eq!(value.get_untracked(), 1);
+
+        owner.cleanup();
+        assert!(!guard.is_mounted());
+        guard.set(set_value, 2);
+    }
+}
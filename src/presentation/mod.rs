@@ -1,4 +1,6 @@
 pub mod components;
 pub mod dashboard;
+pub mod glance;
+pub mod lifecycle;
 pub mod models;
 pub mod settings;
@@ -1,33 +1,111 @@
 #![allow(clippy::module_name_repetitions)]
 
-#[derive(Clone)]
-/// Data point backing the histogram chart.
-pub struct ChartPoint {
-    pub label: String,
-    pub duration_ms: u64,
+pub use time_wise_shared::chart_data::{ChartPoint, SpeedCategory};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+/// Which series the startup chart plots.
+pub enum ChartMode {
+    #[default]
+    Recent,
+    Distribution,
+}
+
+impl ChartMode {
+    pub const ALL: [ChartMode; 2] = [ChartMode::Recent, ChartMode::Distribution];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ChartMode::Recent => "Recent",
+            ChartMode::Distribution => "Distribution",
+        }
+    }
+
+    /// Value stored as the `last_view` preference.
+    pub fn key(self) -> &'static str {
+        match self {
+            ChartMode::Recent => "recent",
+            ChartMode::Distribution => "distribution",
+        }
+    }
+
+    /// Reads a stored `last_view`, falling back to the default for unknown values.
+    pub fn from_key(key: &str) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|mode| mode.key() == key)
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Direction recent startup times are heading in.
+pub enum Trend {
+    Improving,
+    Stable,
+    Regressing,
+    /// Too few runs to tell.
+    Unknown,
+}
+
+impl Trend {
+    pub fn label(self) -> &'static str {
+        match self {
+            Trend::Improving => "improving",
+            Trend::Stable => "stable",
+            Trend::Regressing => "regressing",
+            Trend::Unknown => "unknown",
+        }
+    }
 }
 
 #[derive(Clone)]
 /// Aggregated summary per performance bucket.
 pub struct CategorySummary {
+    pub category: SpeedCategory,
     pub name: &'static str,
     pub class_names: &'static str,
     pub summary: String,
 }
 
+#[derive(Clone)]
+/// Aggregated summary per fixed-width duration bucket in the detailed breakdown.
+pub struct FineBucketSummary {
+    pub name: String,
+    pub summary: String,
+}
+
 #[derive(Clone)]
 /// UI model for each startup tile.
 pub struct StartupTile {
     pub icon: &'static str,
     pub label: String,
     pub duration: String,
+    pub duration_ms: u64,
 }
 
 #[derive(Clone)]
 /// UI model for desktop usage list entries.
 pub struct UsageTile {
+    /// Process name, used to match budgets and other per-app settings.
     pub name: String,
+    /// Text shown for the app, chosen by the tile label preference.
+    pub label: String,
     pub duration: String,
+    pub total_ms: u64,
     pub subtitle: String,
     pub active: bool,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ChartMode;
+
+    #[test]
+    fn chart_mode_round_trips_and_falls_back_to_recent() {
+        for mode in ChartMode::ALL {
+            assert_eq!(ChartMode::from_key(mode.key()), mode);
+        }
+        assert_eq!(ChartMode::from_key("histogram"), ChartMode::Recent);
+        assert_eq!(ChartMode::from_key(""), ChartMode::Recent);
+    }
+}
@@ -1,11 +1,204 @@
+use std::collections::{BTreeMap, BTreeSet};
+
 use leptos::prelude::*;
 use leptos::task::spawn_local;
 use wasm_bindgen::JsCast;
 use web_sys::HtmlInputElement;
 
+use crate::application::startup_service::format_timestamp;
+use crate::application::update_service::is_newer_version;
+use crate::application::usage_service::{coverage_label, start_of_today_ms};
+use crate::domain::preferences::{
+    BackupSchedule, Preferences, StartupMeasurement, StartupMilestone, TileLabelSource,
+    UsageRounding, UsageSort, WeekStart, AVATAR_ICON,
+};
+use crate::infrastructure::metrics_adapter::{
+    load_categorizable_apps, load_stale_apps, load_tracking_coverage, relabel_launcher,
+    vacuum_database,
+};
+use crate::infrastructure::preferences_adapter::{
+    back_up_now, fetch_preferences, reset_preferences, seed_allowlist_from_current, set_app_budget,
+    set_app_categories, set_app_category, set_autostart_paused_until, set_launcher_ignored,
+    set_min_session_seconds, set_preference,
+};
 use crate::infrastructure::tauri_adapter::{
-    fetch_autostart_enabled, set_autostart_enabled, AutostartStatus,
+    clear_error_log, copy_text, export_bundle, fetch_autostart_enabled, import_bundle,
+    load_monitors, load_recent_errors, load_unclean_exits_this_week, set_autostart_enabled,
+    validate_data_dir, AdapterError, AutostartStatus,
 };
+use crate::infrastructure::update_adapter::fetch_latest_version;
+use crate::presentation::lifecycle::MountGuard;
+use time_wise_shared::preferences::{MAX_DURATION_PRECISION, UNCATEGORIZED};
+
+/// Formats minutes after midnight as the `HH:MM` value of a time input.
+fn minutes_to_time_input(minutes: u32) -> String {
+    format!("{:02}:{:02}", (minutes / 60) % 24, minutes % 60)
+}
+
+/// Parses a time input `HH:MM` value into minutes after midnight.
+fn time_input_to_minutes(value: &str) -> Option<u32> {
+    let (hours, minutes) = value.split_once(':')?;
+    let hours: u32 = hours.parse().ok()?;
+    let minutes: u32 = minutes.parse().ok()?;
+    (hours < 24 && minutes < 60).then_some(hours * 60 + minutes)
+}
+
+/// Splits a comma-separated list of app names, dropping blanks.
+fn parse_app_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Apps unused for this many days are offered for cleanup.
+const STALE_APP_DAYS: u32 = 30;
+
+/// Largest bar count either chart mode accepts.
+const MAX_CHART_BARS: u32 = 24;
+
+/// Most app tiles the dashboard usage list can show.
+const MAX_USAGE_TILES: u32 = 30;
+
+/// Largest lifetime threshold, in polls, accepted for short-lived processes.
+const MAX_OBSERVED_POLLS: u32 = 20;
+
+/// Longest minimum app session, in seconds.
+const MAX_MIN_SESSION_SECONDS: u32 = 10 * 60;
+
+/// Largest minimum run count accepted for ranking launchers by slowdown.
+const MAX_LAUNCHER_RANKING_RUNS: u32 = 100;
+
+/// Highest startup floor, in milliseconds, below which runs go unrecorded.
+const MAX_MIN_RECORDABLE_MS: u64 = 10_000;
+
+/// Longest idle time, in seconds, before a tray-opened dashboard hides itself.
+const MAX_AUTO_HIDE_SECONDS: u32 = 60 * 60;
+
+/// Longest time, in minutes, an inactive app can stay listed.
+const MAX_INACTIVE_DISPLAY_MINUTES: u32 = 24 * 60;
+
+/// Longest break, in minutes, before reopening an app counts as a long gap.
+const MAX_WELCOME_BACK_MINUTES: u32 = 30 * 24 * 60;
+
+/// Largest daily budget, in minutes, an app can be given.
+const MAX_BUDGET_MINUTES: u32 = 24 * 60;
+
+/// How long "Pause for a day" keeps launch-on-boot off.
+const AUTOSTART_PAUSE_MS: u64 = 24 * 60 * 60 * 1_000;
+
+/// Returns true while launch-on-boot is temporarily paused.
+fn is_autostart_paused(paused_until_ms: Option<u64>, now_ms: u64) -> bool {
+    paused_until_ms.is_some_and(|until| now_ms < until)
+}
+
+/// Normalizes an avatar setting to `icon` or a single uppercase letter.
+fn parse_avatar(value: &str) -> Option<String> {
+    let value = value.trim();
+    if value.eq_ignore_ascii_case(AVATAR_ICON) {
+        return Some(AVATAR_ICON.to_string());
+    }
+    let mut chars = value.chars();
+    match (chars.next(), chars.next()) {
+        (Some(letter), None) if letter.is_alphanumeric() => Some(letter.to_uppercase().collect()),
+        _ => None,
+    }
+}
+
+/// Parses an hour of the day, `0..=23`.
+fn parse_hour(value: &str) -> Option<u32> {
+    let hour: u32 = value.trim().parse().ok()?;
+    (hour < 24).then_some(hour)
+}
+
+/// Parses a positive count, rejecting values outside `1..=max`.
+fn parse_bounded_count(value: &str, max: u32) -> Option<u32> {
+    let count: u32 = value.trim().parse().ok()?;
+    (1..=max).contains(&count).then_some(count)
+}
+
+/// Parses a decimal place count in `0..=MAX_DURATION_PRECISION`.
+fn parse_duration_precision(value: &str) -> Option<u32> {
+    let precision: u32 = value.trim().parse().ok()?;
+    (precision <= MAX_DURATION_PRECISION).then_some(precision)
+}
+
+/// Formats a byte count with the largest unit that keeps it above one.
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+/// Stores an app's daily budget, where zero minutes removes it, and refreshes the local copy.
+fn persist_app_budget(
+    name: String,
+    minutes: u32,
+    set_preferences: WriteSignal<Preferences>,
+    set_message: WriteSignal<Option<String>>,
+) {
+    spawn_local(async move {
+        match set_app_budget(&name, minutes).await {
+            Ok(updated) => set_preferences.set(updated),
+            Err(err) => set_message.set(Some(err.to_string())),
+        }
+    });
+}
+
+/// Adds a launcher to, or removes it from, the ignored set and refreshes the local copy.
+fn persist_launcher_ignored(
+    launcher: String,
+    ignored: bool,
+    set_preferences: WriteSignal<Preferences>,
+    set_message: WriteSignal<Option<String>>,
+) {
+    spawn_local(async move {
+        match set_launcher_ignored(&launcher, ignored).await {
+            Ok(updated) => set_preferences.set(updated),
+            Err(err) => set_message.set(Some(err.to_string())),
+        }
+    });
+}
+
+/// Persists a preference and refreshes the local copy with the stored result.
+fn persist_preference<T>(
+    key: &'static str,
+    value: T,
+    set_preferences: WriteSignal<Preferences>,
+    set_message: WriteSignal<Option<String>>,
+) where
+    T: serde::Serialize + 'static,
+{
+    spawn_local(async move {
+        match set_preference(key, &value).await {
+            Ok(updated) => set_preferences.set(updated),
+            Err(_) => set_message.set(Some("Could not save preference.".to_string())),
+        }
+    });
+}
+
+/// Describes the week's unexpected exits, such as "3 unexpected exits this week".
+fn unclean_exit_label(count: usize) -> String {
+    match count {
+        0 => "No unexpected exits this week".to_string(),
+        1 => "1 unexpected exit this week".to_string(),
+        count => format!("{count} unexpected exits this week"),
+    }
+}
+
+/// Most recent backend errors listed under Diagnostics.
+const RECENT_ERRORS_SHOWN: usize = 20;
 
 #[component]
 /// Settings screen exposing application preferences.
@@ -14,6 +207,32 @@ pub fn Settings() -> impl IntoView {
     let (loaded, set_loaded) = signal(false);
     let (status_message, set_status_message) = signal(None::<String>);
     let (saving, set_saving) = signal(false);
+    let (coverage_text, set_coverage_text) = signal(None::<String>);
+    let (available_update, set_available_update) = signal(None::<String>);
+    let (budget_app, set_budget_app) = signal(String::new());
+    let (budget_minutes, set_budget_minutes) = signal(String::new());
+    let (category_app, set_category_app) = signal(String::new());
+    let (category_name, set_category_name) = signal(String::new());
+    let (tracked_apps, set_tracked_apps) = signal(Vec::<String>::new());
+    let (category_drafts, set_category_drafts) = signal(BTreeMap::<String, String>::new());
+    let (relabel_from, set_relabel_from) = signal("unknown".to_string());
+    let (relabel_to, set_relabel_to) = signal(String::new());
+    let (relabel_status, set_relabel_status) = signal(None::<String>);
+    let (ignored_launcher, set_ignored_launcher) = signal(String::new());
+    let (stale_apps, set_stale_apps) = signal(None::<Result<Vec<(String, u64)>, AdapterError>>);
+    let (optimize_status, set_optimize_status) = signal(None::<String>);
+    let (optimizing, set_optimizing) = signal(false);
+    let (data_dir, set_data_dir) = signal(String::new());
+    let (data_dir_status, set_data_dir_status) = signal(None::<String>);
+    let (bundle_path, set_bundle_path) = signal(String::new());
+    let (bundle_status, set_bundle_status) = signal(None::<String>);
+    let (backup_status, set_backup_status) = signal(None::<String>);
+    let (recent_errors, set_recent_errors) = signal(Vec::<String>::new());
+    let (unclean_exits, set_unclean_exits) = signal(None::<usize>);
+    let (monitors, set_monitors) = signal(Vec::<String>::new());
+    let (preferences, set_preferences) = signal(Preferences::default());
+    // The window can close before the initial loads resolve.
+    let mount = MountGuard::for_current_owner();
 
     Effect::new(move |_| {
         if loaded.get() {
@@ -23,19 +242,60 @@ pub fn Settings() -> impl IntoView {
             let set_autostart = set_autostart_enabled_signal;
             let set_loaded = set_loaded;
             let set_message = set_status_message;
+            let mount = mount.clone();
             async move {
                 match fetch_autostart_enabled().await {
                     Ok(state) => {
-                        set_autostart.set(state);
-                        set_message.set(None);
+                        mount.set(set_autostart, state);
+                        mount.set(set_message, None);
                     }
-                    Err(()) => {
-                        set_message.set(Some(
-                            "Unable to load automatic launch preference.".to_string(),
-                        ));
+                    Err(_) => {
+                        mount.set(
+                            set_message,
+                            Some("Unable to load automatic launch preference.".to_string()),
+                        );
+                    }
+                }
+                mount.set(set_loaded, true);
+            }
+        });
+        let mount = mount.clone();
+        spawn_local(async move {
+            let (rollover_hour, update_endpoint) = match fetch_preferences().await {
+                Ok(stored) => {
+                    let rollover_hour = stored.day_rollover_hour;
+                    let update_endpoint =
+                        stored.network_endpoint(stored.update_check, &stored.update_endpoint);
+                    mount.set(set_preferences, stored);
+                    (rollover_hour, update_endpoint)
+                }
+                Err(_) => (0, None),
+            };
+            if !mount.is_mounted() {
+                return;
+            }
+            if let Ok(coverage) = load_tracking_coverage(start_of_today_ms(rollover_hour)).await {
+                mount.set(set_coverage_text, Some(coverage_label(&coverage)));
+            }
+            if let Ok(errors) = load_recent_errors(RECENT_ERRORS_SHOWN).await {
+                mount.set(set_recent_errors, errors);
+            }
+            if let Ok(count) = load_unclean_exits_this_week().await {
+                mount.set(set_unclean_exits, count);
+            }
+            if let Ok(names) = load_monitors().await {
+                mount.set(set_monitors, names);
+            }
+            if let Ok(names) = load_categorizable_apps().await {
+                mount.set(set_tracked_apps, names);
+            }
+            if let Some(endpoint) = update_endpoint.filter(|_| mount.is_mounted()) {
+                // Network failures and malformed versions simply leave the badge hidden.
+                if let Ok(latest) = fetch_latest_version(&endpoint).await {
+                    if is_newer_version(&latest, env!("CARGO_PKG_VERSION")) == Some(true) {
+                        mount.set(set_available_update, Some(latest));
                     }
                 }
-                set_loaded.set(true);
             }
         });
     });
@@ -45,6 +305,17 @@ pub fn Settings() -> impl IntoView {
             <section class="settings">
                 <header class="settings__header">
                     <h1 class="settings__title">"Settings"</h1>
+                    {move || {
+                        available_update
+                            .get()
+                            .map(|version| {
+                                view! {
+                                    <span class="settings__update-badge">
+                                        {format!("Update available: {version}")}
+                                    </span>
+                                }
+                            })
+                    }}
                     <p class="settings__subtitle">
                         "Control how Time Wise behaves on startup."
                     </p>
@@ -82,6 +353,10 @@ pub fn Settings() -> impl IntoView {
                                             set_autostart_enabled(desired).await;
                                         set_autostart.set(enabled);
                                         if success {
+                                            set_preferences.update(|preferences| {
+                                                preferences.autostart_intended = Some(desired);
+                                                preferences.autostart_paused_until_ms = None;
+                                            });
                                             set_message.set(None);
                                         } else {
                                             set_message.set(Some(
@@ -102,15 +377,1997 @@ pub fn Settings() -> impl IntoView {
                             </span>
                         </div>
                     </label>
-                    <Show when=move || status_message.get().is_some()>
-                        {move || {
-                            status_message
-                                .get()
-                                .map(|message| view! { <p class="settings__status">{message}</p> })
-                        }}
-                    </Show>
-                </div>
-            </section>
-        </main>
+                    <div class="settings__utility">
+                        <div class="settings__details">
+                            <span class="settings__label">"Pause automatic launch"</span>
+                            <span class="settings__description">
+                                {move || {
+                                    let paused_until = preferences.get().autostart_paused_until_ms;
+                                    if is_autostart_paused(paused_until, js_sys::Date::now() as u64) {
+                                        format!(
+                                            "Temporarily paused until {}; your launch preference is kept.",
+                                            format_timestamp(paused_until.unwrap_or_default()),
+                                        )
+                                    } else {
+                                        "Skip launching on boot for the next day without changing the setting above."
+                                            .to_string()
+                                    }
+                                }}
+                            </span>
+                        </div>
+                        <div class="settings__utility-row">
+                            <button
+                                type="button"
+                                class="settings__button"
+                                disabled=move || !loaded.get() || saving.get()
+                                on:click=move |_| {
+                                    let now_ms = js_sys::Date::now() as u64;
+                                    let until_ms = if is_autostart_paused(
+                                        preferences.get().autostart_paused_until_ms,
+                                        now_ms,
+                                    ) {
+                                        None
+                                    } else {
+                                        Some(now_ms + AUTOSTART_PAUSE_MS)
+                                    };
+                                    spawn_local(async move {
+                                        match set_autostart_paused_until(until_ms).await {
+                                            Ok(updated) => set_preferences.set(updated),
+                                            Err(err) => set_status_message.set(Some(err.to_string())),
+                                        }
+                                        if let Ok(enabled) = fetch_autostart_enabled().await {
+                                            set_autostart_enabled_signal.set(enabled);
+                                        }
+                                    });
+                                }
+                            >
+                                {move || {
+                                    if is_autostart_paused(
+                                        preferences.get().autostart_paused_until_ms,
+                                        js_sys::Date::now() as u64,
+                                    ) {
+                                        "Resume"
+                                    } else {
+                                        "Pause for a day"
+                                    }
+                                }}
+                            </button>
+                        </div>
+                    </div>
+                    <label class="settings__item">
+                        <input
+                            type="checkbox"
+                            class="settings__checkbox"
+                            prop:checked=move || preferences.get().window_effects
+                            on:change=move |ev| {
+                                persist_preference(
+                                    "window_effects",
+                                    event_target_checked(&ev),
+                                    set_preferences,
+                                    set_status_message,
+                                );
+                            }
+                        />
+                        <div class="settings__details">
+                            <span class="settings__label">"Translucent window"</span>
+                            <span class="settings__description">
+                                "Blur the desktop behind the dashboard on macOS and Windows."
+                            </span>
+                        </div>
+                    </label>
+                    <label class="settings__item">
+                        <input
+                            type="checkbox"
+                            class="settings__checkbox"
+                            prop:checked=move || preferences.get().show_startup_metrics
+                            on:change=move |ev| {
+                                persist_preference(
+                                    "show_startup_metrics",
+                                    event_target_checked(&ev),
+                                    set_preferences,
+                                    set_status_message,
+                                );
+                            }
+                        />
+                        <div class="settings__details">
+                            <span class="settings__label">"Startup metrics"</span>
+                            <span class="settings__description">
+                                "Record how long Time Wise takes to start and show the startup card. Turn off to track app usage only."
+                            </span>
+                        </div>
+                    </label>
+                    <label class="settings__item">
+                        <input
+                            type="checkbox"
+                            class="settings__checkbox"
+                            prop:checked=move || preferences.get().capture_environment
+                            on:change=move |ev| {
+                                persist_preference(
+                                    "capture_environment",
+                                    event_target_checked(&ev),
+                                    set_preferences,
+                                    set_status_message,
+                                );
+                            }
+                        />
+                        <div class="settings__details">
+                            <span class="settings__label">"Record launch conditions"</span>
+                            <span class="settings__description">
+                                "Save battery level and thermal state with each startup."
+                            </span>
+                        </div>
+                    </label>
+                    <label class="settings__item">
+                        <input
+                            type="checkbox"
+                            class="settings__checkbox"
+                            prop:checked=move || preferences.get().capture_cpu_time
+                            on:change=move |ev| {
+                                persist_preference(
+                                    "capture_cpu_time",
+                                    event_target_checked(&ev),
+                                    set_preferences,
+                                    set_status_message,
+                                );
+                            }
+                        />
+                        <div class="settings__details">
+                            <span class="settings__label">"Record startup CPU time"</span>
+                            <span class="settings__description">
+                                "Save the CPU time used before ready to show each run's CPU/wall ratio."
+                            </span>
+                        </div>
+                    </label>
+                    <label class="settings__item">
+                        <input
+                            type="checkbox"
+                            class="settings__checkbox"
+                            prop:checked=move || preferences.get().track_unclean_exits
+                            on:change=move |ev| {
+                                persist_preference(
+                                    "track_unclean_exits",
+                                    event_target_checked(&ev),
+                                    set_preferences,
+                                    set_status_message,
+                                );
+                            }
+                        />
+                        <div class="settings__details">
+                            <span class="settings__label">"Count unexpected exits"</span>
+                            <span class="settings__description">
+                                "Notice runs that ended without quitting and show the weekly count under Diagnostics. Takes effect on the next launch."
+                            </span>
+                        </div>
+                    </label>
+                    <label class="settings__item">
+                        <input
+                            type="checkbox"
+                            class="settings__checkbox"
+                            prop:checked=move || {
+                                preferences.get().startup_milestone == StartupMilestone::FirstPaint
+                            }
+                            on:change=move |ev| {
+                                let milestone = if event_target_checked(&ev) {
+                                    StartupMilestone::FirstPaint
+                                } else {
+                                    StartupMilestone::ProcessReady
+                                };
+                                persist_preference(
+                                    "startup_milestone",
+                                    milestone,
+                                    set_preferences,
+                                    set_status_message,
+                                );
+                            }
+                        />
+                        <div class="settings__details">
+                            <span class="settings__label">"Measure startup to first paint"</span>
+                            <span class="settings__description">
+                                "Stop the startup timer when the dashboard renders instead of when the app is ready."
+                            </span>
+                        </div>
+                    </label>
+                    <label class="settings__item">
+                        <input
+                            type="checkbox"
+                            class="settings__checkbox"
+                            prop:checked=move || {
+                                preferences.get().startup_measurement == StartupMeasurement::SinceBoot
+                            }
+                            on:change=move |ev| {
+                                let measurement = if event_target_checked(&ev) {
+                                    StartupMeasurement::SinceBoot
+                                } else {
+                                    StartupMeasurement::Process
+                                };
+                                persist_preference(
+                                    "startup_measurement",
+                                    measurement,
+                                    set_preferences,
+                                    set_status_message,
+                                );
+                            }
+                        />
+                        <div class="settings__details">
+                            <span class="settings__label">"Measure startup from system boot"</span>
+                            <span class="settings__description">
+                                "Time launch-on-boot starts from when the computer booted. Other launches keep measuring the app alone."
+                            </span>
+                        </div>
+                    </label>
+                    <label class="settings__item">
+                        <input
+                            type="checkbox"
+                            class="settings__checkbox"
+                            prop:checked=move || preferences.get().sound_feedback
+                            on:change=move |ev| {
+                                persist_preference(
+                                    "sound_feedback",
+                                    event_target_checked(&ev),
+                                    set_preferences,
+                                    set_status_message,
+                                );
+                            }
+                        />
+                        <div class="settings__details">
+                            <span class="settings__label">"Sound feedback"</span>
+                            <span class="settings__description">
+                                "Play a short sound when tracking is paused or resumed."
+                            </span>
+                        </div>
+                    </label>
+                    <label class="settings__item">
+                        <input
+                            type="checkbox"
+                            class="settings__checkbox"
+                            prop:checked=move || {
+                                preferences.get().tile_label_source == TileLabelSource::WindowTitle
+                            }
+                            on:change=move |ev| {
+                                let source = if event_target_checked(&ev) {
+                                    TileLabelSource::WindowTitle
+                                } else {
+                                    TileLabelSource::Name
+                                };
+                                persist_preference(
+                                    "tile_label_source",
+                                    source,
+                                    set_preferences,
+                                    set_status_message,
+                                );
+                            }
+                        />
+                        <div class="settings__details">
+                            <span class="settings__label">"Label apps by window title"</span>
+                            <span class="settings__description">
+                                "Show the window title instead of the process name when one was captured. Titles are read with wmctrl on Linux and need the Accessibility permission on macOS."
+                            </span>
+                        </div>
+                    </label>
+                    <label class="settings__item">
+                        <input
+                            type="checkbox"
+                            class="settings__checkbox"
+                            prop:checked=move || preferences.get().show_inactive_apps
+                            on:change=move |ev| {
+                                persist_preference(
+                                    "show_inactive_apps",
+                                    event_target_checked(&ev),
+                                    set_preferences,
+                                    set_status_message,
+                                );
+                            }
+                        />
+                        <div class="settings__details">
+                            <span class="settings__label">"Show inactive apps"</span>
+                            <span class="settings__description">
+                                "List apps that have closed alongside the ones running now."
+                            </span>
+                        </div>
+                    </label>
+                    <label class="settings__item">
+                        <input
+                            type="checkbox"
+                            class="settings__checkbox"
+                            prop:checked=move || preferences.get().canonical_app_names
+                            on:change=move |ev| {
+                                persist_preference(
+                                    "canonical_app_names",
+                                    event_target_checked(&ev),
+                                    set_preferences,
+                                    set_status_message,
+                                );
+                            }
+                        />
+                        <div class="settings__details">
+                            <span class="settings__label">"Same app names on every platform"</span>
+                            <span class="settings__description">
+                                "Record \"Code.exe\" and \"Code\" as one app so exports from macOS and Windows line up."
+                            </span>
+                        </div>
+                    </label>
+                    <div class="settings__utility">
+                        <div class="settings__details">
+                            <span class="settings__label">"Usage tiles"</span>
+                            <span class="settings__description">
+                                "Most apps shown in the dashboard usage list."
+                            </span>
+                        </div>
+                        <div class="settings__utility-row">
+                            <input
+                                type="number"
+                                class="settings__input"
+                                min="1"
+                                max=MAX_USAGE_TILES
+                                prop:value=move || preferences.get().usage_tile_limit.to_string()
+                                on:change=move |ev| {
+                                    if let Some(count) = parse_bounded_count(&event_target_value(&ev), MAX_USAGE_TILES) {
+                                        persist_preference(
+                                            "usage_tile_limit",
+                                            count,
+                                            set_preferences,
+                                            set_status_message,
+                                        );
+                                    }
+                                }
+                            />
+                        </div>
+                    </div>
+                    <div class="settings__utility">
+                        <div class="settings__details">
+                            <span class="settings__label">"Sort usage by"</span>
+                            <span class="settings__description">
+                                "Order of the apps in the dashboard usage list."
+                            </span>
+                        </div>
+                        <div class="settings__utility-row">
+                            <select
+                                class="settings__input"
+                                prop:value=move || preferences.get().usage_sort.key()
+                                on:change=move |ev| {
+                                    persist_preference(
+                                        "usage_sort",
+                                        UsageSort::from_key(&event_target_value(&ev)),
+                                        set_preferences,
+                                        set_status_message,
+                                    );
+                                }
+                            >
+                                {UsageSort::ALL
+                                    .into_iter()
+                                    .map(|sort| view! { <option value=sort.key()>{sort.label()}</option> })
+                                    .collect::<Vec<_>>()}
+                            </select>
+                        </div>
+                    </div>
+                    <div class="settings__utility">
+                        <div class="settings__details">
+                            <span class="settings__label">"Round usage totals"</span>
+                            <span class="settings__description">
+                                "Rounds the times shown on tiles and in summaries; recorded totals stay exact."
+                            </span>
+                        </div>
+                        <div class="settings__utility-row">
+                            <select
+                                class="settings__input"
+                                prop:value=move || preferences.get().usage_rounding.key()
+                                on:change=move |ev| {
+                                    persist_preference(
+                                        "usage_rounding",
+                                        UsageRounding::from_key(&event_target_value(&ev)),
+                                        set_preferences,
+                                        set_status_message,
+                                    );
+                                }
+                            >
+                                {UsageRounding::ALL
+                                    .into_iter()
+                                    .map(|rounding| {
+                                        view! { <option value=rounding.key()>{rounding.label()}</option> }
+                                    })
+                                    .collect::<Vec<_>>()}
+                            </select>
+                        </div>
+                    </div>
+                    <label class="settings__item">
+                        <input
+                            type="checkbox"
+                            class="settings__checkbox"
+                            prop:checked=move || preferences.get().show_raw_durations
+                            on:change=move |ev| {
+                                persist_preference(
+                                    "show_raw_durations",
+                                    event_target_checked(&ev),
+                                    set_preferences,
+                                    set_status_message,
+                                );
+                            }
+                        />
+                        <div class="settings__details">
+                            <span class="settings__label">"Show exact milliseconds on hover"</span>
+                            <span class="settings__description">
+                                "Hovering a duration on the dashboard reveals the raw value it was rounded from."
+                            </span>
+                        </div>
+                    </label>
+                    <label class="settings__item">
+                        <input
+                            type="checkbox"
+                            class="settings__checkbox"
+                            prop:checked=move || preferences.get().auto_hide_on_blur
+                            on:change=move |ev| {
+                                persist_preference(
+                                    "auto_hide_on_blur",
+                                    event_target_checked(&ev),
+                                    set_preferences,
+                                    set_status_message,
+                                );
+                            }
+                        />
+                        <div class="settings__details">
+                            <span class="settings__label">"Hide dashboard when clicking away"</span>
+                            <span class="settings__description">
+                                "Close the dashboard as soon as another window takes focus."
+                            </span>
+                        </div>
+                    </label>
+                    <label class="settings__item">
+                        <input
+                            type="checkbox"
+                            class="settings__checkbox"
+                            prop:checked=move || preferences.get().dashboard_pinned
+                            on:change=move |ev| {
+                                persist_preference(
+                                    "dashboard_pinned",
+                                    event_target_checked(&ev),
+                                    set_preferences,
+                                    set_status_message,
+                                );
+                            }
+                        />
+                        <div class="settings__details">
+                            <span class="settings__label">"Pin dashboard"</span>
+                            <span class="settings__description">
+                                "Keep the dashboard open until you toggle it, even when clicking away."
+                            </span>
+                        </div>
+                    </label>
+                    <div class="settings__utility">
+                        <div class="settings__details">
+                            <span class="settings__label">"Open dashboard on"</span>
+                            <span class="settings__description">
+                                "Falls back to the tray icon's display when the chosen monitor is not connected."
+                            </span>
+                        </div>
+                        <div class="settings__utility-row">
+                            <select
+                                class="settings__input"
+                                prop:value=move || preferences.get().preferred_monitor
+                                on:change=move |ev| {
+                                    persist_preference(
+                                        "preferred_monitor",
+                                        event_target_value(&ev),
+                                        set_preferences,
+                                        set_status_message,
+                                    );
+                                }
+                            >
+                                <option value="">"Follow the tray icon"</option>
+                                {move || {
+                                    let stored = preferences.get().preferred_monitor;
+                                    let mut names = monitors.get();
+                                    let disconnected = !stored.is_empty()
+                                        && !names.iter().any(|name| name.eq_ignore_ascii_case(&stored));
+                                    if disconnected {
+                                        names.push(stored);
+                                    }
+                                    names
+                                        .into_iter()
+                                        .map(|name| {
+                                            let value = name.clone();
+                                            view! { <option value=value>{name}</option> }
+                                        })
+                                        .collect::<Vec<_>>()
+                                }}
+                            </select>
+                        </div>
+                    </div>
+                    <label class="settings__item">
+                        <input
+                            type="checkbox"
+                            class="settings__checkbox"
+                            prop:checked=move || preferences.get().weekly_summary
+                            on:change=move |ev| {
+                                persist_preference(
+                                    "weekly_summary",
+                                    event_target_checked(&ev),
+                                    set_preferences,
+                                    set_status_message,
+                                );
+                            }
+                        />
+                        <div class="settings__details">
+                            <span class="settings__label">"Weekly summary"</span>
+                            <span class="settings__description">
+                                "Notify me with a digest of the previous week when a new week begins."
+                            </span>
+                        </div>
+                    </label>
+                    <div class="settings__utility">
+                        <div class="settings__utility-row">
+                            <span class="settings__description">"Weeks start on"</span>
+                            <select
+                                class="settings__input"
+                                prop:value=move || preferences.get().week_start.key()
+                                on:change=move |ev| {
+                                    persist_preference(
+                                        "week_start",
+                                        WeekStart::from_key(&event_target_value(&ev)),
+                                        set_preferences,
+                                        set_status_message,
+                                    );
+                                }
+                            >
+                                {WeekStart::ALL
+                                    .into_iter()
+                                    .map(|start| {
+                                        view! { <option value=start.key()>{start.label()}</option> }
+                                    })
+                                    .collect::<Vec<_>>()}
+                            </select>
+                        </div>
+                    </div>
+                    <label class="settings__item">
+                        <input
+                            type="checkbox"
+                            class="settings__checkbox"
+                            prop:checked=move || preferences.get().habit_reminders
+                            on:change=move |ev| {
+                                persist_preference(
+                                    "habit_reminders",
+                                    event_target_checked(&ev),
+                                    set_preferences,
+                                    set_status_message,
+                                );
+                            }
+                        />
+                        <div class="settings__details">
+                            <span class="settings__label">"Habit reminders"</span>
+                            <span class="settings__description">
+                                "Notify me if a pinned app hasn't been opened by the chosen time."
+                            </span>
+                        </div>
+                    </label>
+                    <div class="settings__utility">
+                        <div class="settings__utility-row">
+                            <input
+                                type="text"
+                                class="settings__input"
+                                placeholder="Pinned apps, comma separated"
+                                prop:value=move || preferences.get().pinned_apps.join(", ")
+                                on:change=move |ev| {
+                                    persist_preference(
+                                        "pinned_apps",
+                                        parse_app_list(&event_target_value(&ev)),
+                                        set_preferences,
+                                        set_status_message,
+                                    );
+                                }
+                            />
+                            <input
+                                type="time"
+                                class="settings__input"
+                                prop:value=move || {
+                                    minutes_to_time_input(preferences.get().habit_reminder_minutes)
+                                }
+                                on:change=move |ev| {
+                                    if let Some(minutes) = time_input_to_minutes(&event_target_value(&ev)) {
+                                        persist_preference(
+                                            "habit_reminder_minutes",
+                                            minutes,
+                                            set_preferences,
+                                            set_status_message,
+                                        );
+                                    }
+                                }
+                            />
+                        </div>
+                    </div>
+                    <label class="settings__item">
+                        <input
+                            type="checkbox"
+                            class="settings__checkbox"
+                            prop:checked=move || preferences.get().builtin_blocklist
+                            on:change=move |ev| {
+                                persist_preference(
+                                    "builtin_blocklist",
+                                    event_target_checked(&ev),
+                                    set_preferences,
+                                    set_status_message,
+                                );
+                            }
+                        />
+                        <div class="settings__details">
+                            <span class="settings__label">"Hide system processes"</span>
+                            <span class="settings__description">
+                                "Skip well-known system noise such as the Dock or Explorer."
+                            </span>
+                        </div>
+                    </label>
+                    <div class="settings__utility">
+                        <div class="settings__utility-row">
+                            <input
+                                type="text"
+                                class="settings__input"
+                                placeholder="Never track, comma separated"
+                                prop:value=move || preferences.get().blocked_apps.join(", ")
+                                on:change=move |ev| {
+                                    persist_preference(
+                                        "blocked_apps",
+                                        parse_app_list(&event_target_value(&ev)),
+                                        set_preferences,
+                                        set_status_message,
+                                    );
+                                }
+                            />
+                        </div>
+                    </div>
+                    <label class="settings__item">
+                        <input
+                            type="checkbox"
+                            class="settings__checkbox"
+                            prop:checked=move || preferences.get().allowlist_mode
+                            on:change=move |ev| {
+                                persist_preference(
+                                    "allowlist_mode",
+                                    event_target_checked(&ev),
+                                    set_preferences,
+                                    set_status_message,
+                                );
+                            }
+                        />
+                        <div class="settings__details">
+                            <span class="settings__label">"Track only listed apps"</span>
+                            <span class="settings__description">
+                                "Ignore every app that is not on the allowlist below."
+                            </span>
+                        </div>
+                    </label>
+                    <div class="settings__utility">
+                        <div class="settings__utility-row">
+                            <input
+                                type="text"
+                                class="settings__input"
+                                placeholder="Always track, comma separated"
+                                prop:value=move || preferences.get().allowed_apps.join(", ")
+                                on:change=move |ev| {
+                                    persist_preference(
+                                        "allowed_apps",
+                                        parse_app_list(&event_target_value(&ev)),
+                                        set_preferences,
+                                        set_status_message,
+                                    );
+                                }
+                            />
+                            <button
+                                type="button"
+                                class="settings__button"
+                                on:click=move |_| {
+                                    spawn_local(async move {
+                                        let message = match seed_allowlist_from_current().await {
+                                            Ok(added) => format!("Added {added} apps to the allowlist."),
+                                            Err(err) => err.to_string(),
+                                        };
+                                        set_status_message.set(Some(message));
+                                        if let Ok(updated) = fetch_preferences().await {
+                                            set_preferences.set(updated);
+                                        }
+                                    });
+                                }
+                            >
+                                "Track currently-open apps"
+                            </button>
+                        </div>
+                    </div>
+                    <label class="settings__item">
+                        <input
+                            type="checkbox"
+                            class="settings__checkbox"
+                            prop:checked=move || preferences.get().offline_mode
+                            on:change=move |ev| {
+                                persist_preference(
+                                    "offline_mode",
+                                    event_target_checked(&ev),
+                                    set_preferences,
+                                    set_status_message,
+                                );
+                            }
+                        />
+                        <div class="settings__details">
+                            <span class="settings__label">"Offline mode"</span>
+                            <span class="settings__description">
+                                "Never contact the community or update endpoints, even when enabled."
+                            </span>
+                        </div>
+                    </label>
+                    <label class="settings__item">
+                        <input
+                            type="checkbox"
+                            class="settings__checkbox"
+                            prop:checked=move || preferences.get().community_comparison
+                            on:change=move |ev| {
+                                persist_preference(
+                                    "community_comparison",
+                                    event_target_checked(&ev),
+                                    set_preferences,
+                                    set_status_message,
+                                );
+                            }
+                        />
+                        <div class="settings__details">
+                            <span class="settings__label">"Compare with community"</span>
+                            <span class="settings__description">
+                                "Fetch an anonymized median startup for your OS from the endpoint below."
+                            </span>
+                        </div>
+                    </label>
+                    <div class="settings__utility">
+                        <div class="settings__utility-row">
+                            <input
+                                type="url"
+                                class="settings__input"
+                                placeholder="https://example.com/baseline"
+                                prop:value=move || preferences.get().community_endpoint
+                                on:change=move |ev| {
+                                    persist_preference(
+                                        "community_endpoint",
+                                        event_target_value(&ev).trim().to_string(),
+                                        set_preferences,
+                                        set_status_message,
+                                    );
+                                }
+                            />
+                        </div>
+                    </div>
+                    <label class="settings__item">
+                        <input
+                            type="checkbox"
+                            class="settings__checkbox"
+                            prop:checked=move || preferences.get().update_check
+                            on:change=move |ev| {
+                                persist_preference(
+                                    "update_check",
+                                    event_target_checked(&ev),
+                                    set_preferences,
+                                    set_status_message,
+                                );
+                            }
+                        />
+                        <div class="settings__details">
+                            <span class="settings__label">"Check for updates"</span>
+                            <span class="settings__description">
+                                "Ask the endpoint below for the latest version when Settings opens."
+                            </span>
+                        </div>
+                    </label>
+                    <div class="settings__utility">
+                        <div class="settings__utility-row">
+                            <input
+                                type="url"
+                                class="settings__input"
+                                placeholder="https://example.com/latest-version"
+                                prop:value=move || preferences.get().update_endpoint
+                                on:change=move |ev| {
+                                    persist_preference(
+                                        "update_endpoint",
+                                        event_target_value(&ev).trim().to_string(),
+                                        set_preferences,
+                                        set_status_message,
+                                    );
+                                }
+                            />
+                        </div>
+                    </div>
+                    <div class="settings__utility">
+                        <div class="settings__details">
+                            <span class="settings__label">"Ignore short-lived processes"</span>
+                            <span class="settings__description">
+                                "Polls an app must stay open before it appears in usage."
+                            </span>
+                        </div>
+                        <div class="settings__utility-row">
+                            <input
+                                type="number"
+                                class="settings__input"
+                                min="1"
+                                max=MAX_OBSERVED_POLLS
+                                prop:value=move || preferences.get().min_observed_polls.to_string()
+                                on:change=move |ev| {
+                                    if let Some(polls) = parse_bounded_count(
+                                        &event_target_value(&ev),
+                                        MAX_OBSERVED_POLLS,
+                                    ) {
+                                        persist_preference(
+                                            "min_observed_polls",
+                                            polls,
+                                            set_preferences,
+                                            set_status_message,
+                                        );
+                                    }
+                                }
+                            />
+                        </div>
+                    </div>
+                    <div class="settings__utility">
+                        <div class="settings__details">
+                            <span class="settings__label">"Minimum session (seconds)"</span>
+                            <span class="settings__description">
+                                "App sessions shorter than this, like accidental opens, do not count toward usage. 0 counts everything."
+                            </span>
+                        </div>
+                        <div class="settings__utility-row">
+                            <input
+                                type="number"
+                                class="settings__input"
+                                min="0"
+                                max=MAX_MIN_SESSION_SECONDS
+                                prop:value=move || preferences.get().min_session_seconds.to_string()
+                                on:change=move |ev| {
+                                    let seconds = event_target_value(&ev)
+                                        .trim()
+                                        .parse::<u32>()
+                                        .ok()
+                                        .filter(|seconds| *seconds <= MAX_MIN_SESSION_SECONDS);
+                                    if let Some(seconds) = seconds {
+                                        spawn_local(async move {
+                                            match set_min_session_seconds(seconds).await {
+                                                Ok(updated) => set_preferences.set(updated),
+                                                Err(err) => set_status_message.set(Some(err.to_string())),
+                                            }
+                                        });
+                                    }
+                                }
+                            />
+                        </div>
+                    </div>
+                    <div class="settings__utility">
+                        <div class="settings__details">
+                            <span class="settings__label">"Auto-hide after (seconds)"</span>
+                            <span class="settings__description">
+                                "Hide the dashboard when it sits untouched this long. 0 keeps it open; pinning also disables it."
+                            </span>
+                        </div>
+                        <div class="settings__utility-row">
+                            <input
+                                type="number"
+                                class="settings__input"
+                                min="0"
+                                max=MAX_AUTO_HIDE_SECONDS
+                                prop:value=move || preferences.get().auto_hide_seconds.to_string()
+                                on:change=move |ev| {
+                                    let seconds = event_target_value(&ev)
+                                        .trim()
+                                        .parse::<u32>()
+                                        .ok()
+                                        .filter(|seconds| *seconds <= MAX_AUTO_HIDE_SECONDS);
+                                    if let Some(seconds) = seconds {
+                                        persist_preference(
+                                            "auto_hide_seconds",
+                                            seconds,
+                                            set_preferences,
+                                            set_status_message,
+                                        );
+                                    }
+                                }
+                            />
+                        </div>
+                    </div>
+                    <div class="settings__utility">
+                        <div class="settings__details">
+                            <span class="settings__label">"Minimum recorded startup (ms)"</span>
+                            <span class="settings__description">
+                                "Skip startups faster than this, such as warm relaunches. 0 records every run."
+                            </span>
+                        </div>
+                        <div class="settings__utility-row">
+                            <input
+                                type="number"
+                                class="settings__input"
+                                min="0"
+                                max=MAX_MIN_RECORDABLE_MS
+                                prop:value=move || preferences.get().min_recordable_ms.to_string()
+                                on:change=move |ev| {
+                                    let floor_ms = event_target_value(&ev)
+                                        .trim()
+                                        .parse::<u64>()
+                                        .ok()
+                                        .filter(|floor_ms| *floor_ms <= MAX_MIN_RECORDABLE_MS);
+                                    if let Some(floor_ms) = floor_ms {
+                                        persist_preference(
+                                            "min_recordable_ms",
+                                            floor_ms,
+                                            set_preferences,
+                                            set_status_message,
+                                        );
+                                    }
+                                }
+                            />
+                        </div>
+                    </div>
+                    <div class="settings__utility">
+                        <div class="settings__details">
+                            <span class="settings__label">"Launcher ranking threshold"</span>
+                            <span class="settings__description">
+                                "Runs a launcher needs before it is ranked by how much it slows startup."
+                            </span>
+                        </div>
+                        <div class="settings__utility-row">
+                            <input
+                                type="number"
+                                class="settings__input"
+                                min="1"
+                                max=MAX_LAUNCHER_RANKING_RUNS
+                                prop:value=move || preferences.get().launcher_ranking_min_runs.to_string()
+                                on:change=move |ev| {
+                                    if let Some(runs) = parse_bounded_count(
+                                        &event_target_value(&ev),
+                                        MAX_LAUNCHER_RANKING_RUNS,
+                                    ) {
+                                        persist_preference(
+                                            "launcher_ranking_min_runs",
+                                            runs,
+                                            set_preferences,
+                                            set_status_message,
+                                        );
+                                    }
+                                }
+                            />
+                        </div>
+                    </div>
+                    <div class="settings__utility">
+                        <div class="settings__details">
+                            <span class="settings__label">"Avatar"</span>
+                            <span class="settings__description">
+                                "A single letter, or \"icon\" to show the app icon on the dashboard."
+                            </span>
+                        </div>
+                        <div class="settings__utility-row">
+                            <input
+                                type="text"
+                                class="settings__input"
+                                prop:value=move || preferences.get().avatar
+                                on:change=move |ev| {
+                                    if let Some(avatar) = parse_avatar(&event_target_value(&ev)) {
+                                        persist_preference(
+                                            "avatar",
+                                            avatar,
+                                            set_preferences,
+                                            set_status_message,
+                                        );
+                                    }
+                                }
+                            />
+                        </div>
+                    </div>
+                    <div class="settings__utility">
+                        <div class="settings__details">
+                            <span class="settings__label">"Day starts at"</span>
+                            <span class="settings__description">
+                                "Hour (0–23) when a new day begins; earlier usage counts toward the previous day."
+                            </span>
+                        </div>
+                        <div class="settings__utility-row">
+                            <input
+                                type="number"
+                                class="settings__input"
+                                min="0"
+                                max="23"
+                                prop:value=move || preferences.get().day_rollover_hour.to_string()
+                                on:change=move |ev| {
+                                    if let Some(hour) = parse_hour(&event_target_value(&ev)) {
+                                        persist_preference(
+                                            "day_rollover_hour",
+                                            hour,
+                                            set_preferences,
+                                            set_status_message,
+                                        );
+                                    }
+                                }
+                            />
+                        </div>
+                    </div>
+                    <div class="settings__utility">
+                        <div class="settings__details">
+                            <span class="settings__label">"Hide inactive apps after"</span>
+                            <span class="settings__description">
+                                "Minutes a closed app stays in the usage list. Its total is kept."
+                            </span>
+                        </div>
+                        <div class="settings__utility-row">
+                            <input
+                                type="number"
+                                class="settings__input"
+                                min="1"
+                                max=MAX_INACTIVE_DISPLAY_MINUTES
+                                prop:value=move || {
+                                    preferences.get().inactive_display_minutes.to_string()
+                                }
+                                on:change=move |ev| {
+                                    if let Some(minutes) = parse_bounded_count(
+                                        &event_target_value(&ev),
+                                        MAX_INACTIVE_DISPLAY_MINUTES,
+                                    ) {
+                                        persist_preference(
+                                            "inactive_display_minutes",
+                                            minutes,
+                                            set_preferences,
+                                            set_status_message,
+                                        );
+                                    }
+                                }
+                            />
+                        </div>
+                    </div>
+                    <label class="settings__item">
+                        <input
+                            type="checkbox"
+                            class="settings__checkbox"
+                            prop:checked=move || preferences.get().align_polls
+                            on:change=move |ev| {
+                                persist_preference(
+                                    "align_polls",
+                                    event_target_checked(&ev),
+                                    set_preferences,
+                                    set_status_message,
+                                );
+                            }
+                        />
+                        <div class="settings__details">
+                            <span class="settings__label">"Align polls to the clock"</span>
+                            <span class="settings__description">
+                                "Check running apps at :00, :15, :30 and :45 seconds so hour and day totals split cleanly."
+                            </span>
+                        </div>
+                    </label>
+                    <div class="settings__utility">
+                        <div class="settings__details">
+                            <span class="settings__label">"Startup time decimals"</span>
+                            <span class="settings__description">
+                                "Decimal places shown for startups of a second or more."
+                            </span>
+                        </div>
+                        <div class="settings__utility-row">
+                            <input
+                                type="number"
+                                class="settings__input"
+                                min="0"
+                                max=MAX_DURATION_PRECISION
+                                prop:value=move || preferences.get().duration_precision.to_string()
+                                on:change=move |ev| {
+                                    if let Some(precision) = parse_duration_precision(&event_target_value(&ev)) {
+                                        persist_preference(
+                                            "duration_precision",
+                                            precision,
+                                            set_preferences,
+                                            set_status_message,
+                                        );
+                                    }
+                                }
+                            />
+                        </div>
+                    </div>
+                    <div class="settings__utility">
+                        <div class="settings__details">
+                            <span class="settings__label">"Startup chart bars"</span>
+                            <span class="settings__description">
+                                "Bars for recent runs and buckets for the distribution view."
+                            </span>
+                        </div>
+                        <div class="settings__utility-row">
+                            <input
+                                type="number"
+                                class="settings__input"
+                                min="1"
+                                max=MAX_CHART_BARS
+                                prop:value=move || preferences.get().recent_chart_bars.to_string()
+                                on:change=move |ev| {
+                                    if let Some(count) = parse_bounded_count(&event_target_value(&ev), MAX_CHART_BARS) {
+                                        persist_preference(
+                                            "recent_chart_bars",
+                                            count,
+                                            set_preferences,
+                                            set_status_message,
+                                        );
+                                    }
+                                }
+                            />
+                            <input
+                                type="number"
+                                class="settings__input"
+                                min="1"
+                                max=MAX_CHART_BARS
+                                prop:value=move || {
+                                    preferences.get().distribution_chart_bars.to_string()
+                                }
+                                on:change=move |ev| {
+                                    if let Some(count) = parse_bounded_count(&event_target_value(&ev), MAX_CHART_BARS) {
+                                        persist_preference(
+                                            "distribution_chart_bars",
+                                            count,
+                                            set_preferences,
+                                            set_status_message,
+                                        );
+                                    }
+                                }
+                            />
+                        </div>
+                    </div>
+                    <label class="settings__item">
+                        <input
+                            type="checkbox"
+                            class="settings__checkbox"
+                            prop:checked=move || preferences.get().welcome_back
+                            on:change=move |ev| {
+                                persist_preference(
+                                    "welcome_back",
+                                    event_target_checked(&ev),
+                                    set_preferences,
+                                    set_status_message,
+                                );
+                            }
+                        />
+                        <div class="settings__details">
+                            <span class="settings__label">"Welcome back"</span>
+                            <span class="settings__description">
+                                "Notify me when I reopen one of these apps after a long break."
+                            </span>
+                        </div>
+                    </label>
+                    <div class="settings__utility">
+                        <div class="settings__utility-row">
+                            <input
+                                type="text"
+                                class="settings__input"
+                                placeholder="Apps, comma separated"
+                                prop:value=move || preferences.get().welcome_back_apps.join(", ")
+                                on:change=move |ev| {
+                                    persist_preference(
+                                        "welcome_back_apps",
+                                        parse_app_list(&event_target_value(&ev)),
+                                        set_preferences,
+                                        set_status_message,
+                                    );
+                                }
+                            />
+                            <input
+                                type="number"
+                                class="settings__input"
+                                min="1"
+                                max=MAX_WELCOME_BACK_MINUTES
+                                title="Minutes away before it counts as a long break"
+                                prop:value=move || preferences.get().welcome_back_minutes.to_string()
+                                on:change=move |ev| {
+                                    if let Some(minutes) = parse_bounded_count(
+                                        &event_target_value(&ev),
+                                        MAX_WELCOME_BACK_MINUTES,
+                                    ) {
+                                        persist_preference(
+                                            "welcome_back_minutes",
+                                            minutes,
+                                            set_preferences,
+                                            set_status_message,
+                                        );
+                                    }
+                                }
+                            />
+                        </div>
+                    </div>
+                    <div class="settings__utility">
+                        <div class="settings__details">
+                            <span class="settings__label">"Daily budgets"</span>
+                            <span class="settings__description">
+                                "Get notified at 80% and 100% of an app's daily time."
+                            </span>
+                        </div>
+                        <ul class="settings__list">
+                            {move || {
+                                preferences
+                                    .get()
+                                    .app_budgets
+                                    .into_iter()
+                                    .map(|(name, minutes)| {
+                                        let remove_name = name.clone();
+                                        view! {
+                                            <li class="settings__description">
+                                                {format!("{name} — {minutes} min")}
+                                                " "
+                                                <button
+                                                    type="button"
+                                                    class="settings__button"
+                                                    on:click=move |_| {
+                                                        persist_app_budget(
+                                                            remove_name.clone(),
+                                                            0,
+                                                            set_preferences,
+                                                            set_status_message,
+                                                        );
+                                                    }
+                                                >
+                                                    "Remove"
+                                                </button>
+                                            </li>
+                                        }
+                                    })
+                                    .collect::<Vec<_>>()
+                            }}
+                        </ul>
+                        <div class="settings__utility-row">
+                            <input
+                                type="text"
+                                class="settings__input"
+                                placeholder="App name"
+                                prop:value=move || budget_app.get()
+                                on:input=move |ev| set_budget_app.set(event_target_value(&ev))
+                            />
+                            <input
+                                type="number"
+                                class="settings__input"
+                                min="1"
+                                max=MAX_BUDGET_MINUTES
+                                placeholder="Minutes"
+                                prop:value=move || budget_minutes.get()
+                                on:input=move |ev| set_budget_minutes.set(event_target_value(&ev))
+                            />
+                            <button
+                                type="button"
+                                class="settings__button"
+                                disabled=move || {
+                                    budget_app.get().trim().is_empty()
+                                        || parse_bounded_count(&budget_minutes.get(), MAX_BUDGET_MINUTES)
+                                            .is_none()
+                                }
+                                on:click=move |_| {
+                                    let Some(minutes) = parse_bounded_count(
+                                        &budget_minutes.get(),
+                                        MAX_BUDGET_MINUTES,
+                                    ) else {
+                                        return;
+                                    };
+                                    persist_app_budget(
+                                        budget_app.get().trim().to_string(),
+                                        minutes,
+                                        set_preferences,
+                                        set_status_message,
+                                    );
+                                    set_budget_app.set(String::new());
+                                    set_budget_minutes.set(String::new());
+                                }
+                            >
+                                "Set budget"
+                            </button>
+                        </div>
+                    </div>
+                    <div class="settings__utility">
+                        <div class="settings__details">
+                            <span class="settings__label">"Categories"</span>
+                            <span class="settings__description">
+                                {format!(
+                                    "Group apps for the dashboard breakdown. Leave the category blank to clear it; unassigned apps count as {UNCATEGORIZED}."
+                                )}
+                            </span>
+                        </div>
+                        <div class="settings__utility-row">
+                            <input
+                                type="text"
+                                class="settings__input"
+                                placeholder="App name"
+                                prop:value=move || category_app.get()
+                                on:input=move |ev| set_category_app.set(event_target_value(&ev))
+                            />
+                            <input
+                                type="text"
+                                class="settings__input"
+                                placeholder="Work, Social…"
+                                prop:value=move || category_name.get()
+                                on:input=move |ev| set_category_name.set(event_target_value(&ev))
+                            />
+                            <button
+                                type="button"
+                                class="settings__button"
+                                disabled=move || category_app.get().trim().is_empty()
+                                on:click=move |_| {
+                                    let name = category_app.get();
+                                    let category = category_name.get();
+                                    spawn_local(async move {
+                                        if let Err(err) = set_app_category(&name, &category).await {
+                                            set_status_message.set(Some(err.to_string()));
+                                        }
+                                    });
+                                    set_category_app.set(String::new());
+                                    set_category_name.set(String::new());
+                                }
+                            >
+                                "Assign"
+                            </button>
+                        </div>
+                        <datalist id="settings-known-categories">
+                            {move || {
+                                let known: BTreeSet<String> = preferences
+                                    .get()
+                                    .app_categories
+                                    .into_values()
+                                    .collect();
+                                known
+                                    .into_iter()
+                                    .map(|category| view! { <option value=category></option> })
+                                    .collect::<Vec<_>>()
+                            }}
+                        </datalist>
+                        <table class="settings__category-table">
+                            <tbody>
+                                {move || {
+                                    tracked_apps
+                                        .get()
+                                        .into_iter()
+                                        .map(|name| {
+                                            let value_name = name.clone();
+                                            let draft_name = name.clone();
+                                            view! {
+                                                <tr>
+                                                    <td class="settings__description">{name}</td>
+                                                    <td>
+                                                        <input
+                                                            type="text"
+                                                            class="settings__input"
+                                                            list="settings-known-categories"
+                                                            placeholder=UNCATEGORIZED
+                                                            prop:value=move || {
+                                                                category_drafts
+                                                                    .get()
+                                                                    .get(&value_name)
+                                                                    .cloned()
+                                                                    .or_else(|| {
+                                                                        preferences
+                                                                            .get()
+                                                                            .app_categories
+                                                                            .get(&value_name)
+                                                                            .cloned()
+                                                                    })
+                                                                    .unwrap_or_default()
+                                                            }
+                                                            on:input=move |ev| {
+                                                                let category = event_target_value(&ev);
+                                                                set_category_drafts
+                                                                    .update(|drafts| {
+                                                                        drafts.insert(draft_name.clone(), category);
+                                                                    });
+                                                            }
+                                                        />
+                                                    </td>
+                                                </tr>
+                                            }
+                                        })
+                                        .collect::<Vec<_>>()
+                                }}
+                            </tbody>
+                        </table>
+                        <div class="settings__utility-row">
+                            <button
+                                type="button"
+                                class="settings__button"
+                                disabled=move || category_drafts.get().is_empty()
+                                on:click=move |_| {
+                                    let assignments: Vec<(String, String)> = category_drafts
+                                        .get()
+                                        .into_iter()
+                                        .collect();
+                                    spawn_local(async move {
+                                        match set_app_categories(&assignments).await {
+                                            Ok(count) => {
+                                                set_category_drafts.set(BTreeMap::new());
+                                                set_status_message
+                                                    .set(Some(format!("Saved {count} categories.")));
+                                                if let Ok(updated) = fetch_preferences().await {
+                                                    set_preferences.set(updated);
+                                                }
+                                            }
+                                            Err(err) => set_status_message.set(Some(err.to_string())),
+                                        }
+                                    });
+                                }
+                            >
+                                "Save categories"
+                            </button>
+                        </div>
+                    </div>
+                    <div class="settings__utility">
+                        <div class="settings__details">
+                            <span class="settings__label">"Relabel launcher"</span>
+                            <span class="settings__description">
+                                "Rename a launcher across all recorded startups."
+                            </span>
+                        </div>
+                        <div class="settings__utility-row">
+                            <input
+                                type="text"
+                                class="settings__input"
+                                placeholder="From"
+                                prop:value=move || relabel_from.get()
+                                on:input=move |ev| set_relabel_from.set(event_target_value(&ev))
+                            />
+                            <input
+                                type="text"
+                                class="settings__input"
+                                placeholder="To"
+                                prop:value=move || relabel_to.get()
+                                on:input=move |ev| set_relabel_to.set(event_target_value(&ev))
+                            />
+                            <button
+                                type="button"
+                                class="settings__button"
+                                disabled=move || relabel_to.get().trim().is_empty()
+                                on:click=move |_| {
+                                    let from = relabel_from.get();
+                                    let to = relabel_to.get();
+                                    spawn_local(async move {
+                                        let message = match relabel_launcher(&from, &to).await {
+                                            Ok(1) => "Updated 1 run.".to_string(),
+                                            Ok(count) => format!("Updated {count} runs."),
+                                            Err(err) => err.to_string(),
+                                        };
+                                        set_relabel_status.set(Some(message));
+                                    });
+                                }
+                            >
+                                "Relabel"
+                            </button>
+                        </div>
+                        {move || {
+                            relabel_status
+                                .get()
+                                .map(|message| {
+                                    view! { <span class="settings__description">{message}</span> }
+                                })
+                        }}
+                    </div>
+                    <div class="settings__utility">
+                        <div class="settings__details">
+                            <span class="settings__label">"Ignored launchers"</span>
+                            <span class="settings__description">
+                                "Runs from these launchers are kept but left out of launcher stats."
+                            </span>
+                        </div>
+                        <div class="settings__utility-row">
+                            <input
+                                type="text"
+                                class="settings__input"
+                                placeholder="Launcher"
+                                prop:value=move || ignored_launcher.get()
+                                on:input=move |ev| set_ignored_launcher.set(event_target_value(&ev))
+                            />
+                            <button
+                                type="button"
+                                class="settings__button"
+                                disabled=move || ignored_launcher.get().trim().is_empty()
+                                on:click=move |_| {
+                                    persist_launcher_ignored(
+                                        ignored_launcher.get().trim().to_string(),
+                                        true,
+                                        set_preferences,
+                                        set_status_message,
+                                    );
+                                    set_ignored_launcher.set(String::new());
+                                }
+                            >
+                                "Ignore"
+                            </button>
+                        </div>
+                        <ul class="settings__list">
+                            {move || {
+                                preferences
+                                    .get()
+                                    .ignored_launchers
+                                    .into_iter()
+                                    .map(|launcher| {
+                                        let removed = launcher.clone();
+                                        view! {
+                                            <li class="settings__description">
+                                                {launcher}
+                                                " "
+                                                <button
+                                                    type="button"
+                                                    class="settings__button"
+                                                    on:click=move |_| {
+                                                        persist_launcher_ignored(
+                                                            removed.clone(),
+                                                            false,
+                                                            set_preferences,
+                                                            set_status_message,
+                                                        );
+                                                    }
+                                                >
+                                                    "Remove"
+                                                </button>
+                                            </li>
+                                        }
+                                    })
+                                    .collect::<Vec<_>>()
+                            }}
+                        </ul>
+                    </div>
+                    <div class="settings__utility">
+                        <div class="settings__details">
+                            <span class="settings__label">"Cleanup"</span>
+                            <span class="settings__description">
+                                {format!("Apps you haven't used in {STALE_APP_DAYS} days.")}
+                            </span>
+                        </div>
+                        <div class="settings__utility-row">
+                            <button
+                                type="button"
+                                class="settings__button"
+                                on:click=move |_| {
+                                    spawn_local(async move {
+                                        set_stale_apps.set(Some(load_stale_apps(STALE_APP_DAYS).await));
+                                    });
+                                }
+                            >
+                                "Find unused apps"
+                            </button>
+                        </div>
+                        {move || match stale_apps.get() {
+                            None => ().into_any(),
+                            Some(Err(err)) => {
+                                view! { <span class="settings__description">{err.to_string()}</span> }.into_any()
+                            }
+                            Some(Ok(apps)) if apps.is_empty() => {
+                                view! {
+                                    <span class="settings__description">"Every tracked app was used recently."</span>
+                                }
+                                    .into_any()
+                            }
+                            Some(Ok(apps)) => {
+                                view! {
+                                    <ul class="settings__list">
+                                        {apps
+                                            .into_iter()
+                                            .map(|(name, last_seen_ms)| {
+                                                view! {
+                                                    <li class="settings__description">
+                                                        {format!("{name} — last used {}", format_timestamp(last_seen_ms))}
+                                                    </li>
+                                                }
+                                            })
+                                            .collect::<Vec<_>>()}
+                                    </ul>
+                                }
+                                    .into_any()
+                            }
+                        }}
+                    </div>
+                    <div class="settings__utility">
+                        <div class="settings__details">
+                            <span class="settings__label">"Optimize storage"</span>
+                            <span class="settings__description">
+                                "Compact the local databases to reclaim unused space."
+                            </span>
+                        </div>
+                        <div class="settings__utility-row">
+                            <button
+                                type="button"
+                                class="settings__button"
+                                disabled=move || optimizing.get()
+                                on:click=move |_| {
+                                    set_optimizing.set(true);
+                                    spawn_local(async move {
+                                        let message = match vacuum_database().await {
+                                            Ok(bytes) => format!("Reclaimed {}.", format_bytes(bytes)),
+                                            Err(err) => err.to_string(),
+                                        };
+                                        set_optimize_status.set(Some(message));
+                                        set_optimizing.set(false);
+                                    });
+                                }
+                            >
+                                {move || if optimizing.get() { "Optimizing…" } else { "Optimize" }}
+                            </button>
+                        </div>
+                        {move || {
+                            optimize_status
+                                .get()
+                                .map(|message| {
+                                    view! { <span class="settings__description">{message}</span> }
+                                })
+                        }}
+                    </div>
+                    <div class="settings__utility">
+                        <div class="settings__details">
+                            <span class="settings__label">"Check data folder"</span>
+                            <span class="settings__description">
+                                "Confirm a folder exists or could be created, and is writable, without creating anything."
+                            </span>
+                        </div>
+                        <div class="settings__utility-row">
+                            <input
+                                type="text"
+                                class="settings__input"
+                                placeholder="Folder path"
+                                prop:value=move || data_dir.get()
+                                on:input=move |ev| set_data_dir.set(event_target_value(&ev))
+                            />
+                            <button
+                                type="button"
+                                class="settings__button"
+                                disabled=move || data_dir.get().trim().is_empty()
+                                on:click=move |_| {
+                                    let path = data_dir.get_untracked();
+                                    spawn_local(async move {
+                                        let message = match validate_data_dir(&path).await {
+                                            Ok(()) => "Folder is writable.".to_string(),
+                                            Err(err) => err.to_string(),
+                                        };
+                                        set_data_dir_status.set(Some(message));
+                                    });
+                                }
+                            >
+                                "Check"
+                            </button>
+                        </div>
+                        {move || {
+                            data_dir_status
+                                .get()
+                                .map(|message| {
+                                    view! { <span class="settings__description">{message}</span> }
+                                })
+                        }}
+                    </div>
+                    <div class="settings__utility">
+                        <div class="settings__details">
+                            <span class="settings__label">"Automatic backups"</span>
+                            <span class="settings__description">
+                                {move || {
+                                    match preferences.get().last_backup_ms {
+                                        Some(ms) => format!("Last backup: {}", format_timestamp(ms)),
+                                        None => "Write a backup bundle to a folder on a schedule. No backup yet.".to_string(),
+                                    }
+                                }}
+                            </span>
+                        </div>
+                        <div class="settings__utility-row">
+                            <select
+                                class="settings__input"
+                                prop:value=move || preferences.get().backup_schedule.key()
+                                on:change=move |ev| {
+                                    persist_preference(
+                                        "backup_schedule",
+                                        BackupSchedule::from_key(&event_target_value(&ev)),
+                                        set_preferences,
+                                        set_status_message,
+                                    );
+                                }
+                            >
+                                {BackupSchedule::ALL
+                                    .into_iter()
+                                    .map(|schedule| {
+                                        view! { <option value=schedule.key()>{schedule.label()}</option> }
+                                    })
+                                    .collect::<Vec<_>>()}
+                            </select>
+                            <input
+                                type="text"
+                                class="settings__input"
+                                placeholder="Backup folder"
+                                prop:value=move || preferences.get().backup_dir
+                                on:change=move |ev| {
+                                    persist_preference(
+                                        "backup_dir",
+                                        event_target_value(&ev).trim().to_string(),
+                                        set_preferences,
+                                        set_status_message,
+                                    );
+                                }
+                            />
+                            <button
+                                type="button"
+                                class="settings__button"
+                                disabled=move || preferences.get().backup_dir.is_empty()
+                                on:click=move |_| {
+                                    spawn_local(async move {
+                                        let message = match back_up_now().await {
+                                            Ok(updated) => {
+                                                set_preferences.set(updated);
+                                                "Backup written.".to_string()
+                                            }
+                                            Err(err) => err.to_string(),
+                                        };
+                                        set_backup_status.set(Some(message));
+                                    });
+                                }
+                            >
+                                "Back up now"
+                            </button>
+                        </div>
+                        {move || {
+                            backup_status
+                                .get()
+                                .map(|message| {
+                                    view! { <span class="settings__description">{message}</span> }
+                                })
+                        }}
+                    </div>
+                    <div class="settings__utility">
+                        <div class="settings__details">
+                            <span class="settings__label">"Backup bundle"</span>
+                            <span class="settings__description">
+                                "Export startup times, usage, and settings to one zip file, or restore them from one. Importing replaces the current data."
+                            </span>
+                        </div>
+                        <div class="settings__utility-row">
+                            <input
+                                type="text"
+                                class="settings__input"
+                                placeholder="Bundle file path, ending in .zip"
+                                prop:value=move || bundle_path.get()
+                                on:input=move |ev| set_bundle_path.set(event_target_value(&ev))
+                            />
+                            <button
+                                type="button"
+                                class="settings__button"
+                                disabled=move || bundle_path.get().trim().is_empty()
+                                on:click=move |_| {
+                                    let path = bundle_path.get_untracked();
+                                    spawn_local(async move {
+                                        let message = match export_bundle(path.trim()).await {
+                                            Ok(()) => "Bundle exported.".to_string(),
+                                            Err(err) => err.to_string(),
+                                        };
+                                        set_bundle_status.set(Some(message));
+                                    });
+                                }
+                            >
+                                "Export"
+                            </button>
+                            <button
+                                type="button"
+                                class="settings__button"
+                                disabled=move || bundle_path.get().trim().is_empty()
+                                on:click=move |_| {
+                                    let path = bundle_path.get_untracked();
+                                    spawn_local(async move {
+                                        let message = match import_bundle(path.trim()).await {
+                                            Ok(()) => {
+                                                if let Ok(stored) = fetch_preferences().await {
+                                                    set_preferences.set(stored);
+                                                }
+                                                "Bundle imported.".to_string()
+                                            }
+                                            Err(err) => err.to_string(),
+                                        };
+                                        set_bundle_status.set(Some(message));
+                                    });
+                                }
+                            >
+                                "Import"
+                            </button>
+                        </div>
+                        {move || {
+                            bundle_status
+                                .get()
+                                .map(|message| {
+                                    view! { <span class="settings__description">{message}</span> }
+                                })
+                        }}
+                    </div>
+                    <div class="settings__utility">
+                        <div class="settings__details">
+                            <span class="settings__label">"Reset all settings"</span>
+                            <span class="settings__description">
+                                "Restore every preference to its default. Startup times and usage history are kept."
+                            </span>
+                        </div>
+                        <div class="settings__utility-row">
+                            <button
+                                type="button"
+                                class="settings__button"
+                                on:click=move |_| {
+                                    let confirmed = web_sys::window()
+                                        .and_then(|window| {
+                                            window
+                                                .confirm_with_message(
+                                                    "Reset every setting to its default? This cannot be undone.",
+                                                )
+                                                .ok()
+                                        })
+                                        .unwrap_or(false);
+                                    if !confirmed {
+                                        return;
+                                    }
+                                    spawn_local(async move {
+                                        match reset_preferences().await {
+                                            Ok(defaults) => {
+                                                set_preferences.set(defaults);
+                                                set_status_message
+                                                    .set(Some("All settings were reset.".to_string()));
+                                            }
+                                            Err(err) => set_status_message.set(Some(err.to_string())),
+                                        }
+                                    });
+                                }
+                            >
+                                "Reset all settings"
+                            </button>
+                        </div>
+                    </div>
+                    <div class="settings__diagnostics">
+                        <span class="settings__label">"Diagnostics"</span>
+                        <span class="settings__description">
+                            {move || {
+                                coverage_text
+                                    .get()
+                                    .unwrap_or_else(|| "Measuring tracking coverage…".to_string())
+                            }}
+                        </span>
+                        {move || {
+                            unclean_exits
+                                .get()
+                                .map(|count| {
+                                    view! {
+                                        <span class="settings__description">
+                                            {unclean_exit_label(count)}
+                                        </span>
+                                    }
+                                })
+                        }}
+                        <span class="settings__description">
+                            "Recent errors, newest first. Copy them into a bug report."
+                        </span>
+                        {move || {
+                            let errors = recent_errors.get();
+                            if errors.is_empty() {
+                                view! { <span class="settings__description">"No errors logged."</span> }
+                                    .into_any()
+                            } else {
+                                view! {
+                                    <ul class="settings__errors">
+                                        {errors
+                                            .into_iter()
+                                            .map(|error| view! { <li>{error}</li> })
+                                            .collect::<Vec<_>>()}
+                                    </ul>
+                                }
+                                    .into_any()
+                            }
+                        }}
+                        <div class="settings__utility-row">
+                            <button
+                                type="button"
+                                class="settings__button"
+                                on:click=move |_| {
+                                    spawn_local(async move {
+                                        if let Ok(errors) = load_recent_errors(RECENT_ERRORS_SHOWN).await {
+                                            set_recent_errors.set(errors);
+                                        }
+                                    });
+                                }
+                            >
+                                "Refresh"
+                            </button>
+                            <button
+                                type="button"
+                                class="settings__button"
+                                disabled=move || recent_errors.with(Vec::is_empty)
+                                on:click=move |_| {
+                                    let text = recent_errors.get_untracked().join("\n");
+                                    spawn_local(async move {
+                                        if let Err(err) = copy_text(&text).await {
+                                            set_status_message.set(Some(err.to_string()));
+                                        }
+                                    });
+                                }
+                            >
+                                "Copy"
+                            </button>
+                            <button
+                                type="button"
+                                class="settings__button"
+                                disabled=move || recent_errors.with(Vec::is_empty)
+                                on:click=move |_| {
+                                    spawn_local(async move {
+                                        match clear_error_log().await {
+                                            Ok(()) => set_recent_errors.set(Vec::new()),
+                                            Err(err) => set_status_message.set(Some(err.to_string())),
+                                        }
+                                    });
+                                }
+                            >
+                                "Clear"
+                            </button>
+                        </div>
+                    </div>
+                    <Show when=move || status_message.get().is_some()>
+                        {move || {
+                            status_message
+                                .get()
+                                .map(|message| view! { <p class="settings__status">{message}</p> })
+                        }}
+                    </Show>
+                </div>
+            </section>
+        </main>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn time_input_round_trips_minutes() {
+        assert_eq!(minutes_to_time_input(18 * 60 + 5), "18:05");
+        assert_eq!(time_input_to_minutes("18:05"), Some(18 * 60 + 5));
+        assert_eq!(time_input_to_minutes("24:00"), None);
+        assert_eq!(time_input_to_minutes("noon"), None);
+    }
+
+    #[test]
+    fn unclean_exit_label_counts_exits() {
+        assert_eq!(unclean_exit_label(0), "No unexpected exits this week");
+        assert_eq!(unclean_exit_label(1), "1 unexpected exit this week");
+        assert_eq!(unclean_exit_label(3), "3 unexpected exits this week");
+    }
+
+    #[test]
+    fn parse_app_list_trims_and_drops_blanks() {
+        assert_eq!(
+            parse_app_list(" Journal, ,Code "),
+            vec!["Journal".to_string(), "Code".to_string()]
+        );
+    }
+
+    #[test]
+    fn parse_avatar_accepts_one_letter_or_icon() {
+        assert_eq!(parse_avatar(" t "), Some("T".to_string()));
+        assert_eq!(parse_avatar("ICON"), Some(AVATAR_ICON.to_string()));
+        assert_eq!(parse_avatar("AB"), None);
+        assert_eq!(parse_avatar(""), None);
+    }
+
+    #[test]
+    fn parse_hour_accepts_midnight_through_eleven_pm() {
+        assert_eq!(parse_hour("0"), Some(0));
+        assert_eq!(parse_hour(" 23 "), Some(23));
+        assert_eq!(parse_hour("24"), None);
+        assert_eq!(parse_hour("-1"), None);
+    }
+
+    #[test]
+    fn parse_bounded_count_accepts_only_supported_range() {
+        assert_eq!(parse_bounded_count(" 8 ", MAX_CHART_BARS), Some(8));
+        assert_eq!(parse_bounded_count("0", MAX_CHART_BARS), None);
+        assert_eq!(parse_bounded_count("25", MAX_CHART_BARS), None);
+        assert_eq!(parse_bounded_count("many", MAX_CHART_BARS), None);
+    }
+
+    #[test]
+    fn parse_duration_precision_allows_zero_up_to_the_maximum() {
+        assert_eq!(parse_duration_precision("0"), Some(0));
+        assert_eq!(parse_duration_precision(" 3 "), Some(3));
+        assert_eq!(parse_duration_precision("4"), None);
+        assert_eq!(parse_duration_precision("-1"), None);
+    }
+
+    #[test]
+    fn autostart_pause_ends_at_its_deadline() {
+        assert!(!is_autostart_paused(None, 1_000));
+        assert!(is_autostart_paused(Some(2_000), 1_000));
+        assert!(!is_autostart_paused(Some(2_000), 2_000));
+    }
+
+    #[test]
+    fn format_bytes_picks_readable_unit() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KB");
+        assert_eq!(format_bytes(5 * 1024 * 1024), "5.0 MB");
     }
 }
@@ -4,7 +4,7 @@ use leptos::task::spawn_local;
 use wasm_bindgen::JsValue;
 
 use crate::domain::app_usage_record::AppUsageRecord;
-use crate::infrastructure::tauri_adapter::load_app_usage_records;
+use crate::infrastructure::metrics_adapter::load_app_usage_records;
 
 fn to_pretty_json(records: &[AppUsageRecord]) -> String {
     serde_json::to_string_pretty(records).unwrap_or_else(|_| "[]".to_string())
@@ -35,8 +35,8 @@ pub fn UsageView() -> impl IntoView {
                             .into();
                         set_last_loaded.set(Some(timestamp));
                     }
-                    Err(error_message) => {
-                        set_load_error.set(Some(error_message));
+                    Err(error) => {
+                        set_load_error.set(Some(error.to_string()));
                     }
                 }
                 set_loading.set(false);